@@ -0,0 +1,138 @@
+// Generado con `dappcenvote-identity constants`; no editar a mano
+
+// Niveles del árbol de identidades de cada grupo
+pub const TREE_DEPTH: u32 = 16;
+
+// Constantes de ronda de MiMC: c₀ = 0 y cᵢ = sha256("dappcenvote/mimc" || i) mod r
+pub const MIMC_CONSTANTS: [[u64; 4]; 110] = [
+    [0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000],
+    [0x17d773dd9072abf4, 0x76f3476436d45160, 0xab4e0351cbca00ba, 0xa7b706d0aba75e99],
+    [0x2eab160bed12b43f, 0x10e1019035823d54, 0x483c1ffec85b631b, 0x3e9f3c7668e86d9b],
+    [0x4f6550df9cea8058, 0x754f04cbd8da0784, 0xa69af7a114eca829, 0x515cccc4c3d027eb],
+    [0x6999417027421162, 0xe684f102622e663a, 0x34892c3018566658, 0xf757e4602db1ea3d],
+    [0x3d8ce8e50b8ab11a, 0xdbeeb7b7dfb963ed, 0x2630fc29e7fb11fc, 0x1730d048663afc48],
+    [0x4abcb004a3251f69, 0x8f8712b58035e9c9, 0x7966562b6a445a5b, 0x70708a6eced37fa7],
+    [0x5fb1ba5990efba91, 0xb8555d16a8db2a18, 0x40548b74a80055f1, 0x71e35a7304a06435],
+    [0x2362ff133639f66b, 0x33fcc30ddba31f44, 0x76fa9a6d124e5d38, 0x6e8486e8cb5de30a],
+    [0x11e2270dad265deb, 0x1892ea54fc17c26d, 0x5b32cbd8c848d6c8, 0xc6aef5ba366eac86],
+    [0x16d0288e58569bb0, 0xbd2f0824b28b0880, 0x28541746261730e6, 0x58805be1369e8c1b],
+    [0x442dedcd18a3b1e2, 0x6d98573412db51f8, 0x9550f78a117aa0a7, 0xc30fcd3fcf3361c6],
+    [0x10809e5f0b2d8e1c, 0x2c5900711948828c, 0x13bf7d0deb72bfbd, 0x1724c495ef3d5407],
+    [0x029f84e3f38b036d, 0x6be61ecf60c97355, 0x6956e5ee9c049fcd, 0x37f0023f1ba39f40],
+    [0x1fe0912e014db79e, 0x20c0d4dbb107673d, 0xe30fc1c90d38f185, 0x208853579b264aa4],
+    [0x43c0654c8f76beaf, 0x550ce24c5859c67a, 0x81bc10a8c02413cc, 0x202fdea5ec2dc911],
+    [0x60f9714f7b086ba1, 0x1ea9f7e7500436b0, 0xfc86fb91eec70978, 0x0a7c3696846c71f2],
+    [0x400d3834b64c396a, 0xef5361c1bfa71d39, 0xbcf99138a2b8e323, 0x9064a0063386f0ce],
+    [0x5b7aa97cdfe1a8cc, 0x8336b28d5b2854f8, 0xe85a50abdfaad3a8, 0x135645258a94f894],
+    [0x09d45def4360f73d, 0x4a0150bd91326230, 0x2d661a6432e7648c, 0xb6ca17830b60484a],
+    [0x5e866f90b8306b70, 0x4b5c84d81e4658e5, 0x01638406e3d439ad, 0xfb72924d5fd42556],
+    [0x6cc7e2332d3775e3, 0xc732ff1d37e72101, 0x2f4a7490f9c1ff6d, 0x3d730a1c65898d8a],
+    [0x44474e8441c72204, 0x5dc56a949e8afa46, 0x997ea05eddd8b375, 0x31cd75742e09387b],
+    [0x701e1e501d77e71f, 0x32415cde9059799e, 0x992848a56fe7e140, 0x5348a955844dc349],
+    [0x69cedb80fd10bf30, 0xa11b79546a8e972f, 0x5b84e3a5b14b263e, 0x54b2ec62c2da5bde],
+    [0x37820245e877164f, 0x3eb09f3e391ffff8, 0x29185ab56e2c5890, 0x7877935b8bc73865],
+    [0x2b84be435c6e8628, 0xcfe6d2eeec64a528, 0xe82765d65d9c189b, 0x53a9cea03f21b814],
+    [0x114cda87e1041d25, 0xc1094227cf0c42d8, 0xe8e17ebb47b924c7, 0xebc3667265784e4b],
+    [0x5f81d1658306bcf1, 0xc7f0a32acc8c7127, 0xf0d6341120da3f87, 0xf9e3f8a08f3ea752],
+    [0x545703332b541085, 0xd6740f0ed089ebfc, 0xaad29c31981c1bf4, 0xbcb208d128035e89],
+    [0x606cb3f260732aca, 0x323662572ef37f1c, 0x76c62129780a1081, 0x110edff10eda6740],
+    [0x102ac2b18b2690f9, 0x1779b7cda9f66ca1, 0xb1d4c90351b55a5e, 0xa84ffed72caba17f],
+    [0x092f4b9b56b3ee41, 0xb1261e3de6a58177, 0x7adce74b5be2e24b, 0xaa59a84de2c92b9b],
+    [0x0c6b3d26a1af2750, 0x448e4da750b9af44, 0xc3fe58b3c64f8852, 0x9faca5940a7b592f],
+    [0x6de61e1be4240941, 0xb49556c8f0a06fca, 0x2b2b499da31e01ef, 0xebd6b08b160cb387],
+    [0x4c57c1e5ddb79dd1, 0x91f6c9830024dabb, 0x2e389cbcb2d94127, 0x8999cc0238fec0a2],
+    [0x071d13d0a662b6ea, 0x23bd612609089d39, 0x6b4b525db41131b5, 0xfbee83fb15a06dcc],
+    [0x0aa04ea341d54f7c, 0xc19e3914a51ce907, 0xa80e099ed47b541e, 0xce4315bbf68c32e3],
+    [0x078b6879b1c1cc75, 0x9e3acc90c505ece5, 0x75fbcd5c06fc3dd7, 0x82fd065ffa25f47e],
+    [0x55d15b1c70ec7353, 0x8db13387591427be, 0x61127715f7fa906f, 0xefa3784ed58b583b],
+    [0x6e857ff60558c6e3, 0xd907963bf34e6321, 0x429bcad52ff59c3d, 0x21cd9d45085b5699],
+    [0x103279561afe9f1c, 0x83aa67e6e5ed466e, 0x65f8ddf4dc30140e, 0xe9fb5fa73fe88e9e],
+    [0x0bbb1ae1a1abf898, 0xf2b013057d243f91, 0x03e2e5fe277f5f0f, 0x72976b59c713d130],
+    [0x253fa77a4ee183f6, 0x4d51e22ac20e7e11, 0xd5d5ebb9edf01361, 0x238ebdcab80fe3fa],
+    [0x1d7810a3c287ee60, 0xd9404067f9ce9659, 0xc9ca48ece659ef57, 0x0cc523118dc0a0c7],
+    [0x1a35ff1cc0b2844d, 0x8cbd0af0387700b4, 0xb3ccd9288078e46c, 0x82e442d043f2def4],
+    [0x378b8f3fdaadbb31, 0x4d7ec71829ea2feb, 0xcbb4df5dd03affa4, 0x1396300cea5740aa],
+    [0x318bae0daae173cf, 0x74f3d7b0e3e99932, 0x795dafecc66a47ae, 0x280903f42d677014],
+    [0x4fd535d7ff2da95f, 0x9a32a4b8d3cd5408, 0xa7c7895b79525230, 0x95ef5e0539a7f8ae],
+    [0x0f4f79a882d201ab, 0xcdd7b53607f0ad62, 0x18bb850c7c745afe, 0x0ee49b0e4ff357e3],
+    [0x5f475f9d8b21f0b1, 0xbd59656c81525932, 0x752baae681da0dc5, 0x6e72594309e767f3],
+    [0x5bdb0b39dccf3d5a, 0xd0b13f5ad8f85a1a, 0xfd6eb2c534e805fa, 0x27e378374ea18cc5],
+    [0x2fe9bc5aab79cd03, 0x671f4b403af5035b, 0x4fe23469855b92e5, 0x36c6e7bc599690c0],
+    [0x3bfdefdcdb7a5f10, 0x01ef169121978905, 0xd8f7cdfed802b551, 0x1a0d9503079c6285],
+    [0x03066f5ac17d6447, 0xd169b49277831cd4, 0xbad8148be4a985b7, 0x842e126a482c706a],
+    [0x5528409fa3d265cd, 0x1c5945ffe714e7cd, 0xb90a30b4f473c66b, 0x91a322cbfd7bf84c],
+    [0x444d0ac990d91c71, 0x8fa97f4f58106f0b, 0xebd1d371d3c9008d, 0xe1e46dfb39d15476],
+    [0x670010f6d564a20d, 0x9cf4289ae219215c, 0x6fa1d4d90090b14e, 0x261275ec6d7db264],
+    [0x33b38c21477824d7, 0x9cf131279eefda92, 0x482a964cc4219fe7, 0x6efbe2a1fbe8a76f],
+    [0x679196f0b344f557, 0x34d6cb679b20a1b1, 0x603455183da940b3, 0xf8cf1b7974037c0e],
+    [0x617dd3ac375528d3, 0xe3dc220ab44ab074, 0x403cf4d23ff628c0, 0x1ed84391c1aa48db],
+    [0x0145f9947489f348, 0x0b9aae8cb25fb61c, 0x18419c36283a9c78, 0x49d97946cac911d2],
+    [0x031fd97e23491176, 0x115095ec2046ea82, 0xa9efba435a2db35a, 0xef2704b8a0a3577f],
+    [0x708b058a0b385884, 0x68c22204af313a38, 0x36fdcaa91d16b207, 0x66da2bec34572a8e],
+    [0x1d82dfeabd7f6e31, 0xb05d50cadd83f672, 0x00feb9d8c2b95629, 0xd05dc1e94534eb3b],
+    [0x4e6d8ea2dd94b59e, 0xf901faf310960e3b, 0xbf0570c8046c40fe, 0x17afd93bfaa138d2],
+    [0x1e547ddcf9758349, 0xe04fda0f696827d4, 0xfccbb2101c371fa3, 0x09c67efaf6ae7695],
+    [0x2a9b2d10640ca269, 0x75230518a447031a, 0x09917bca97b2d159, 0x6a7da6015a4ed18e],
+    [0x4cec3a3bf383c2f0, 0xdba2acf504ce8153, 0x3500098a7f2cb3a8, 0xa12f98c5358f7a2c],
+    [0x5a05a98deb291b61, 0x4a636db045dd716d, 0xed4fd9d233c14bad, 0x9874634089824336],
+    [0x64cf173ba5aa72a6, 0xaead80f943967f51, 0x7d57a2705dbc9595, 0x7ad4095aa381a627],
+    [0x7116deea3815d22c, 0xecbb6718ddf20b2d, 0xf64f21a619a4f73c, 0xcd57129d38e7c825],
+    [0x5e1aeeb0036fc6ae, 0x5206418031b9bcd5, 0x64016ae08b41c853, 0xc26f45f1f90f4930],
+    [0x6300a9af03b77e98, 0x32abcc6d269a7f71, 0x1c82ee2f9180003a, 0x9670d10a2e89ee0b],
+    [0x5e92a7891ea90013, 0xff050e11435d7983, 0x75757f57c4b11d0e, 0x15e3049cfad7da0c],
+    [0x1f031365bafb5d66, 0x63afe7ed13c0be96, 0xe192ec21d638c5f4, 0x4a348d766bb0debe],
+    [0x72648e4fc6d3fd11, 0x63465ba7fd37fc7a, 0xbb9a35e2b6eb2293, 0xaa62d61e219db53c],
+    [0x0e7a4034b09aa7f7, 0x6122731a4e8bd40e, 0xe29acea5aba40945, 0xfe413b958dc2072e],
+    [0x115b46448dccf2bc, 0x0e6e7bededf92e2a, 0xfcd7638ea25e160e, 0xee27bd57725b49a7],
+    [0x63f418b413196c1c, 0xfb80b841020d58e1, 0x27346702c9a3865e, 0xbe1e6df45399739d],
+    [0x5241d8d7ce7277a7, 0x70683139843364bb, 0xbb990698ec2003f6, 0xe367ae9f47a881e8],
+    [0x014c1cfd94a8b749, 0xfd6055913118036d, 0x23dc0a1272a8304c, 0x3405b24438b7fb0a],
+    [0x3a0795c71136d9d7, 0x2c7535a39dac7efc, 0xd8e0b95b5b88fe82, 0x20f61210bbc912d1],
+    [0x4fa422c3216cfe0b, 0x7b11b26139b832fb, 0x3d57549c6bc4022d, 0xa56e1ca888791b68],
+    [0x4fe44618e9bcac02, 0x0b220980a7a5ffc6, 0xa6fbae8eb4843870, 0x87c41d7c54012aee],
+    [0x00a5e5e35b167b26, 0x418eda658620c93a, 0xbf5e80dc332a5ab9, 0xebd7ceffb51e40bd],
+    [0x26eedc17500def8c, 0xc29509176151b00a, 0x6c277fc9c8cbcb72, 0xa93aac5b2d0fb84b],
+    [0x6cd4080369a395a3, 0xf1a599709641b7d0, 0xfc5917c2b5ebc5ec, 0xa7b8cc3abad37d9b],
+    [0x50d0f01ecb84dd49, 0xb7842195d0083569, 0x44c8cec26a307b5e, 0xc640fa06ca349ca8],
+    [0x5ff3b3857b306cba, 0x1d1302dfd2f5539d, 0xa443efea379e96f2, 0x5dbc81b02bde8ff1],
+    [0x5f5835f210be5a47, 0xbc0afe4a9c87be4b, 0x7932fc06d5e317be, 0xa843dabf64e26ac7],
+    [0x623dccdc127f36e4, 0x4691c7c4022a3781, 0x9a6427f553169f52, 0x90c0b472458d10fa],
+    [0x47af0cc341459cda, 0x5e46d026ad2d17fe, 0x0e0a74936840178b, 0x50ec04352cda93a5],
+    [0x4243ce417bb2eb18, 0xdab0f97eee09ad82, 0x917e41981fcc6c07, 0xe9c7b9a3a671eaec],
+    [0x3c89cc6d8baad808, 0xffbe1b300398805b, 0xa37c1e98cb67c2ce, 0x65e5953a3067a4e1],
+    [0x29c6204371818687, 0xaf1181124b10d4df, 0x92ef8f6c410fbeac, 0x53b7d227843f7e17],
+    [0x4fbcc2c6aaf9f213, 0x0657803c4424a173, 0x853043a0d9e65196, 0x9eb37444845395b7],
+    [0x5549cf2696021b5d, 0xb02aa1571f8a2408, 0x486298218bfa2145, 0x0a2026c09be1deaa],
+    [0x61ee8e3b419a6c0b, 0xfa0194bd6e4793ac, 0xa8e30d062d9a250c, 0xd205159ad0cb6c33],
+    [0x0a4631da7973959c, 0x1498202ff3cde4e3, 0xb3a2fada7c29972e, 0x0299944ef0c216a9],
+    [0x25edb8a14418d2d8, 0xb407d85117245214, 0x09e31fe97d338a95, 0x755a54552c077caa],
+    [0x156f579cbcf4e348, 0x2ea372952ba65806, 0x709ed7dc09ec1ab7, 0xffa97b194844984e],
+    [0x04db211c02bdbf8d, 0x9abeb75dc6865de9, 0xa7f8004a002239d4, 0x3c526ce70bcc70a5],
+    [0x101ca88d0e240ffb, 0x8b2d16d3c6d48801, 0x986d9f8bcf48cbac, 0x3f370d0825ea4aea],
+    [0x1c128992fe8915cc, 0xe4b8c59d0028d3e7, 0xd534145bea5626aa, 0xd61ecc9a824397cc],
+    [0x6ecc966955a325fd, 0xb1ae123a3f02c56a, 0xc484e7f334c753dd, 0x00c41edd9595dcf7],
+    [0x6efaf3506711a029, 0xdfba8979caf7540b, 0xfb010878d6a7a510, 0x40d6bcbdd32be06e],
+    [0x1868813bdbae161e, 0x819bd31651edf9ee, 0x7748f16c0457341b, 0xf9f4ca920b0b3802],
+    [0x18325e9f6feb3ead, 0x91469e90ac041258, 0xcf537606f22e3542, 0x4e51c88490d137a7],
+    [0x2adccfbaf266c34f, 0x1a7ae1040c3a315f, 0x1dccb126cff24978, 0xa8a30baab2eb8261],
+];
+
+// Nodos de un subárbol vacío en cada nivel
+pub const ZEROS: [[u64; 4]; 16] = [
+    [0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000],
+    [0x033b78ad3badc55f, 0x86c56b271a3ce543, 0xcdbca367d0ade392, 0xfdf671c5b13a44c5],
+    [0x1eab74facc7724cb, 0x5eaf382dccfaef5f, 0xa2b745750eb75dcd, 0xa1f7aa6e4c83bdba],
+    [0x4c4217095ea8d1c2, 0x5b1e7c15b9acd9ab, 0xd107502bd91e998c, 0x4f6fb002d2421791],
+    [0x600d936f4e02fe45, 0xaa720e3ee65e38e9, 0x901a7426143523af, 0xacb233c229ec6743],
+    [0x04ef89c99bd437d7, 0x7bea1fee80c060cf, 0x5c25666ab47733fb, 0x9336e60cc2b0ec90],
+    [0x632d925ab4135a39, 0xf396bd2b444a0f5a, 0x75b40e088f2f95a7, 0xed5cf094e3c7a54b],
+    [0x070879c53d59dbff, 0xa76aa6e5fcfa0af2, 0xbf97d7d4a44c253c, 0x236cfc34ed0aaabb],
+    [0x08d53724452da404, 0xc821f4a3fdf4f03b, 0x9f1e5b6f8545431a, 0x7cda5779f498ad5b],
+    [0x4c53717b822149d8, 0x2065ee920cb1aebd, 0x111240d98a6107cd, 0x8ed58daa923fe343],
+    [0x4eddefe52283b477, 0x1b03ca58ff3fc6d4, 0xed6b47f980e8a7e9, 0x81163ca5f172f5e0],
+    [0x70ec0251fe7879c2, 0x44f1edf1d330ff1b, 0x33f9d9d0c7fafc13, 0xbf6c0d278277c773],
+    [0x12a5ac294b757296, 0x56a7ebc1255afa62, 0xa2e305d3898ecaa5, 0x617530833181372b],
+    [0x4c92cf41cfe3045b, 0x7e8e5b1271496d59, 0x4fe1d052fa0b3bd4, 0xb2c22167ec5701f6],
+    [0x4558f4d175a52aef, 0x62f346dfff2cd1a7, 0x5bd80ca90841184f, 0x346e8c687f4c9135],
+    [0x49630379e38f7788, 0xe7954a1c1f4417e6, 0x9a5e0857c36f1f82, 0x6ea43b21690bdc60],
+];
@@ -0,0 +1,631 @@
+#![no_std]
+//! Urna anónima para encuestas de candidatos, al estilo de Semaphore. Antes del
+//! inicio, cada usuario que podría votar añade al grupo de la encuesta el
+//! compromiso de una identidad que guarda en secreto. Para votar basta una
+//! prueba Groth16 de que se conoce una identidad del árbol, junto con su
+//! nullifier para la encuesta; el contrato la verifica con las funciones host
+//! de BLS12-381 y nunca sabe quién vota ni exige su firma, así que cualquier
+//! cuenta puede enviar el voto.
+//!
+//! El árbol de Merkle usa el mismo MiMC que `dappcenvote-identity`, que genera
+//! las identidades, las pruebas y `constants.rs`.
+mod constants;
+
+use constants::{MIMC_CONSTANTS, TREE_DEPTH, ZEROS};
+use dappcenvote_types::{
+    anonymous_vote::{
+        AnonymousVoteCast, AnonymousVoteError, AnonymousVoteInterface, DataKey, Groth16Proof, Group,
+        GroupCreated, MemberAdded, TallyPublished, VerifyingKey,
+    },
+    survey::{Survey, SurveyClient},
+    user_registry::UserRegistryClient,
+    PauseScope,
+};
+use soroban_sdk::{
+    contract, contractimpl,
+    crypto::bls12_381::{Fr, G1Affine, G2Affine},
+    panic_with_error, vec, Address, BytesN, Env, Vec, U256,
+};
+
+const CONTRACT_VERSION: u32 = 1;
+
+// Orden r del grupo de BLS12-381: los elementos de Fr deben ser menores
+const FR_MODULUS: [u64; 4] = [0x73eda753299d7d48, 0x3339d80809a1d805, 0x53bda402fffe5bfe, 0xffffffff00000001];
+
+#[contract]
+pub struct AnonymousBallotBox;
+
+#[contractimpl]
+impl AnonymousBallotBox {
+    /// Inicializa el contrato al desplegarlo, con el administrador y el contrato de encuestas
+    pub fn __constructor(env: Env, admin: Address, survey_contract: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        env.storage().instance().set(&DataKey::Survey, &survey_contract);
+        env.storage().instance().extend_ttl(100, 8640000);
+    }
+}
+
+#[contractimpl]
+impl AnonymousVoteInterface for AnonymousBallotBox {
+    /// Reemplaza el código del contrato conservando su almacenamiento (solo administrador)
+    /// Debe seguirse de `migrate` si la nueva versión cambia el formato de los datos
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Migra el almacenamiento a la versión de este código (solo administrador)
+    /// Sin efecto si el almacenamiento ya está en esa versión
+    /// La versión 1 es el formato original, por lo que aún no hay transformaciones
+    fn migrate(env: Env) {
+        Self::require_admin(&env);
+
+        let version = Self::version(env.clone());
+        if version >= CONTRACT_VERSION {
+            return; // Ya está al día; repetir la llamada no es un error
+        }
+
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+    }
+
+    /// Obtiene la versión del formato de almacenamiento
+    fn version(env: Env) -> u32 {
+        let key = DataKey::ContractVersion;
+        env.storage().instance().get(&key).unwrap_or(1)
+    }
+
+    /// Fija la clave de verificación del circuito de voto (solo administrador)
+    /// Sale de la configuración de confianza de `dappcenvote-identity setup`
+    /// Solo la usan los grupos creados después; los existentes conservan la suya
+    fn set_verifying_key(env: Env, verifying_key: VerifyingKey) {
+        Self::require_admin(&env);
+
+        if verifying_key.ic.len() != 5 {
+            panic!("Verifying key must have one IC point per public input plus one");
+        }
+        env.storage().instance().set(&DataKey::VerifyingKey, &verifying_key);
+        env.storage().instance().extend_ttl(100, 8640000);
+    }
+
+    /// Obtiene la clave de verificación de los nuevos grupos (si ya se fijó)
+    fn get_verifying_key(env: Env) -> Option<VerifyingKey> {
+        env.storage().instance().get(&DataKey::VerifyingKey)
+    }
+
+    /// Convierte una encuesta en votación anónima (solo el creador, antes del inicio)
+    /// Desde entonces la encuesta rechaza los votos en claro, y el grupo verifica
+    /// los votos con la clave vigente en este momento
+    fn create_group(env: Env, survey_id: u64) {
+        let survey_client = Self::survey_client(&env);
+        let survey = survey_client.get_survey(&survey_id).expect("Survey not found");
+        survey.creator.require_auth();
+
+        let group_key = DataKey::Group(survey_id);
+        if env.storage().persistent().has(&group_key) {
+            panic!("Group already exists");
+        }
+        let verifying_key = Self::get_verifying_key(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, AnonymousVoteError::NoVerifyingKey));
+
+        // La encuesta valida que siga en borrador y que tenga candidatos
+        survey_client.set_ballot_box(&survey_id, &env.current_contract_address());
+
+        let top = Self::constant(&env, &ZEROS[TREE_DEPTH as usize - 1]);
+        let group = Group {
+            survey_id,
+            size: 0,
+            root: Self::hash(&env, &top, &top).to_bytes(),
+            ballots: 0,
+            tallied: false,
+        };
+        Self::save_group(&env, &group);
+        Self::set_persistent(&env, &DataKey::GroupKey(survey_id), &verifying_key);
+
+        let mut filled = Vec::new(&env);
+        for zero in ZEROS.iter() {
+            filled.push_back(Self::constant(&env, zero).to_bytes());
+        }
+        Self::set_persistent(&env, &DataKey::FilledSubtrees(survey_id), &filled);
+        let mut counts = Vec::new(&env);
+        for _ in survey.candidates.iter() {
+            counts.push_back(0u64);
+        }
+        Self::set_persistent(&env, &DataKey::Counts(survey_id), &counts);
+
+        GroupCreated { survey_id }.publish(&env);
+    }
+
+    /// Añade al grupo el compromiso de identidad de un usuario, antes del inicio
+    /// Se exige lo mismo que en `vote` (electorado y registro); cada usuario aporta
+    /// un solo compromiso, pero nada lo liga después a su voto
+    fn join_group(
+        env: Env,
+        survey_id: u64,
+        user: Address,
+        commitment: BytesN<32>,
+        proof: Option<Vec<BytesN<32>>>,
+    ) {
+        user.require_auth();
+
+        let survey_client = Self::survey_client(&env);
+        if survey_client.is_paused(&PauseScope::Registration) {
+            panic_with_error!(&env, AnonymousVoteError::Paused);
+        }
+        let mut group = Self::load_group(&env, survey_id);
+        let survey = Self::load_survey(&env, survey_id);
+        if env.ledger().timestamp() >= survey.start_date {
+            panic!("Survey has already started");
+        }
+        if survey_client.is_cancelled(&survey_id) {
+            panic!("Survey has been cancelled");
+        }
+        if !survey_client.is_eligible(&survey_id, &user, &proof) {
+            panic_with_error!(&env, AnonymousVoteError::NotEligible);
+        }
+        if let Some(registries) = survey_client.get_registries() {
+            if !UserRegistryClient::new(&env, &registries.user_registry).user_exists(&user) {
+                panic_with_error!(&env, AnonymousVoteError::NotRegistered);
+            }
+        }
+
+        let member_key = DataKey::Member(survey_id, user);
+        if env.storage().persistent().has(&member_key) {
+            panic_with_error!(&env, AnonymousVoteError::AlreadyMember);
+        }
+        let leaf = Self::field(&env, &commitment);
+        let commitment_key = DataKey::Commitment(survey_id, commitment.clone());
+        if env.storage().persistent().has(&commitment_key) {
+            panic_with_error!(&env, AnonymousVoteError::DuplicateCommitment);
+        }
+        if group.size >= 1 << TREE_DEPTH {
+            panic_with_error!(&env, AnonymousVoteError::GroupFull);
+        }
+
+        // Árbol incremental: en cada nivel se guarda el último hijo izquierdo
+        let filled_key = DataKey::FilledSubtrees(survey_id);
+        let mut filled: Vec<BytesN<32>> = env.storage().persistent().get(&filled_key).unwrap();
+        let mut node = leaf;
+        for (level, zero) in ZEROS.iter().enumerate() {
+            let level = level as u32;
+            node = if (group.size >> level) & 1 == 0 {
+                filled.set(level, node.to_bytes());
+                Self::hash(&env, &node, &Self::constant(&env, zero))
+            } else {
+                Self::hash(&env, &Fr::from_bytes(filled.get(level).unwrap()), &node)
+            };
+        }
+        Self::set_persistent(&env, &filled_key, &filled);
+
+        let members_key = DataKey::Members(survey_id);
+        let mut members = Self::get_members(env.clone(), survey_id);
+        members.push_back(commitment.clone());
+        Self::set_persistent(&env, &members_key, &members);
+        Self::set_persistent(&env, &member_key, &true);
+        Self::set_persistent(&env, &commitment_key, &true);
+
+        let index = group.size;
+        group.size += 1;
+        group.root = node.to_bytes();
+        Self::save_group(&env, &group);
+        MemberAdded { survey_id, commitment, index, root: group.root }.publish(&env);
+    }
+
+    /// Cuenta un voto anónimo durante la votación (cualquiera puede enviarlo)
+    /// La prueba liga la raíz del grupo, el nullifier, la encuesta y el candidato
+    fn cast_vote(
+        env: Env,
+        survey_id: u64,
+        candidate: Address,
+        nullifier_hash: BytesN<32>,
+        proof: Groth16Proof,
+    ) -> BytesN<32> {
+        let survey_client = Self::survey_client(&env);
+        if survey_client.is_paused(&PauseScope::Voting) {
+            panic_with_error!(&env, AnonymousVoteError::Paused);
+        }
+        let mut group = Self::load_group(&env, survey_id);
+        let verifying_key = Self::get_group_verifying_key(env.clone(), survey_id).unwrap();
+
+        let survey = Self::load_survey(&env, survey_id);
+        let current_time = env.ledger().timestamp();
+        if current_time < survey.start_date {
+            panic!("Survey has not started yet");
+        }
+        if current_time > survey.end_date {
+            panic!("Survey has ended");
+        }
+        if survey_client.is_cancelled(&survey_id) {
+            panic!("Survey has been cancelled");
+        }
+        let index = survey.candidates.first_index_of(&candidate)
+            .expect("Candidate not found in survey");
+
+        let nullifier = Self::field(&env, &nullifier_hash);
+        let nullifier_key = DataKey::Nullifier(survey_id, nullifier_hash.clone());
+        if env.storage().persistent().has(&nullifier_key) {
+            panic_with_error!(&env, AnonymousVoteError::AlreadyVoted);
+        }
+        let inputs = vec![
+            &env,
+            Fr::from_bytes(group.root.clone()),
+            nullifier,
+            Self::scalar(&env, survey_id),
+            Self::scalar(&env, index as u64),
+        ];
+        if !Self::verify_proof(&env, &verifying_key, &inputs, &proof) {
+            panic_with_error!(&env, AnonymousVoteError::InvalidProof);
+        }
+        Self::set_persistent(&env, &nullifier_key, &true);
+
+        let counts_key = DataKey::Counts(survey_id);
+        let mut counts: Vec<u64> = env.storage().persistent().get(&counts_key).unwrap();
+        counts.set(index, counts.get(index).unwrap() + 1);
+        Self::set_persistent(&env, &counts_key, &counts);
+
+        group.ballots += 1;
+        Self::save_group(&env, &group);
+        AnonymousVoteCast { survey_id, nullifier_hash: nullifier_hash.clone(), candidate }.publish(&env);
+
+        // El recibo queda a nombre de la urna y ligado al nullifier, no al votante
+        survey_client.record_receipt(&survey_id, &env.current_contract_address(), &nullifier_hash)
+    }
+
+    /// Entrega el conteo a la encuesta tras el cierre (cualquiera), sin votantes
+    fn publish_tally(env: Env, survey_id: u64) {
+        let mut group = Self::load_group(&env, survey_id);
+        if group.tallied {
+            panic_with_error!(&env, AnonymousVoteError::AlreadyTallied);
+        }
+        let survey = Self::load_survey(&env, survey_id);
+        if env.ledger().timestamp() <= survey.end_date {
+            panic!("Survey has not ended yet");
+        }
+
+        let counts: Vec<u64> = env.storage().persistent().get(&DataKey::Counts(survey_id)).unwrap();
+        group.tallied = true;
+        Self::save_group(&env, &group);
+        TallyPublished { survey_id, counts: counts.clone() }.publish(&env);
+
+        Self::survey_client(&env).record_tally(&survey_id, &counts, &Vec::new(&env));
+    }
+
+    /// Obtiene el grupo de una encuesta anónima
+    fn get_group(env: Env, survey_id: u64) -> Option<Group> {
+        env.storage().persistent().get(&DataKey::Group(survey_id))
+    }
+
+    /// Obtiene la clave con la que el grupo de una encuesta verifica los votos
+    fn get_group_verifying_key(env: Env, survey_id: u64) -> Option<VerifyingKey> {
+        env.storage().persistent().get(&DataKey::GroupKey(survey_id))
+    }
+
+    /// Obtiene los compromisos del grupo en orden de alta, para reconstruir el árbol
+    fn get_members(env: Env, survey_id: u64) -> Vec<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Members(survey_id)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Indica si un nullifier ya se usó para votar en la encuesta
+    fn is_nullifier_used(env: Env, survey_id: u64, nullifier_hash: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::Nullifier(survey_id, nullifier_hash))
+    }
+}
+
+impl AnonymousBallotBox {
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        admin
+    }
+
+    fn survey_client(env: &Env) -> SurveyClient<'_> {
+        let survey: Address = env.storage().instance()
+            .get(&DataKey::Survey)
+            .expect("Contract not initialized");
+        SurveyClient::new(env, &survey)
+    }
+
+    fn load_survey(env: &Env, survey_id: u64) -> Survey {
+        Self::survey_client(env).get_survey(&survey_id).expect("Survey not found")
+    }
+
+    fn load_group(env: &Env, survey_id: u64) -> Group {
+        Self::get_group(env.clone(), survey_id).expect("Group not found")
+    }
+
+    fn save_group(env: &Env, group: &Group) {
+        Self::set_persistent(env, &DataKey::Group(group.survey_id), group);
+    }
+
+    fn set_persistent<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(key, 100, 8640000);
+    }
+
+    fn scalar(env: &Env, value: u64) -> Fr {
+        Fr::from_u256(U256::from_u128(env, value as u128))
+    }
+
+    fn constant(env: &Env, limbs: &[u64; 4]) -> Fr {
+        Fr::from_u256(U256::from_parts(env, limbs[0], limbs[1], limbs[2], limbs[3]))
+    }
+
+    // Un mismo elemento con dos codificaciones permitiría reutilizar un nullifier
+    fn field(env: &Env, bytes: &BytesN<32>) -> Fr {
+        let value = Fr::from_bytes(bytes.clone());
+        if value.as_u256() >= &U256::from_parts(env, FR_MODULUS[0], FR_MODULUS[1], FR_MODULUS[2], FR_MODULUS[3]) {
+            panic_with_error!(env, AnonymousVoteError::InvalidField);
+        }
+        value
+    }
+
+    // MiMC_left(right) + left + right, con x ← (x + left + cᵢ)⁵ en cada ronda
+    fn hash(env: &Env, left: &Fr, right: &Fr) -> Fr {
+        let bls = env.crypto().bls12_381();
+        let mut x = right.clone();
+        for c in MIMC_CONSTANTS.iter() {
+            x = bls.fr_pow(&bls.fr_add(&bls.fr_add(&x, left), &Self::constant(env, c)), 5);
+        }
+        bls.fr_add(&bls.fr_add(&bls.fr_add(&x, left), left), right)
+    }
+
+    // e(A, B) = e(α, β)·e(IC₀ + Σ entradaᵢ·ICᵢ, γ)·e(C, δ)
+    fn verify_proof(env: &Env, verifying_key: &VerifyingKey, inputs: &Vec<Fr>, proof: &Groth16Proof) -> bool {
+        let bls = env.crypto().bls12_381();
+        let mut points = Vec::new(env);
+        for i in 0..inputs.len() {
+            points.push_back(G1Affine::from_bytes(verifying_key.ic.get(i + 1).unwrap()));
+        }
+        let prepared = bls.g1_add(
+            &G1Affine::from_bytes(verifying_key.ic.get(0).unwrap()),
+            &bls.g1_msm(points, inputs.clone()),
+        );
+
+        let g1 = vec![
+            env,
+            -&G1Affine::from_bytes(proof.a.clone()),
+            G1Affine::from_bytes(verifying_key.alpha.clone()),
+            prepared,
+            G1Affine::from_bytes(proof.c.clone()),
+        ];
+        let g2 = vec![
+            env,
+            G2Affine::from_bytes(proof.b.clone()),
+            G2Affine::from_bytes(verifying_key.beta.clone()),
+            G2Affine::from_bytes(verifying_key.gamma.clone()),
+            G2Affine::from_bytes(verifying_key.delta.clone()),
+        ];
+        bls.pairing_check(g1, g2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use dappcenvote_identity as identity;
+    use soroban_sdk::{testutils::{Address as _, Ledger}, String};
+    use survey::{SurveyContract, SurveyContractClient};
+
+    struct Setup<'a> {
+        env: Env,
+        survey: SurveyContractClient<'a>,
+        client: AnonymousBallotBoxClient<'a>,
+        survey_id: u64,
+        candidates: Vec<Address>,
+        fixture: identity::Fixture,
+    }
+
+    fn load_fixture() -> identity::Fixture {
+        serde_json::from_str(include_str!("../fixtures/votes.json")).unwrap()
+    }
+
+    fn verifying_key(env: &Env, key: &identity::VerifyingKey) -> VerifyingKey {
+        let mut ic = Vec::new(env);
+        for point in &key.ic {
+            ic.push_back(BytesN::from_array(env, &point.0));
+        }
+        VerifyingKey {
+            alpha: BytesN::from_array(env, &key.alpha.0),
+            beta: BytesN::from_array(env, &key.beta.0),
+            gamma: BytesN::from_array(env, &key.gamma.0),
+            delta: BytesN::from_array(env, &key.delta.0),
+            ic,
+        }
+    }
+
+    fn proof(env: &Env, vote: &identity::Vote) -> Groth16Proof {
+        Groth16Proof {
+            a: BytesN::from_array(env, &vote.proof.a.0),
+            b: BytesN::from_array(env, &vote.proof.b.0),
+            c: BytesN::from_array(env, &vote.proof.c.0),
+        }
+    }
+
+    fn nullifier(env: &Env, vote: &identity::Vote) -> BytesN<32> {
+        BytesN::from_array(env, &vote.nullifier_hash.0)
+    }
+
+    fn create_survey(s: &Setup) -> u64 {
+        s.survey.create_survey(
+            &Address::generate(&s.env),
+            &String::from_str(&s.env, "Delegados de curso"),
+            &String::from_str(&s.env, "Voto anónimo"),
+            &1000,
+            &3000,
+            &s.candidates,
+        )
+    }
+
+    // Encuesta de dos candidatos entre 1000 y 3000, la misma de las pruebas precalculadas
+    fn setup<'a>() -> Setup<'a> {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(500);
+
+        let admin = Address::generate(&env);
+        let survey = SurveyContractClient::new(&env, &env.register(SurveyContract, (admin.clone(),)));
+        let client = AnonymousBallotBoxClient::new(
+            &env,
+            &env.register(AnonymousBallotBox, (admin.clone(), survey.address.clone())),
+        );
+        survey.add_ballot_box_contract(&client.address);
+
+        let fixture = load_fixture();
+        client.set_verifying_key(&verifying_key(&env, &fixture.verifying_key));
+        let candidates = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+        let mut s = Setup { env, survey, client, survey_id: 0, candidates, fixture };
+        s.survey_id = create_survey(&s);
+        assert_eq!(s.survey_id, s.fixture.survey_id);
+        s.client.create_group(&s.survey_id);
+        s
+    }
+
+    fn join_all(s: &Setup, survey_id: u64) {
+        for member in &s.fixture.members {
+            let commitment = BytesN::from_array(&s.env, &member.0);
+            s.client.join_group(&survey_id, &Address::generate(&s.env), &commitment, &None);
+        }
+    }
+
+    #[test]
+    fn test_anonymous_vote() {
+        let s = setup();
+        let empty_root = s.client.get_group(&s.survey_id).unwrap().root;
+        join_all(&s, s.survey_id);
+
+        // La raíz calculada en la cadena coincide con la del árbol de la herramienta
+        let group = s.client.get_group(&s.survey_id).unwrap();
+        assert_ne!(group.root, empty_root);
+        assert_eq!(group.root, BytesN::from_array(&s.env, &s.fixture.root.0));
+        assert_eq!(group.size, 3);
+        assert_eq!(s.client.get_members(&s.survey_id).len(), 3);
+
+        s.env.ledger().set_timestamp(1500);
+        for vote in &s.fixture.votes {
+            let candidate = s.candidates.get(vote.candidate).unwrap();
+            let receipt = s.client.cast_vote(&s.survey_id, &candidate, &nullifier(&s.env, vote), &proof(&s.env, vote));
+            assert!(s.survey.verify_receipt(&s.survey_id, &receipt));
+        }
+        let first = &s.fixture.votes[0];
+        assert!(s.client.is_nullifier_used(&s.survey_id, &nullifier(&s.env, first)));
+
+        // El mismo nullifier no vota dos veces, ni siquiera por otro candidato
+        let result = s.client.try_cast_vote(
+            &s.survey_id,
+            &s.candidates.get(first.candidate).unwrap(),
+            &nullifier(&s.env, first),
+            &proof(&s.env, first),
+        );
+        assert_eq!(result.err(), Some(Ok(AnonymousVoteError::AlreadyVoted.into())));
+
+        // La encuesta no se finaliza sin el conteo de la urna
+        s.env.ledger().set_timestamp(3001);
+        assert!(s.survey.try_finalize_survey(&s.survey_id).is_err());
+        s.client.publish_tally(&s.survey_id);
+        assert_eq!(s.client.try_publish_tally(&s.survey_id).err(), Some(Ok(AnonymousVoteError::AlreadyTallied.into())));
+
+        // La encuesta recibe los conteos sin ninguna dirección de votante
+        let results = s.survey.get_results(&s.survey_id);
+        assert_eq!(results.get(0).unwrap().votes, 1);
+        assert_eq!(results.get(1).unwrap().votes, 2);
+        assert!(s.survey.get_voters(&s.survey_id).is_empty());
+        assert_eq!(s.survey.get_total_votes(&s.survey_id), 3);
+        s.survey.finalize_survey(&s.survey_id);
+        assert_eq!(s.survey.get_certificate(&s.survey_id).unwrap().total_votes, 3);
+    }
+
+    #[test]
+    fn test_rejects_invalid_votes() {
+        let s = setup();
+        join_all(&s, s.survey_id);
+        s.env.ledger().set_timestamp(1500);
+        let vote = &s.fixture.votes[1];
+
+        // La prueba no sirve para otro candidato
+        let other = s.candidates.get(1 - vote.candidate).unwrap();
+        let result = s.client.try_cast_vote(&s.survey_id, &other, &nullifier(&s.env, vote), &proof(&s.env, vote));
+        assert_eq!(result.err(), Some(Ok(AnonymousVoteError::InvalidProof.into())));
+
+        // Ni con otro nullifier
+        let other_vote = &s.fixture.votes[2];
+        let candidate = s.candidates.get(vote.candidate).unwrap();
+        let result = s.client.try_cast_vote(&s.survey_id, &candidate, &nullifier(&s.env, other_vote), &proof(&s.env, vote));
+        assert_eq!(result.err(), Some(Ok(AnonymousVoteError::InvalidProof.into())));
+
+        // Un nullifier fuera de Fr no cuela como codificación alternativa
+        let result = s.client.try_cast_vote(&s.survey_id, &candidate, &BytesN::from_array(&s.env, &[0xff; 32]), &proof(&s.env, vote));
+        assert_eq!(result.err(), Some(Ok(AnonymousVoteError::InvalidField.into())));
+
+        s.client.cast_vote(&s.survey_id, &candidate, &nullifier(&s.env, vote), &proof(&s.env, vote));
+        assert_eq!(s.client.get_group(&s.survey_id).unwrap().ballots, 1);
+    }
+
+    #[test]
+    fn test_proofs_are_bound_to_their_survey() {
+        let s = setup();
+        join_all(&s, s.survey_id);
+
+        // Otra encuesta con el mismo grupo tiene la misma raíz, pero otro ámbito
+        let other_survey = create_survey(&s);
+        s.client.create_group(&other_survey);
+        join_all(&s, other_survey);
+        assert_eq!(s.client.get_group(&other_survey).unwrap().root, s.client.get_group(&s.survey_id).unwrap().root);
+
+        s.env.ledger().set_timestamp(1500);
+        let vote = &s.fixture.votes[0];
+        let candidate = s.candidates.get(vote.candidate).unwrap();
+        let result = s.client.try_cast_vote(&other_survey, &candidate, &nullifier(&s.env, vote), &proof(&s.env, vote));
+        assert_eq!(result.err(), Some(Ok(AnonymousVoteError::InvalidProof.into())));
+        s.client.cast_vote(&s.survey_id, &candidate, &nullifier(&s.env, vote), &proof(&s.env, vote));
+    }
+
+    #[test]
+    fn test_key_change_only_affects_new_groups() {
+        let s = setup();
+        join_all(&s, s.survey_id);
+        let original = verifying_key(&s.env, &s.fixture.verifying_key);
+
+        // Una clave reemplazada durante la votación no llega al grupo existente
+        let mut replacement = original.clone();
+        replacement.delta = replacement.gamma.clone();
+        s.client.set_verifying_key(&replacement);
+        let other_survey = create_survey(&s);
+        s.client.create_group(&other_survey);
+        assert_eq!(s.client.get_group_verifying_key(&s.survey_id), Some(original));
+        assert_eq!(s.client.get_group_verifying_key(&other_survey), Some(replacement));
+
+        s.env.ledger().set_timestamp(1500);
+        let vote = &s.fixture.votes[0];
+        let candidate = s.candidates.get(vote.candidate).unwrap();
+        s.client.cast_vote(&s.survey_id, &candidate, &nullifier(&s.env, vote), &proof(&s.env, vote));
+        assert_eq!(s.client.get_group(&s.survey_id).unwrap().ballots, 1);
+    }
+
+    #[test]
+    fn test_join_group_rules() {
+        let s = setup();
+        let user = Address::generate(&s.env);
+        let commitment = BytesN::from_array(&s.env, &s.fixture.members[0].0);
+        s.client.join_group(&s.survey_id, &user, &commitment, &None);
+
+        let other = BytesN::from_array(&s.env, &s.fixture.members[1].0);
+        let result = s.client.try_join_group(&s.survey_id, &user, &other, &None);
+        assert_eq!(result.err(), Some(Ok(AnonymousVoteError::AlreadyMember.into())));
+        let result = s.client.try_join_group(&s.survey_id, &Address::generate(&s.env), &commitment, &None);
+        assert_eq!(result.err(), Some(Ok(AnonymousVoteError::DuplicateCommitment.into())));
+        let outside = BytesN::from_array(&s.env, &[0xff; 32]);
+        let result = s.client.try_join_group(&s.survey_id, &Address::generate(&s.env), &outside, &None);
+        assert_eq!(result.err(), Some(Ok(AnonymousVoteError::InvalidField.into())));
+
+        // Los votos en claro pasan a rechazarse y el grupo se cierra al empezar
+        s.env.ledger().set_timestamp(1500);
+        assert!(s.survey.try_vote(&s.survey_id, &user, &s.candidates.get(0).unwrap(), &None).is_err());
+        assert!(s.client.try_join_group(&s.survey_id, &Address::generate(&s.env), &other, &None).is_err());
+        assert!(s.client.try_create_group(&s.survey_id).is_err());
+    }
+}
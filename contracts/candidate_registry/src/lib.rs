@@ -1,32 +1,101 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
-
-// Estructura de datos para almacenar información del candidato
-#[contracttype]
-#[derive(Clone)]
-pub struct Candidate {
-    pub wallet: Address,
-    pub name: String,
-    pub rfc: String,
-    pub timestamp: u64,
-}
+use dappcenvote_types::{
+    candidate_registry::{
+        Attestation, Candidate, CandidateProfile, CandidateRegistered, CandidateRegistryError,
+        CandidateRegistryInterface, DataKey, Party, PartyChanged, PartyCreated, ProfileAttested,
+        ProfileUpdated,
+    },
+    PauseScope,
+};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, String, Vec};
 
-// Clave para el storage
-#[contracttype]
-pub enum DataKey {
-    Candidate(Address), // Mapea wallet address -> Candidate
-    CandidateList,      // Lista de todos los wallets de candidatos
-    CandidateCount,     // Contador total de candidatos
-}
+const CONTRACT_VERSION: u32 = 1;
+
+// Longitud máxima de profile_uri (un CID de IPFS con prefijo y ruta cabe de sobra)
+const MAX_PROFILE_URI_LEN: u32 = 256;
+const PROFILE_URI_PREFIX: &[u8] = b"ipfs://";
+
+// Máximo de candidatos en la lista de un partido
+const MAX_PARTY_MEMBERS: u32 = 100;
 
 #[contract]
 pub struct CandidateRegistry;
 
 #[contractimpl]
 impl CandidateRegistry {
+    /// Inicializa el contrato al desplegarlo, con la dirección del administrador
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        env.storage().instance().extend_ttl(100, 8640000);
+    }
+}
+
+#[contractimpl]
+impl CandidateRegistryInterface for CandidateRegistry {
+    /// Define la dirección guardián que puede pausar el contrato (solo administrador)
+    fn set_guardian(env: Env, guardian: Address) {
+        Self::require_admin(&env);
+
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+    }
+
+    /// Obtiene la dirección guardián (si existe)
+    fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Guardian)
+    }
+
+    /// Pausa un ámbito de operación (administrador o guardián)
+    fn pause(env: Env, caller: Address, scope: PauseScope) {
+        Self::require_admin_or_guardian(&env, &caller);
+
+        env.storage().instance().set(&DataKey::Paused(scope), &true);
+    }
+
+    /// Reanuda un ámbito de operación pausado (administrador o guardián)
+    fn unpause(env: Env, caller: Address, scope: PauseScope) {
+        Self::require_admin_or_guardian(&env, &caller);
+
+        env.storage().instance().remove(&DataKey::Paused(scope));
+    }
+
+    /// Verifica si un ámbito de operación está pausado
+    fn is_paused(env: Env, scope: PauseScope) -> bool {
+        let key = DataKey::Paused(scope);
+        env.storage().instance().get(&key).unwrap_or(false)
+    }
+
+    /// Reemplaza el código del contrato conservando su almacenamiento (solo administrador)
+    /// Debe seguirse de `migrate` si la nueva versión cambia el formato de los datos
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Migra el almacenamiento a la versión de este código (solo administrador)
+    /// Sin efecto si el almacenamiento ya está en esa versión
+    /// La versión 1 es el formato original, por lo que aún no hay transformaciones
+    fn migrate(env: Env) {
+        Self::require_admin(&env);
+
+        let version = Self::version(env.clone());
+        if version >= CONTRACT_VERSION {
+            return; // Ya está al día; repetir la llamada no es un error
+        }
+
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+    }
+
+    /// Obtiene la versión del formato de almacenamiento
+    fn version(env: Env) -> u32 {
+        let key = DataKey::ContractVersion;
+        env.storage().instance().get(&key).unwrap_or(1)
+    }
+
     /// Registra un nuevo candidato en la blockchain
     /// Retorna true si el registro fue exitoso
-    pub fn register_candidate(
+    fn register_candidate(
         env: Env,
         wallet: Address,
         name: String,
@@ -34,6 +103,7 @@ impl CandidateRegistry {
     ) -> bool {
         // Verificar que el wallet esté autenticado
         wallet.require_auth();
+        Self::require_not_paused(&env, PauseScope::Registration);
 
         // Verificar si el candidato ya existe
         let key = DataKey::Candidate(wallet.clone());
@@ -53,6 +123,13 @@ impl CandidateRegistry {
         env.storage().persistent().set(&key, &candidate);
         env.storage().persistent().extend_ttl(&key, 100, 8640000);
 
+        CandidateRegistered {
+            wallet: wallet.clone(),
+            name: candidate.name,
+            timestamp: candidate.timestamp,
+        }
+        .publish(&env);
+
         // Agregar a la lista de candidatos
         let list_key = DataKey::CandidateList;
         let mut candidate_list: Vec<Address> = env
@@ -75,19 +152,19 @@ impl CandidateRegistry {
     }
 
     /// Obtiene los datos de un candidato por su wallet address
-    pub fn get_candidate(env: Env, wallet: Address) -> Option<Candidate> {
+    fn get_candidate(env: Env, wallet: Address) -> Option<Candidate> {
         let key = DataKey::Candidate(wallet);
         env.storage().persistent().get(&key)
     }
 
     /// Verifica si un candidato está registrado
-    pub fn candidate_exists(env: Env, wallet: Address) -> bool {
+    fn candidate_exists(env: Env, wallet: Address) -> bool {
         let key = DataKey::Candidate(wallet);
         env.storage().persistent().has(&key)
     }
 
     /// Obtiene la lista de todos los wallets de candidatos registrados
-    pub fn get_all_candidates(env: Env) -> Vec<Address> {
+    fn get_all_candidates(env: Env) -> Vec<Address> {
         let key = DataKey::CandidateList;
         env.storage()
             .persistent()
@@ -96,10 +173,307 @@ impl CandidateRegistry {
     }
 
     /// Obtiene el número total de candidatos registrados
-    pub fn get_candidate_count(env: Env) -> u64 {
+    fn get_candidate_count(env: Env) -> u64 {
         let key = DataKey::CandidateCount;
         env.storage().persistent().get(&key).unwrap_or(0)
     }
+
+    /// Autoriza a un registrador para atestiguar perfiles (solo administrador)
+    fn add_registrar(env: Env, registrar: Address) {
+        Self::require_admin(&env);
+
+        let key = DataKey::Registrar(registrar);
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 100, 8640000);
+    }
+
+    /// Retira la autorización de un registrador (solo administrador)
+    /// Sus constancias anteriores se conservan
+    fn remove_registrar(env: Env, registrar: Address) {
+        Self::require_admin(&env);
+
+        env.storage().persistent().remove(&DataKey::Registrar(registrar));
+    }
+
+    /// Verifica si una dirección es registrador autorizado
+    fn is_registrar(env: Env, registrar: Address) -> bool {
+        let key = DataKey::Registrar(registrar);
+        env.storage().persistent().get(&key).unwrap_or(false)
+    }
+
+    /// Publica una nueva versión del perfil del candidato
+    /// Las versiones anteriores se conservan; retorna el número de la nueva
+    fn update_profile(
+        env: Env,
+        wallet: Address,
+        profile_uri: String,
+        content_hash: BytesN<32>,
+    ) -> u32 {
+        wallet.require_auth();
+        Self::require_not_paused(&env, PauseScope::Registration);
+
+        if !Self::candidate_exists(env.clone(), wallet.clone()) {
+            panic!("Candidate not found");
+        }
+        // Igual que los metadatos de las encuestas: una URI ipfs:// acotada
+        let uri_len = profile_uri.len();
+        if uri_len <= PROFILE_URI_PREFIX.len() as u32 || uri_len > MAX_PROFILE_URI_LEN {
+            panic!("Invalid profile URI");
+        }
+        let mut buf = [0u8; MAX_PROFILE_URI_LEN as usize];
+        let uri = &mut buf[..uri_len as usize];
+        profile_uri.copy_into_slice(uri);
+        if !uri.starts_with(PROFILE_URI_PREFIX) {
+            panic!("Invalid profile URI");
+        }
+
+        let version_key = DataKey::ProfileVersion(wallet.clone());
+        let version: u32 = env.storage().persistent().get(&version_key).unwrap_or(0) + 1;
+        let profile = CandidateProfile {
+            version,
+            profile_uri,
+            content_hash,
+            updated_at: env.ledger().timestamp(),
+        };
+
+        let key = DataKey::Profile(wallet.clone(), version);
+        env.storage().persistent().set(&key, &profile);
+        env.storage().persistent().extend_ttl(&key, 100, 8640000);
+        env.storage().persistent().set(&version_key, &version);
+        env.storage().persistent().extend_ttl(&version_key, 100, 8640000);
+
+        ProfileUpdated {
+            wallet,
+            version,
+            profile_uri: profile.profile_uri,
+            content_hash: profile.content_hash,
+        }
+        .publish(&env);
+        version
+    }
+
+    /// Obtiene la versión vigente del perfil de un candidato
+    fn get_profile(env: Env, wallet: Address) -> Option<CandidateProfile> {
+        let version_key = DataKey::ProfileVersion(wallet.clone());
+        let version: u32 = env.storage().persistent().get(&version_key)?;
+        Self::get_profile_version(env, wallet, version)
+    }
+
+    /// Obtiene una versión concreta del perfil de un candidato
+    fn get_profile_version(env: Env, wallet: Address, version: u32) -> Option<CandidateProfile> {
+        let key = DataKey::Profile(wallet, version);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Obtiene todas las versiones del perfil, de la más antigua a la vigente
+    fn get_profile_history(env: Env, wallet: Address) -> Vec<CandidateProfile> {
+        let version_key = DataKey::ProfileVersion(wallet.clone());
+        let latest: u32 = env.storage().persistent().get(&version_key).unwrap_or(0);
+
+        let mut history = Vec::new(&env);
+        for version in 1..=latest {
+            if let Some(profile) = Self::get_profile_version(env.clone(), wallet.clone(), version) {
+                history.push_back(profile);
+            }
+        }
+        history
+    }
+
+    /// Un registrador autorizado da constancia de un dato de la versión vigente del perfil
+    fn attest_profile(env: Env, registrar: Address, wallet: Address, claim: String) {
+        registrar.require_auth();
+        Self::require_not_paused(&env, PauseScope::Registration);
+
+        if !Self::is_registrar(env.clone(), registrar.clone()) {
+            panic_with_error!(&env, CandidateRegistryError::Unauthorized);
+        }
+        let profile = Self::get_profile(env.clone(), wallet.clone()).expect("Profile not found");
+        if claim.is_empty() {
+            panic!("Claim cannot be empty");
+        }
+
+        let key = DataKey::Attestations(wallet.clone());
+        let mut attestations = Self::get_attestations(env.clone(), wallet.clone());
+        attestations.push_back(Attestation {
+            registrar: registrar.clone(),
+            claim: claim.clone(),
+            version: profile.version,
+            content_hash: profile.content_hash,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &attestations);
+        env.storage().persistent().extend_ttl(&key, 100, 8640000);
+
+        ProfileAttested { wallet, registrar, claim, version: profile.version }.publish(&env);
+    }
+
+    /// Obtiene las constancias de un candidato; cada una indica la versión que revisó
+    fn get_attestations(env: Env, wallet: Address) -> Vec<Attestation> {
+        let key = DataKey::Attestations(wallet);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Crea un partido encabezado por un candidato registrado, que queda como primer miembro
+    fn create_party(env: Env, leader: Address, name: String, logo_hash: BytesN<32>) -> u64 {
+        leader.require_auth();
+        Self::require_not_paused(&env, PauseScope::Registration);
+
+        if name.is_empty() {
+            panic!("Party name cannot be empty");
+        }
+        Self::require_unaffiliated(&env, &leader);
+
+        let count_key = DataKey::PartyCount;
+        let party_id: u64 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+        env.storage().persistent().set(&count_key, &party_id);
+        env.storage().persistent().extend_ttl(&count_key, 100, 8640000);
+
+        let party = Party {
+            party_id,
+            name: name.clone(),
+            logo_hash,
+            leader: leader.clone(),
+            members: Vec::from_array(&env, [leader.clone()]),
+        };
+        Self::save_party(&env, &party);
+        Self::set_candidate_party(&env, &leader, party_id);
+
+        PartyCreated { party_id, name, leader }.publish(&env);
+        party_id
+    }
+
+    /// Añade un candidato al final de la lista (el líder y el candidato deben firmar)
+    fn add_party_member(env: Env, party_id: u64, member: Address) {
+        let mut party = Self::load_party(&env, party_id);
+        party.leader.require_auth();
+        member.require_auth();
+        Self::require_not_paused(&env, PauseScope::Registration);
+
+        Self::require_unaffiliated(&env, &member);
+        if party.members.len() >= MAX_PARTY_MEMBERS {
+            panic!("Party list is full");
+        }
+
+        party.members.push_back(member.clone());
+        Self::save_party(&env, &party);
+        Self::set_candidate_party(&env, &member, party_id);
+        Self::publish_party_changed(&env, &party);
+    }
+
+    /// Saca a un candidato de la lista (el líder o el propio candidato)
+    /// El líder no puede salir sin ceder antes el liderazgo
+    fn remove_party_member(env: Env, party_id: u64, caller: Address, member: Address) {
+        caller.require_auth();
+        Self::require_not_paused(&env, PauseScope::Registration);
+
+        let mut party = Self::load_party(&env, party_id);
+        if caller != party.leader && caller != member {
+            panic_with_error!(&env, CandidateRegistryError::Unauthorized);
+        }
+        if member == party.leader {
+            panic!("Leader cannot leave the party");
+        }
+        let position = party.members.first_index_of(&member).expect("Not a party member");
+
+        party.members.remove(position);
+        Self::save_party(&env, &party);
+        env.storage().persistent().remove(&DataKey::CandidateParty(member));
+        Self::publish_party_changed(&env, &party);
+    }
+
+    /// Cede el liderazgo a otro miembro del partido (solo el líder)
+    fn set_party_leader(env: Env, party_id: u64, new_leader: Address) {
+        let mut party = Self::load_party(&env, party_id);
+        party.leader.require_auth();
+        Self::require_not_paused(&env, PauseScope::Registration);
+
+        if !party.members.contains(&new_leader) {
+            panic!("Not a party member");
+        }
+
+        party.leader = new_leader;
+        Self::save_party(&env, &party);
+        Self::publish_party_changed(&env, &party);
+    }
+
+    /// Obtiene un partido
+    fn get_party(env: Env, party_id: u64) -> Option<Party> {
+        env.storage().persistent().get(&DataKey::Party(party_id))
+    }
+
+    /// Obtiene el número de partidos creados
+    fn get_party_count(env: Env) -> u64 {
+        env.storage().persistent().get(&DataKey::PartyCount).unwrap_or(0)
+    }
+
+    /// Obtiene el partido al que pertenece un candidato (si pertenece a alguno)
+    fn get_candidate_party(env: Env, wallet: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::CandidateParty(wallet))
+    }
+}
+
+impl CandidateRegistry {
+    fn load_party(env: &Env, party_id: u64) -> Party {
+        Self::get_party(env.clone(), party_id).expect("Party not found")
+    }
+
+    fn save_party(env: &Env, party: &Party) {
+        let key = DataKey::Party(party.party_id);
+        env.storage().persistent().set(&key, party);
+        env.storage().persistent().extend_ttl(&key, 100, 8640000);
+    }
+
+    fn set_candidate_party(env: &Env, wallet: &Address, party_id: u64) {
+        let key = DataKey::CandidateParty(wallet.clone());
+        env.storage().persistent().set(&key, &party_id);
+        env.storage().persistent().extend_ttl(&key, 100, 8640000);
+    }
+
+    // Solo los candidatos registrados sin partido pueden entrar en una lista
+    fn require_unaffiliated(env: &Env, wallet: &Address) {
+        if !Self::candidate_exists(env.clone(), wallet.clone()) {
+            panic!("Candidate not found");
+        }
+        if Self::get_candidate_party(env.clone(), wallet.clone()).is_some() {
+            panic!("Candidate already in a party");
+        }
+    }
+
+    fn publish_party_changed(env: &Env, party: &Party) {
+        PartyChanged {
+            party_id: party.party_id,
+            leader: party.leader.clone(),
+            members: party.members.clone(),
+        }
+        .publish(env);
+    }
+
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        admin
+    }
+
+    fn require_admin_or_guardian(env: &Env, caller: &Address) {
+        caller.require_auth();
+
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        let guardian: Option<Address> = env.storage().instance().get(&DataKey::Guardian);
+        if admin.as_ref() != Some(caller) && guardian.as_ref() != Some(caller) {
+            panic_with_error!(env, CandidateRegistryError::Unauthorized);
+        }
+    }
+
+    fn require_not_paused(env: &Env, scope: PauseScope) {
+        if Self::is_paused(env.clone(), scope) {
+            panic_with_error!(env, CandidateRegistryError::Paused);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -107,10 +481,15 @@ mod test {
     use super::*;
     use soroban_sdk::{testutils::Address as _, Env};
 
+    mod candidate_registry_wasm {
+        soroban_sdk::contractimport!(file = "testdata/candidate_registry.wasm");
+    }
+
     #[test]
     fn test_register_candidate() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, CandidateRegistry);
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
         let client = CandidateRegistryClient::new(&env, &contract_id);
 
         let candidate_wallet = Address::generate(&env);
@@ -130,7 +509,8 @@ mod test {
     #[test]
     fn test_get_candidate() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, CandidateRegistry);
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
         let client = CandidateRegistryClient::new(&env, &contract_id);
 
         let candidate_wallet = Address::generate(&env);
@@ -152,7 +532,8 @@ mod test {
     #[test]
     fn test_get_all_candidates() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, CandidateRegistry);
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
         let client = CandidateRegistryClient::new(&env, &contract_id);
 
         let candidate1 = Address::generate(&env);
@@ -184,7 +565,8 @@ mod test {
     #[test]
     fn test_duplicate_registration() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, CandidateRegistry);
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
         let client = CandidateRegistryClient::new(&env, &contract_id);
 
         let candidate_wallet = Address::generate(&env);
@@ -208,4 +590,259 @@ mod test {
         // Debe haber solo 1 candidato
         assert_eq!(client.get_candidate_count(), 1);
     }
+
+    #[test]
+    fn test_upgrade_preserves_candidates() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(CandidateRegistry, (admin.clone(),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        let candidate_wallet = Address::generate(&env);
+
+        client.register_candidate(
+            &candidate_wallet,
+            &String::from_str(&env, "Laura Mendez"),
+            &String::from_str(&env, "MELA880404GHI"),
+        );
+
+        // Actualizar al código compilado y verificar que los datos persisten
+        let new_hash = env.deployer().upload_contract_wasm(candidate_registry_wasm::WASM);
+        client.upgrade(&new_hash);
+
+        let upgraded = candidate_registry_wasm::Client::new(&env, &contract_id);
+        assert_eq!(upgraded.version(), CONTRACT_VERSION);
+        assert!(upgraded.candidate_exists(&candidate_wallet));
+        assert_eq!(upgraded.get_all_candidates().len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_current_version() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        // Repetir la migración no falla ni cambia la versión
+        client.migrate();
+        assert_eq!(client.version(), CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn test_pause_registration() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(CandidateRegistry, (admin.clone(),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        let guardian = Address::generate(&env);
+        let candidate_wallet = Address::generate(&env);
+        client.set_guardian(&guardian);
+        client.pause(&guardian, &PauseScope::Registration);
+
+        let result = client.try_register_candidate(
+            &candidate_wallet,
+            &String::from_str(&env, "Pedro Nava"),
+            &String::from_str(&env, "NAPE770505JKL"),
+        );
+        assert_eq!(result, Err(Ok(CandidateRegistryError::Paused.into())));
+        assert_eq!(client.get_all_candidates().len(), 0);
+
+        client.unpause(&admin, &PauseScope::Registration);
+        assert!(client.register_candidate(
+            &candidate_wallet,
+            &String::from_str(&env, "Pedro Nava"),
+            &String::from_str(&env, "NAPE770505JKL"),
+        ));
+    }
+
+    #[test]
+    fn test_profile_history() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        let candidate_wallet = Address::generate(&env);
+        client.register_candidate(
+            &candidate_wallet,
+            &String::from_str(&env, "Sofia Ramirez"),
+            &String::from_str(&env, "RASO910606MNO"),
+        );
+        assert_eq!(client.get_profile(&candidate_wallet), None);
+
+        let first_hash = BytesN::from_array(&env, &[1; 32]);
+        let second_hash = BytesN::from_array(&env, &[2; 32]);
+        let uri = String::from_str(&env, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+        assert_eq!(client.update_profile(&candidate_wallet, &uri, &first_hash), 1);
+        assert_eq!(client.update_profile(&candidate_wallet, &uri, &second_hash), 2);
+
+        // La versión vigente es la última, pero las anteriores siguen consultables
+        let profile = client.get_profile(&candidate_wallet).unwrap();
+        assert_eq!(profile.version, 2);
+        assert_eq!(profile.content_hash, second_hash);
+        assert_eq!(client.get_profile_version(&candidate_wallet, &1).unwrap().content_hash, first_hash);
+        let history = client.get_profile_history(&candidate_wallet);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(1).unwrap(), profile);
+    }
+
+    #[test]
+    fn test_attest_profile() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(CandidateRegistry, (admin.clone(),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        let registrar = Address::generate(&env);
+        let candidate_wallet = Address::generate(&env);
+        client.register_candidate(
+            &candidate_wallet,
+            &String::from_str(&env, "Tomas Vega"),
+            &String::from_str(&env, "VETO830707PQR"),
+        );
+        let uri = String::from_str(&env, "ipfs://bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+        let hash = BytesN::from_array(&env, &[7; 32]);
+        client.update_profile(&candidate_wallet, &uri, &hash);
+
+        // Solo los registradores autorizados pueden dar constancia
+        let claim = String::from_str(&env, "x.com/tomasvega");
+        let result = client.try_attest_profile(&registrar, &candidate_wallet, &claim);
+        assert_eq!(result, Err(Ok(CandidateRegistryError::Unauthorized.into())));
+
+        client.add_registrar(&registrar);
+        assert!(client.is_registrar(&registrar));
+        client.attest_profile(&registrar, &candidate_wallet, &claim);
+
+        // La constancia queda ligada a la versión revisada aunque el perfil cambie después
+        client.update_profile(&candidate_wallet, &uri, &BytesN::from_array(&env, &[8; 32]));
+        let attestations = client.get_attestations(&candidate_wallet);
+        assert_eq!(attestations.len(), 1);
+        assert_eq!(attestations.get(0).unwrap().version, 1);
+        assert_eq!(attestations.get(0).unwrap().content_hash, hash);
+
+        client.remove_registrar(&registrar);
+        assert!(!client.is_registrar(&registrar));
+        assert_eq!(client.get_attestations(&candidate_wallet).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Candidate not found")]
+    fn test_profile_requires_candidate() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        client.update_profile(
+            &Address::generate(&env),
+            &String::from_str(&env, "ipfs://bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"),
+            &BytesN::from_array(&env, &[0; 32]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid profile URI")]
+    fn test_profile_requires_ipfs_uri() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        let candidate_wallet = Address::generate(&env);
+        client.register_candidate(
+            &candidate_wallet,
+            &String::from_str(&env, "Lucia Mendez"),
+            &String::from_str(&env, "MELU880808STU"),
+        );
+        // Solo el prefijo tampoco basta
+        let bare = String::from_str(&env, "ipfs://");
+        assert!(client.try_update_profile(&candidate_wallet, &bare, &BytesN::from_array(&env, &[0; 32])).is_err());
+
+        client.update_profile(
+            &candidate_wallet,
+            &String::from_str(&env, "https://example.com/perfil.json"),
+            &BytesN::from_array(&env, &[0; 32]),
+        );
+    }
+
+    fn register(client: &CandidateRegistryClient, env: &Env, name: &str) -> Address {
+        let wallet = Address::generate(env);
+        client.register_candidate(&wallet, &String::from_str(env, name), &String::from_str(env, "RFC"));
+        wallet
+    }
+
+    #[test]
+    fn test_party_membership() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(CandidateRegistry, (admin.clone(),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        let leader = register(&client, &env, "Rosa Lima");
+        let second = register(&client, &env, "Hugo Paz");
+        let third = register(&client, &env, "Ines Mora");
+        let logo = BytesN::from_array(&env, &[3; 32]);
+        let party_id = client.create_party(&leader, &String::from_str(&env, "Partido Azul"), &logo);
+        assert_eq!(party_id, 1);
+        assert_eq!(client.get_party_count(), 1);
+
+        client.add_party_member(&party_id, &second);
+        client.add_party_member(&party_id, &third);
+        let party = client.get_party(&party_id).unwrap();
+        assert_eq!(party.members, Vec::from_array(&env, [leader.clone(), second.clone(), third.clone()]));
+        assert_eq!(client.get_candidate_party(&third), Some(party_id));
+
+        // El candidato puede salir por su cuenta y el líder puede ceder el cargo
+        client.remove_party_member(&party_id, &second, &second);
+        assert_eq!(client.get_candidate_party(&second), None);
+        client.set_party_leader(&party_id, &third);
+        client.remove_party_member(&party_id, &third, &leader);
+        let party = client.get_party(&party_id).unwrap();
+        assert_eq!(party.leader, third);
+        assert_eq!(party.members, Vec::from_array(&env, [third.clone()]));
+
+        // Un tercero no puede sacar a nadie
+        let result = client.try_remove_party_member(&party_id, &second, &third);
+        assert_eq!(result, Err(Ok(CandidateRegistryError::Unauthorized.into())));
+
+        // Con el registro en pausa tampoco cambian los partidos
+        client.pause(&admin, &PauseScope::Registration);
+        let paused = Err(Ok(CandidateRegistryError::Paused.into()));
+        assert_eq!(client.try_add_party_member(&party_id, &second), paused);
+        assert_eq!(client.try_set_party_leader(&party_id, &third), paused);
+    }
+
+    #[test]
+    #[should_panic(expected = "Candidate already in a party")]
+    fn test_one_party_per_candidate() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        let first = register(&client, &env, "Rosa Lima");
+        let second = register(&client, &env, "Hugo Paz");
+        let logo = BytesN::from_array(&env, &[3; 32]);
+        client.create_party(&first, &String::from_str(&env, "Partido Azul"), &logo);
+        let other = client.create_party(&second, &String::from_str(&env, "Partido Rojo"), &logo);
+        client.add_party_member(&other, &first);
+    }
+
+    #[test]
+    #[should_panic(expected = "Leader cannot leave the party")]
+    fn test_leader_cannot_leave() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(CandidateRegistry, (Address::generate(&env),));
+        let client = CandidateRegistryClient::new(&env, &contract_id);
+
+        let leader = register(&client, &env, "Rosa Lima");
+        let party_id = client.create_party(&leader, &String::from_str(&env, "Partido Azul"), &BytesN::from_array(&env, &[3; 32]));
+        client.remove_party_member(&party_id, &leader, &leader);
+    }
 }
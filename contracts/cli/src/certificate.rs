@@ -0,0 +1,435 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{Limits, ScAddress, ScMap, ScMapEntry, ScString, ScSymbol, ScVal, ScVec, WriteXdr};
+
+use crate::stellar::Network;
+
+/// Datos exportados de una encuesta finalizada, tal como los devuelve
+/// `stellar contract invoke` para cada getter del contrato
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ResultsExport {
+    pub survey: ExportedSurvey,
+    pub results: Vec<ExportedResult>,
+    pub voters: Vec<String>,
+    /// Urna externa de la encuesta; con ella el padrón puede no cubrir las papeletas
+    #[serde(default)]
+    pub ballot_box: Option<String>,
+    /// Preguntas de un cuestionario y el conteo de cada una; vacías en encuestas de candidatos
+    #[serde(default)]
+    pub questions: Vec<ExportedQuestion>,
+    #[serde(default)]
+    pub question_results: Vec<Vec<ExportedOption>>,
+    pub certificate: ExportedCertificate,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ExportedSurvey {
+    #[serde(deserialize_with = "number")]
+    pub survey_id: u64,
+    pub creator: String,
+    pub name: String,
+    pub description: String,
+    #[serde(deserialize_with = "number")]
+    pub start_date: u64,
+    #[serde(deserialize_with = "number")]
+    pub end_date: u64,
+    #[serde(deserialize_with = "number")]
+    pub created_at: u64,
+    pub candidates: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ExportedResult {
+    pub candidate: String,
+    #[serde(deserialize_with = "number")]
+    pub votes: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ExportedQuestion {
+    pub prompt: String,
+    pub options: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ExportedOption {
+    pub label: String,
+    #[serde(deserialize_with = "number")]
+    pub votes: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ExportedCertificate {
+    #[serde(deserialize_with = "number")]
+    pub survey_id: u64,
+    pub hash: String,
+    #[serde(deserialize_with = "number")]
+    pub ledger: u64,
+    #[serde(deserialize_with = "number")]
+    pub total_votes: u64,
+}
+
+// stellar-cli imprime los enteros grandes como texto
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Number(n) => n.as_u64().ok_or_else(|| serde::de::Error::custom("expected u64")),
+        Value::String(s) => s.parse().map_err(serde::de::Error::custom),
+        other => Err(serde::de::Error::custom(format!("expected u64, found {other}"))),
+    }
+}
+
+/// Lee del contrato todo lo necesario para verificar el certificado de una encuesta
+pub fn export(network: &mut impl Network, survey_contract: &str, survey_id: u64) -> Result<ResultsExport> {
+    let id = survey_id.to_string();
+    let mut get = |function: &str| -> Result<Value> {
+        let output = network.invoke(survey_contract, function, &[("survey_id", &id)])?;
+        serde_json::from_str(&output).with_context(|| format!("unexpected output from {function}: {output}"))
+    };
+
+    let certificate = get("get_certificate")?;
+    if certificate.is_null() {
+        bail!("survey {survey_id} has not been finalized");
+    }
+    let survey = serde_json::from_value(get("get_survey")?)?;
+    let results = serde_json::from_value(get("get_results")?)?;
+    let voters = serde_json::from_value(get("get_voters")?)?;
+    let ballot_box = serde_json::from_value(get("get_ballot_box")?)?;
+    let questions: Vec<ExportedQuestion> = serde_json::from_value(get("get_questions")?)?;
+
+    let mut question_results = Vec::new();
+    for question in 0..questions.len() {
+        let index = question.to_string();
+        let args = [("survey_id", id.as_str()), ("question", index.as_str())];
+        let output = network.invoke(survey_contract, "get_question_results", &args)?;
+        let options = serde_json::from_str(&output)
+            .with_context(|| format!("unexpected output from get_question_results: {output}"))?;
+        question_results.push(options);
+    }
+
+    Ok(ResultsExport {
+        survey,
+        results,
+        voters,
+        ballot_box,
+        questions,
+        question_results,
+        certificate: serde_json::from_value(certificate)?,
+    })
+}
+
+/// Recalcula el hash del certificado con la misma serialización que `finalize_survey`
+pub fn canonical_hash(export: &ResultsExport) -> Result<[u8; 32]> {
+    let survey = &export.survey;
+    let results = export
+        .results
+        .iter()
+        .map(|result| vec(vec![address(&result.candidate)?, ScVal::U64(result.votes)]))
+        .collect::<Result<Vec<_>>>()?;
+
+    let canonical = vec(vec![
+        ScVal::U64(survey.survey_id),
+        address(&survey.creator)?,
+        string(&survey.name)?,
+        string(&survey.description)?,
+        ScVal::U64(survey.start_date),
+        ScVal::U64(survey.end_date),
+        ScVal::U64(survey.created_at),
+        addresses(&survey.candidates)?,
+        vec(results)?,
+        addresses(&export.voters)?,
+        vec(export.questions.iter().map(question).collect::<Result<_>>()?)?,
+        vec(export
+            .question_results
+            .iter()
+            .map(|options| vec(options.iter().map(option).collect::<Result<_>>()?))
+            .collect::<Result<_>>()?)?,
+    ])?;
+
+    let xdr = canonical.to_xdr(Limits::none())?;
+    Ok(Sha256::digest(&xdr).into())
+}
+
+/// Comprueba que el certificado almacenado corresponde a los datos exportados
+pub fn verify(export: &ResultsExport) -> Result<()> {
+    let certificate = &export.certificate;
+    if certificate.survey_id != export.survey.survey_id {
+        bail!("certificate is for survey {}, not {}", certificate.survey_id, export.survey.survey_id);
+    }
+    // Una urna anónima no entrega padrón; sus papeletas son la suma de los conteos,
+    // ya que no hay delegación
+    if export.ballot_box.is_some() {
+        let ballots: u64 = export.results.iter().map(|result| result.votes).sum();
+        if certificate.total_votes != ballots {
+            bail!("certificate counts {} ballots but the results add up to {ballots}", certificate.total_votes);
+        }
+    } else if certificate.total_votes != export.voters.len() as u64 {
+        bail!(
+            "certificate counts {} voters but the roll has {}",
+            certificate.total_votes,
+            export.voters.len()
+        );
+    }
+    let expected = to_hex(&canonical_hash(export)?);
+    if !certificate.hash.eq_ignore_ascii_case(&expected) {
+        bail!("certificate hash {} does not match recomputed {expected}", certificate.hash);
+    }
+    Ok(())
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn address(strkey: &str) -> Result<ScVal> {
+    let address = ScAddress::from_str(strkey).with_context(|| format!("invalid address {strkey}"))?;
+    Ok(ScVal::Address(address))
+}
+
+fn addresses(strkeys: &[String]) -> Result<ScVal> {
+    vec(strkeys.iter().map(|strkey| address(strkey)).collect::<Result<_>>()?)
+}
+
+// Los structs de contrato se serializan como mapas con las claves en orden
+fn question(question: &ExportedQuestion) -> Result<ScVal> {
+    let options = vec(question.options.iter().map(|label| string(label)).collect::<Result<_>>()?)?;
+    map(vec![("options", options), ("prompt", string(&question.prompt)?)])
+}
+
+fn option(option: &ExportedOption) -> Result<ScVal> {
+    map(vec![("label", string(&option.label)?), ("votes", ScVal::U64(option.votes))])
+}
+
+fn map(entries: Vec<(&str, ScVal)>) -> Result<ScVal> {
+    let entries = entries
+        .into_iter()
+        .map(|(key, val)| Ok(ScMapEntry { key: ScVal::Symbol(ScSymbol(key.try_into()?)), val }))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ScVal::Map(Some(ScMap(entries.try_into()?))))
+}
+
+fn string(value: &str) -> Result<ScVal> {
+    Ok(ScVal::String(ScString(value.as_bytes().to_vec().try_into()?)))
+}
+
+fn vec(items: Vec<ScVal>) -> Result<ScVal> {
+    Ok(ScVal::Vec(Some(ScVec(items.try_into()?))))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use dappcenvote_types::survey::SurveyClient;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{Address, Env};
+    use survey::SurveyContract;
+
+    fn strkey(address: &Address) -> String {
+        ScAddress::from(address).to_string()
+    }
+
+    // Crea una encuesta de dos candidatos en un Env de prueba
+    fn draft_survey(env: &Env) -> (SurveyClient<'_>, u64, soroban_sdk::Vec<Address>) {
+        env.mock_all_auths();
+        let client = SurveyClient::new(env, &env.register(SurveyContract, (Address::generate(env),)));
+
+        let creator = Address::generate(env);
+        let mut candidates = soroban_sdk::Vec::new(env);
+        candidates.push_back(Address::generate(env));
+        candidates.push_back(Address::generate(env));
+        let survey_id = client.create_survey(
+            &creator,
+            &soroban_sdk::String::from_str(env, "Elección 2026"),
+            &soroban_sdk::String::from_str(env, "Certificado"),
+            &1000,
+            &2000,
+            &candidates,
+        );
+        (client, survey_id, candidates)
+    }
+
+    // Finaliza una encuesta con votos directos y exporta sus datos
+    fn finalized_survey() -> ResultsExport {
+        let env = Env::default();
+        let (client, survey_id, candidates) = draft_survey(&env);
+
+        env.ledger().set_timestamp(1500);
+        for choice in [0, 1, 0] {
+            client.vote(&survey_id, &Address::generate(&env), &candidates.get(choice).unwrap(), &None);
+        }
+        env.ledger().set_timestamp(2001);
+        env.ledger().set_sequence_number(77);
+        client.finalize_survey(&survey_id);
+        exported(&client, survey_id)
+    }
+
+    // Finaliza una encuesta cuya urna anónima entrega el conteo sin padrón
+    fn finalized_ballot_box_survey() -> ResultsExport {
+        let env = Env::default();
+        let (client, survey_id, _candidates) = draft_survey(&env);
+        let ballot_box = Address::generate(&env);
+        client.add_ballot_box_contract(&ballot_box);
+        client.set_ballot_box(&survey_id, &ballot_box);
+
+        env.ledger().set_timestamp(2001);
+        let counts = soroban_sdk::Vec::from_array(&env, [1u64, 2]);
+        client.record_tally(&survey_id, &counts, &soroban_sdk::Vec::new(&env));
+        client.finalize_survey(&survey_id);
+        exported(&client, survey_id)
+    }
+
+    // Finaliza un cuestionario de dos preguntas con tres respuestas
+    fn finalized_questionnaire() -> ResultsExport {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = SurveyClient::new(&env, &env.register(SurveyContract, (Address::generate(&env),)));
+
+        let text = |value: &str| soroban_sdk::String::from_str(&env, value);
+        let question = |prompt: &str| dappcenvote_types::survey::Question {
+            prompt: text(prompt),
+            options: soroban_sdk::Vec::from_array(&env, [text("Sí"), text("No")]),
+        };
+        let survey_id = client.create_questionnaire(
+            &Address::generate(&env),
+            &text("Elección 2026"),
+            &text("Certificado"),
+            &1000,
+            &2000,
+            &soroban_sdk::Vec::from_array(&env, [question("¿Primera?"), question("¿Segunda?")]),
+        );
+
+        env.ledger().set_timestamp(1500);
+        for answers in [[0u32, 1], [0, 0], [1, 1]] {
+            let answers = soroban_sdk::Vec::from_array(&env, answers);
+            client.submit_response(&survey_id, &Address::generate(&env), &answers, &None);
+        }
+        env.ledger().set_timestamp(2001);
+        client.finalize_survey(&survey_id);
+        exported(&client, survey_id)
+    }
+
+    // Exporta los datos como lo hace `export` con stellar-cli
+    fn exported(client: &SurveyClient, survey_id: u64) -> ResultsExport {
+        let survey = client.get_survey(&survey_id).unwrap();
+        let certificate = client.get_certificate(&survey_id).unwrap();
+        ResultsExport {
+            survey: ExportedSurvey {
+                survey_id,
+                creator: strkey(&survey.creator),
+                name: "Elección 2026".into(),
+                description: "Certificado".into(),
+                start_date: survey.start_date,
+                end_date: survey.end_date,
+                created_at: survey.created_at,
+                candidates: survey.candidates.iter().map(|c| strkey(&c)).collect(),
+            },
+            results: client
+                .get_results(&survey_id)
+                .iter()
+                .map(|r| ExportedResult { candidate: strkey(&r.candidate), votes: r.votes })
+                .collect(),
+            voters: client.get_voters(&survey_id).iter().map(|v| strkey(&v)).collect(),
+            ballot_box: client.get_ballot_box(&survey_id).map(|b| strkey(&b)),
+            questions: client
+                .get_questions(&survey_id)
+                .iter()
+                .map(|q| ExportedQuestion {
+                    prompt: q.prompt.to_string(),
+                    options: q.options.iter().map(|o| o.to_string()).collect(),
+                })
+                .collect(),
+            question_results: (0..client.get_questions(&survey_id).len())
+                .map(|question| {
+                    client
+                        .get_question_results(&survey_id, &question)
+                        .iter()
+                        .map(|o| ExportedOption { label: o.label.to_string(), votes: o.votes })
+                        .collect()
+                })
+                .collect(),
+            certificate: ExportedCertificate {
+                survey_id: certificate.survey_id,
+                hash: to_hex(&certificate.hash.to_array()),
+                ledger: certificate.ledger.into(),
+                total_votes: certificate.total_votes,
+            },
+        }
+    }
+
+    #[test]
+    fn test_verify_matches_contract() {
+        let export = finalized_survey();
+        assert_eq!(export.certificate.ledger, 77);
+        assert_eq!(export.certificate.total_votes, 3);
+        verify(&export).unwrap();
+    }
+
+    #[test]
+    fn test_verify_ballot_box_survey() {
+        let export = finalized_ballot_box_survey();
+        assert!(export.voters.is_empty());
+        assert_eq!(export.certificate.total_votes, 3);
+
+        // Ida y vuelta por el JSON que escribe `export-results`
+        let json = serde_json::to_string_pretty(&export).unwrap();
+        let export: ResultsExport = serde_json::from_str(&json).unwrap();
+        assert!(export.ballot_box.is_some());
+        verify(&export).unwrap();
+
+        let mut tampered = export;
+        tampered.results[0].votes += 1;
+        assert!(verify(&tampered).unwrap_err().to_string().contains("add up to 4"));
+    }
+
+    #[test]
+    fn test_verify_questionnaire() {
+        let export = finalized_questionnaire();
+        assert_eq!(export.questions.len(), 2);
+        assert_eq!(export.question_results[0][0].votes, 2);
+        verify(&export).unwrap();
+
+        let mut tampered = export.clone();
+        tampered.question_results[1][0].votes += 1;
+        assert!(verify(&tampered).unwrap_err().to_string().contains("does not match"));
+
+        let mut tampered = export;
+        tampered.questions[0].prompt.push('!');
+        assert!(verify(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let export = finalized_survey();
+
+        let mut tampered = export.clone();
+        tampered.results.swap(0, 1);
+        assert!(verify(&tampered).unwrap_err().to_string().contains("does not match"));
+
+        let mut tampered = export.clone();
+        tampered.voters.pop();
+        assert!(verify(&tampered).is_err());
+
+        let mut tampered = export;
+        tampered.survey.description.push('!');
+        assert!(verify(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_parse_cli_output() {
+        let json = r#"{
+            "survey": {"survey_id": 1, "creator": "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+                       "name": "N", "description": "D", "start_date": "1000", "end_date": "2000",
+                       "created_at": "0", "candidates": []},
+            "results": [],
+            "voters": [],
+            "certificate": {"survey_id": "1", "hash": "00", "ledger": 5, "total_votes": "0"}
+        }"#;
+        let export: ResultsExport = serde_json::from_str(json).unwrap();
+        assert_eq!(export.survey.end_date, 2000);
+        assert_eq!(export.certificate.ledger, 5);
+        assert!(verify(&export).is_err());
+    }
+}
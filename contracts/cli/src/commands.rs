@@ -0,0 +1,351 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::deploy_info::DeployInfo;
+use crate::stellar::Network;
+
+/// Contratos en orden de dependencia: los registros antes que `survey`
+pub const CONTRACTS: [&str; 8] = [
+    "user_registry",
+    "candidate_registry",
+    "survey",
+    "governance",
+    "encrypted_ballot",
+    "anonymous_vote",
+    "survey_series",
+    "party_list",
+];
+
+/// Contratos que `survey` acepta como urnas en `set_ballot_box`
+const BALLOT_BOXES: [&str; 3] = ["encrypted_ballot", "anonymous_vote", "party_list"];
+
+const WASM_TARGET: &str = "wasm32v1-none";
+
+/// Encuestas que `survey.migrate` reescribe por transacción
+const MIGRATE_BATCH: &str = "50";
+
+/// Ruta del wasm optimizado de un contrato dentro del workspace
+pub fn wasm_path(workspace: &Path, contract: &str) -> PathBuf {
+    workspace.join("target").join(WASM_TARGET).join("release").join(format!("{contract}.wasm"))
+}
+
+/// Compila los contratos indicados para wasm, cada uno con su propio `cargo build`
+/// para que las features de `dappcenvote-types` que activa uno no engorden a los demás
+pub fn build(workspace: &Path, contracts: &[&str]) -> Result<()> {
+    for contract in contracts {
+        let mut cmd = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+        cmd.current_dir(workspace).args(["build", "--release", "--target", WASM_TARGET, "-p", contract]);
+
+        println!("Building {contract}...");
+        let status = cmd.status().context("failed to run cargo")?;
+        if !status.success() {
+            bail!("cargo build of {contract} failed with {status}");
+        }
+    }
+    Ok(())
+}
+
+/// Sube y despliega los contratos, cada uno inicializado por su constructor en
+/// la misma transacción, y vincula los registros, el programador y las urnas con `survey`.
+/// `deploy-info.json` se guarda tras cada paso para que un despliegue
+/// interrumpido no pierda los ids ya creados.
+pub fn deploy(
+    network: &mut impl Network,
+    network_name: &str,
+    workspace: &Path,
+    info: &mut DeployInfo,
+) -> Result<()> {
+    let admin = network.source_address()?;
+    println!("Deploying to {network_name} with admin {admin}");
+
+    for contract in CONTRACTS {
+        // Las urnas y las series se construyen con el contrato de encuestas, ya desplegado
+        let survey = info.contract_id("survey").unwrap_or_default().to_string();
+        let mut args = vec![("admin", admin.as_str())];
+        if contract == "survey_series" || BALLOT_BOXES.contains(&contract) {
+            args.push(("survey_contract", survey.as_str()));
+        }
+
+        let wasm_hash = network.upload(&wasm_path(workspace, contract))?;
+        let contract_id = network.deploy(&wasm_hash, &args)?;
+        println!("  {contract}: {contract_id}");
+        info.set_deployed(network_name, contract, &contract_id, &wasm_hash);
+        info.save()?;
+    }
+
+    let survey = info.contract_id("survey").unwrap_or_default().to_string();
+    let user_registry = info.contract_id("user_registry").unwrap_or_default().to_string();
+    let candidate_registry = info.contract_id("candidate_registry").unwrap_or_default().to_string();
+    network.invoke(
+        &survey,
+        "set_registries",
+        &[("user_registry", &user_registry), ("candidate_registry", &candidate_registry)],
+    )?;
+    println!("  survey wired to user_registry and candidate_registry");
+
+    let survey_series = info.contract_id("survey_series").unwrap_or_default().to_string();
+    network.invoke(&survey, "set_scheduler", &[("scheduler", &survey_series)])?;
+    println!("  survey_series set as the survey scheduler");
+
+    for ballot_box in BALLOT_BOXES {
+        let ballot_box = info.contract_id(ballot_box).unwrap_or_default().to_string();
+        network.invoke(&survey, "add_ballot_box_contract", &[("ballot_box", &ballot_box)])?;
+    }
+    println!("  {} approved as survey ballot boxes", BALLOT_BOXES.join(", "));
+    Ok(())
+}
+
+/// Actualiza un contrato desplegado a su wasm actual y migra su almacenamiento
+pub fn upgrade(
+    network: &mut impl Network,
+    workspace: &Path,
+    info: &mut DeployInfo,
+    contract: &str,
+) -> Result<()> {
+    if !CONTRACTS.contains(&contract) {
+        bail!("unknown contract `{contract}`; expected one of {}", CONTRACTS.join(", "));
+    }
+    let contract_id = info
+        .contract_id(contract)
+        .with_context(|| format!("{contract} has no contract_id in deploy-info.json"))?
+        .to_string();
+
+    let wasm_hash = network.upload(&wasm_path(workspace, contract))?;
+    if info.wasm_hash(contract) == Some(wasm_hash.as_str()) {
+        println!("{contract} is already running {wasm_hash}");
+        return Ok(());
+    }
+    // Governance no tiene `upgrade`: su código solo cambia con una propuesta aprobada
+    if contract == "governance" {
+        bail!(
+            "governance upgrades through a proposal: propose the action {{\"Upgrade\": \"{wasm_hash}\"}}, \
+             execute it after the timelock, then invoke `migrate` and record the hash in deploy-info.json"
+        );
+    }
+
+    network.invoke(&contract_id, "upgrade", &[("new_wasm_hash", &wasm_hash)])?;
+    // `survey` migra por lotes y retorna true al terminar; los demás en una llamada.
+    // `migrate` no hace nada si el formato ya está al día, así que cualquier error es real
+    let args: &[(&str, &str)] = if contract == "survey" { &[("limit", MIGRATE_BATCH)] } else { &[] };
+    loop {
+        let done = network
+            .invoke(&contract_id, "migrate", args)
+            .with_context(|| format!("{contract} was upgraded to {wasm_hash} but its migration failed"))?;
+        if done != "false" {
+            break;
+        }
+    }
+    let version = network.invoke(&contract_id, "version", &[])?;
+    println!("{contract} upgraded to {wasm_hash} (storage version {version})");
+
+    info.set_wasm_hash(contract, &wasm_hash);
+    info.save()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    // Red en memoria que registra cada llamada
+    #[derive(Default)]
+    struct FakeNetwork {
+        calls: Vec<String>,
+        deployed: u32,
+        release: u32,        // Versión de los wasm compilados
+        migrate_fails: bool, // Simula un migrate que falla en la red
+    }
+
+    impl Network for FakeNetwork {
+        fn source_address(&mut self) -> Result<String> {
+            Ok("GADMIN".into())
+        }
+
+        fn upload(&mut self, wasm: &Path) -> Result<String> {
+            let name = wasm.file_stem().unwrap().to_str().unwrap();
+            self.calls.push(format!("upload {name}"));
+            Ok(format!("{name}-v{}", self.release))
+        }
+
+        fn deploy(&mut self, wasm_hash: &str, args: &[(&str, &str)]) -> Result<String> {
+            self.deployed += 1;
+            self.calls.push(format!("deploy {wasm_hash} {}", format_args(args)).trim_end().into());
+            Ok(format!("C{}", self.deployed))
+        }
+
+        fn invoke(&mut self, contract_id: &str, function: &str, args: &[(&str, &str)]) -> Result<String> {
+            self.calls.push(format!("invoke {contract_id} {function} {}", format_args(args)).trim_end().into());
+            match function {
+                "migrate" if self.migrate_fails => bail!("transaction simulation failed"),
+                "migrate" => Ok("true".into()),
+                _ => Ok("2".into()),
+            }
+        }
+    }
+
+    fn format_args(args: &[(&str, &str)]) -> String {
+        let args: Vec<String> = args.iter().map(|(name, value)| format!("{name}={value}")).collect();
+        args.join(" ")
+    }
+
+    fn temp_deploy_info(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dappcenvote-cli-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("deploy-info.json");
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    const DEPLOY_INFO: &str = r#"{
+    "network": "testnet",
+    "deployed_at": "2025-11-27",
+    "contracts": {
+        "user_registry": { "contract_id": "", "wasm_hash": "old", "functions": ["register_user"] },
+        "candidate_registry": { "contract_id": "", "wasm_hash": "old", "functions": [] },
+        "survey": { "contract_id": "", "wasm_hash": "old", "functions": ["vote"] }
+    }
+}"#;
+
+    #[test]
+    fn test_deploy_in_dependency_order() {
+        let path = temp_deploy_info("deploy", DEPLOY_INFO);
+        let mut info = DeployInfo::load(&path).unwrap();
+        let mut network = FakeNetwork::default();
+
+        deploy(&mut network, "local", Path::new("."), &mut info).unwrap();
+
+        assert_eq!(
+            network.calls,
+            [
+                "upload user_registry",
+                "deploy user_registry-v0 admin=GADMIN",
+                "upload candidate_registry",
+                "deploy candidate_registry-v0 admin=GADMIN",
+                "upload survey",
+                "deploy survey-v0 admin=GADMIN",
+                "upload governance",
+                "deploy governance-v0 admin=GADMIN",
+                "upload encrypted_ballot",
+                "deploy encrypted_ballot-v0 admin=GADMIN survey_contract=C3",
+                "upload anonymous_vote",
+                "deploy anonymous_vote-v0 admin=GADMIN survey_contract=C3",
+                "upload survey_series",
+                "deploy survey_series-v0 admin=GADMIN survey_contract=C3",
+                "upload party_list",
+                "deploy party_list-v0 admin=GADMIN survey_contract=C3",
+                "invoke C3 set_registries user_registry=C1 candidate_registry=C2",
+                "invoke C3 set_scheduler scheduler=C7",
+                "invoke C3 add_ballot_box_contract ballot_box=C5",
+                "invoke C3 add_ballot_box_contract ballot_box=C6",
+                "invoke C3 add_ballot_box_contract ballot_box=C8",
+            ]
+        );
+
+        // El archivo conserva las funciones y registra ids y hashes
+        let saved = DeployInfo::load(&path).unwrap();
+        assert_eq!(saved.network(), Some("local"));
+        assert_eq!(saved.contract_id("survey"), Some("C3"));
+        assert_eq!(saved.wasm_hash("user_registry"), Some("user_registry-v0"));
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("\"register_user\""));
+        assert!(text.starts_with("{\n    \"network\""));
+    }
+
+    #[test]
+    fn test_upgrade_keeps_contract_id() {
+        let path = temp_deploy_info("upgrade", DEPLOY_INFO);
+        let mut info = DeployInfo::load(&path).unwrap();
+        let mut network = FakeNetwork::default();
+        deploy(&mut network, "local", Path::new("."), &mut info).unwrap();
+
+        // Sin cambios en el wasm no hay nada que actualizar
+        network.calls.clear();
+        upgrade(&mut network, Path::new("."), &mut info, "survey").unwrap();
+        assert_eq!(network.calls, ["upload survey"]);
+
+        network.calls.clear();
+        network.release = 1;
+        upgrade(&mut network, Path::new("."), &mut info, "survey").unwrap();
+        assert_eq!(
+            network.calls,
+            [
+                "upload survey",
+                "invoke C3 upgrade new_wasm_hash=survey-v1",
+                "invoke C3 migrate limit=50",
+                "invoke C3 version",
+            ]
+        );
+
+        let saved = DeployInfo::load(&path).unwrap();
+        assert_eq!(saved.contract_id("survey"), Some("C3"));
+        assert_eq!(saved.wasm_hash("survey"), Some("survey-v1"));
+    }
+
+    #[test]
+    fn test_upgrade_fails_when_migrate_fails() {
+        let path = temp_deploy_info("migrate-fails", DEPLOY_INFO);
+        let mut info = DeployInfo::load(&path).unwrap();
+        let mut network = FakeNetwork::default();
+        deploy(&mut network, "local", Path::new("."), &mut info).unwrap();
+
+        network.release = 1;
+        network.migrate_fails = true;
+        let err = upgrade(&mut network, Path::new("."), &mut info, "survey").unwrap_err();
+        assert!(err.to_string().contains("migration failed"));
+
+        // El hash no se actualiza para que el siguiente upgrade vuelva a migrar
+        let saved = DeployInfo::load(&path).unwrap();
+        assert_eq!(saved.wasm_hash("survey"), Some("survey-v0"));
+    }
+
+    #[test]
+    fn test_upgrade_requires_deployment() {
+        let path = temp_deploy_info("undeployed", DEPLOY_INFO);
+        let mut info = DeployInfo::load(&path).unwrap();
+        let mut network = FakeNetwork::default();
+
+        let err = upgrade(&mut network, Path::new("."), &mut info, "survey").unwrap_err();
+        assert!(err.to_string().contains("no contract_id"));
+        assert!(upgrade(&mut network, Path::new("."), &mut info, "passkey").is_err());
+        assert!(network.calls.is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_governance_needs_proposal() {
+        let path = temp_deploy_info("governance", DEPLOY_INFO);
+        let mut info = DeployInfo::load(&path).unwrap();
+        let mut network = FakeNetwork::default();
+        deploy(&mut network, "local", Path::new("."), &mut info).unwrap();
+
+        network.calls.clear();
+        network.release = 1;
+        let err = upgrade(&mut network, Path::new("."), &mut info, "governance").unwrap_err();
+        assert!(err.to_string().contains("{\"Upgrade\": \"governance-v1\"}"));
+        assert_eq!(network.calls, ["upload governance"]);
+    }
+
+    // Despliegue real contra una red local:
+    //   stellar container start local
+    //   stellar keys generate deployer --network local --fund
+    //   cargo test -p dappcenvote-cli -- --ignored
+    #[test]
+    #[ignore]
+    fn test_deploy_to_local_network() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        build(workspace, &CONTRACTS).unwrap();
+
+        let path = temp_deploy_info("local", "{}");
+        let mut info = DeployInfo::load(&path).unwrap();
+        let mut network = crate::stellar::StellarCli {
+            network: std::env::var("STELLAR_NETWORK").unwrap_or_else(|_| "local".into()),
+            source: "deployer".into(),
+        };
+        deploy(&mut network, "local", workspace, &mut info).unwrap();
+
+        let survey = info.contract_id("survey").unwrap().to_string();
+        let registries = network.invoke(&survey, "get_registries", &[]).unwrap();
+        assert!(registries.contains(info.contract_id("user_registry").unwrap()));
+    }
+}
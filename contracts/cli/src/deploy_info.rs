@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, ser::PrettyFormatter, Map, Value};
+
+/// `deploy-info.json`: red, fecha y, por contrato, su id, hash de wasm y funciones.
+/// Se conserva cualquier otro campo del archivo al reescribirlo.
+pub struct DeployInfo {
+    path: PathBuf,
+    value: Value,
+}
+
+impl DeployInfo {
+    /// Lee el archivo; si no existe se parte de uno vacío
+    pub fn load(path: &Path) -> Result<Self> {
+        let value = match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("invalid JSON in {}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => json!({}),
+            Err(err) => return Err(err).with_context(|| format!("failed to read {}", path.display())),
+        };
+        Ok(DeployInfo { path: path.to_path_buf(), value })
+    }
+
+    /// Escribe el archivo con la misma sangría de 4 espacios del repositorio
+    pub fn save(&self) -> Result<()> {
+        let mut out = Vec::new();
+        let mut serializer =
+            serde_json::Serializer::with_formatter(&mut out, PrettyFormatter::with_indent(b"    "));
+        self.value.serialize(&mut serializer)?;
+        out.push(b'\n');
+        fs::write(&self.path, out).with_context(|| format!("failed to write {}", self.path.display()))
+    }
+
+    pub fn network(&self) -> Option<&str> {
+        self.value.get("network")?.as_str()
+    }
+
+    pub fn contract_id(&self, contract: &str) -> Option<&str> {
+        let id = self.contract(contract)?.get("contract_id")?.as_str()?;
+        (!id.is_empty()).then_some(id)
+    }
+
+    pub fn wasm_hash(&self, contract: &str) -> Option<&str> {
+        self.contract(contract)?.get("wasm_hash")?.as_str()
+    }
+
+    /// Registra un despliegue nuevo y marca la fecha de hoy
+    pub fn set_deployed(&mut self, network: &str, contract: &str, contract_id: &str, wasm_hash: &str) {
+        let root = self.root();
+        root.insert("network".into(), json!(network));
+        root.insert("deployed_at".into(), json!(today()));
+
+        let entry = self.contract_mut(contract);
+        entry.insert("contract_id".into(), json!(contract_id));
+        entry.insert("wasm_hash".into(), json!(wasm_hash));
+    }
+
+    /// Registra un nuevo wasm para un contrato existente (upgrade)
+    pub fn set_wasm_hash(&mut self, contract: &str, wasm_hash: &str) {
+        self.contract_mut(contract).insert("wasm_hash".into(), json!(wasm_hash));
+    }
+
+    fn contract(&self, contract: &str) -> Option<&Value> {
+        self.value.get("contracts")?.get(contract)
+    }
+
+    fn root(&mut self) -> &mut Map<String, Value> {
+        if !self.value.is_object() {
+            self.value = json!({});
+        }
+        self.value.as_object_mut().unwrap()
+    }
+
+    fn contract_mut(&mut self, contract: &str) -> &mut Map<String, Value> {
+        let contracts = self.root().entry("contracts").or_insert_with(|| json!({}));
+        let entry = contracts
+            .as_object_mut()
+            .expect("`contracts` must be an object")
+            .entry(contract)
+            .or_insert_with(|| json!({ "contract_id": "", "wasm_hash": "", "functions": [] }));
+        entry.as_object_mut().expect("contract entry must be an object")
+    }
+}
+
+// Fecha UTC actual como AAAA-MM-DD
+fn today() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// Días desde 1970-01-01 a fecha del calendario gregoriano (algoritmo de H. Hinnant)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(20_419), (2025, 11, 27));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+}
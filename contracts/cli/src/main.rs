@@ -0,0 +1,130 @@
+//! `dappcenvote-cli`: compila, despliega, inicializa y actualiza los contratos
+//! de DappCenvote en lugar del antiguo `scripts/deploy.js`. Las operaciones de
+//! red se delegan en `stellar-cli`.
+mod certificate;
+mod commands;
+mod deploy_info;
+mod stellar;
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+
+use deploy_info::DeployInfo;
+use stellar::StellarCli;
+
+#[derive(Parser)]
+#[command(name = "dappcenvote-cli", version, about = "Deploy and administer the DappCenvote contracts")]
+struct Cli {
+    /// Network configured in stellar-cli (testnet, local, ...)
+    #[arg(long, global = true, env = "STELLAR_NETWORK", default_value = "testnet")]
+    network: String,
+
+    /// stellar-cli identity that signs transactions and becomes admin
+    #[arg(long, global = true, env = "STELLAR_ACCOUNT", default_value = "deployer")]
+    source: String,
+
+    /// Cargo workspace containing the contracts
+    #[arg(long, global = true, default_value = ".")]
+    workspace: PathBuf,
+
+    /// Deployment manifest to read and update
+    #[arg(long, global = true, default_value = "deploy-info.json")]
+    deploy_info: PathBuf,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Build the contract wasm files
+    Build,
+    /// Upload, deploy, initialize and wire all contracts
+    Deploy {
+        /// Reuse the wasm files already in target/
+        #[arg(long)]
+        skip_build: bool,
+    },
+    /// Upgrade a deployed contract to the current wasm and migrate its storage
+    Upgrade {
+        /// user_registry, candidate_registry, survey, governance, encrypted_ballot, anonymous_vote,
+        /// survey_series or party_list
+        contract: String,
+        /// Reuse the wasm file already in target/
+        #[arg(long)]
+        skip_build: bool,
+    },
+    /// Show the contracts recorded in the deployment manifest
+    Info,
+    /// Export a finalized survey's results, voter roll and certificate as JSON
+    ExportResults {
+        #[arg(long)]
+        survey_id: u64,
+        /// File to write; prints to stdout when omitted
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Recompute a results certificate offline from an export
+    VerifyCertificate { export: PathBuf },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut info = DeployInfo::load(&cli.deploy_info)?;
+    let mut network = StellarCli { network: cli.network.clone(), source: cli.source.clone() };
+
+    match cli.command {
+        Commands::Build => commands::build(&cli.workspace, &commands::CONTRACTS),
+        Commands::Deploy { skip_build } => {
+            if !skip_build {
+                commands::build(&cli.workspace, &commands::CONTRACTS)?;
+            }
+            commands::deploy(&mut network, &cli.network, &cli.workspace, &mut info)?;
+            println!("Deployment info saved to {}", cli.deploy_info.display());
+            Ok(())
+        }
+        Commands::Upgrade { contract, skip_build } => {
+            if !skip_build {
+                commands::build(&cli.workspace, &[contract.as_str()])?;
+            }
+            commands::upgrade(&mut network, &cli.workspace, &mut info, &contract)
+        }
+        Commands::Info => {
+            println!("network: {}", info.network().unwrap_or("-"));
+            for contract in commands::CONTRACTS {
+                println!(
+                    "{contract}: {} (wasm {})",
+                    info.contract_id(contract).unwrap_or("not deployed"),
+                    info.wasm_hash(contract).unwrap_or("-")
+                );
+            }
+            Ok(())
+        }
+        Commands::ExportResults { survey_id, out } => {
+            let survey = info.contract_id("survey").context("survey is not deployed")?.to_string();
+            let export = certificate::export(&mut network, &survey, survey_id)?;
+            let json = serde_json::to_string_pretty(&export)? + "\n";
+            match out {
+                Some(path) => fs::write(&path, json).with_context(|| format!("failed to write {}", path.display())),
+                None => {
+                    print!("{json}");
+                    Ok(())
+                }
+            }
+        }
+        Commands::VerifyCertificate { export } => {
+            let text = fs::read_to_string(&export)
+                .with_context(|| format!("failed to read {}", export.display()))?;
+            let export: certificate::ResultsExport = serde_json::from_str(&text)?;
+            certificate::verify(&export)?;
+            println!(
+                "certificate {} for survey {} is valid (ledger {})",
+                export.certificate.hash, export.survey.survey_id, export.certificate.ledger
+            );
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+/// Operaciones de red que necesita la CLI. `StellarCli` las delega en el
+/// binario `stellar`; las pruebas usan una implementación en memoria.
+pub trait Network {
+    /// Dirección pública de la cuenta que firma las transacciones
+    fn source_address(&mut self) -> Result<String>;
+    /// Sube un wasm y retorna su hash
+    fn upload(&mut self, wasm: &Path) -> Result<String>;
+    /// Crea una instancia de un wasm ya subido, ejecutando su constructor con
+    /// `args` en la misma transacción, y retorna su contract id
+    fn deploy(&mut self, wasm_hash: &str, args: &[(&str, &str)]) -> Result<String>;
+    /// Invoca una función del contrato y retorna su valor de retorno en JSON
+    fn invoke(&mut self, contract_id: &str, function: &str, args: &[(&str, &str)]) -> Result<String>;
+}
+
+/// Ejecuta `stellar` con la red y la identidad indicadas
+pub struct StellarCli {
+    pub network: String,
+    pub source: String,
+}
+
+impl StellarCli {
+    fn run(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("stellar")
+            .args(args)
+            .output()
+            .context("failed to run `stellar`; is stellar-cli installed?")?;
+        if !output.status.success() {
+            bail!(
+                "`stellar {}` failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        // La última línea de stdout es el resultado; el resto es progreso
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let result = stdout.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("");
+        Ok(result.trim().to_string())
+    }
+
+    fn network_args(&self) -> [&str; 4] {
+        ["--source", self.source.as_str(), "--network", self.network.as_str()]
+    }
+}
+
+impl Network for StellarCli {
+    fn source_address(&mut self) -> Result<String> {
+        self.run(&["keys", "address", &self.source])
+    }
+
+    fn upload(&mut self, wasm: &Path) -> Result<String> {
+        let wasm = wasm.to_str().context("wasm path is not valid UTF-8")?;
+        let mut args = vec!["contract", "upload", "--wasm", wasm];
+        args.extend(self.network_args());
+        self.run(&args)
+    }
+
+    fn deploy(&mut self, wasm_hash: &str, args: &[(&str, &str)]) -> Result<String> {
+        let flags = arg_flags(args);
+        let mut cmd = vec!["contract", "deploy", "--wasm-hash", wasm_hash];
+        cmd.extend(self.network_args());
+        cmd.push("--");
+        push_args(&mut cmd, &flags, args);
+        self.run(&cmd)
+    }
+
+    fn invoke(&mut self, contract_id: &str, function: &str, args: &[(&str, &str)]) -> Result<String> {
+        let flags = arg_flags(args);
+        let mut cmd = vec!["contract", "invoke", "--id", contract_id];
+        cmd.extend(self.network_args());
+        cmd.extend(["--", function]);
+        push_args(&mut cmd, &flags, args);
+        self.run(&cmd)
+    }
+}
+
+fn arg_flags(args: &[(&str, &str)]) -> Vec<String> {
+    args.iter().map(|(name, _)| format!("--{name}")).collect()
+}
+
+// Agrega `--nombre valor` por cada argumento de la función o del constructor
+fn push_args<'a>(cmd: &mut Vec<&'a str>, flags: &'a [String], args: &[(&str, &'a str)]) {
+    for (flag, (_, value)) in flags.iter().zip(args) {
+        cmd.push(flag);
+        cmd.push(value);
+    }
+}
@@ -0,0 +1,975 @@
+#![no_std]
+//! Urna cifrada para encuestas de candidatos. Cada papeleta lleva un cifrado
+//! ElGamal exponencial sobre G1 de BLS12-381 por candidato, con pruebas de que
+//! cada uno cifra 0 o 1 y de que suman exactamente un voto. El contrato solo
+//! acumula los cifrados; al cierre, `threshold` custodios publican descifrados
+//! parciales del agregado con pruebas de Chaum-Pedersen y cualquiera publica el
+//! conteo, que se verifica en la cadena y se entrega a la encuesta.
+//!
+//! La clave se genera sin repartidor (Feldman conjunto): cada custodio publica
+//! los compromisos de su polinomio y envía en privado una evaluación a cada uno
+//! de los demás, que la verifican con `dappcenvote-trustee`.
+use dappcenvote_types::{
+    encrypted_ballot::{
+        Ciphertext, DataKey, DecryptionShare, DecryptionShared, Election, ElectionCreated,
+        ElectionKeyReady, EncryptedBallot, EncryptedBallotError, EncryptedBallotInterface,
+        EncryptedVoteCast, EqualityProof, KeyProof, TallyPublished,
+    },
+    survey::{Survey, SurveyClient},
+    user_registry::UserRegistryClient,
+    PauseScope,
+};
+use soroban_sdk::{
+    bytesn, contract, contractimpl,
+    crypto::bls12_381::{Fr, G1Affine},
+    panic_with_error,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Vec, U256,
+};
+
+const CONTRACT_VERSION: u32 = 1;
+const MAX_TRUSTEES: u32 = 10;
+const MAX_CANDIDATES: u32 = 8; // Cada candidato cuesta cuatro MSM al verificar una papeleta
+
+// Etiquetas de dominio de los transcritos de Fiat-Shamir (ver dappcenvote-trustee)
+const KEY_TAG: &[u8] = b"dappcenvote/key";
+const BIT_TAG: &[u8] = b"dappcenvote/bit";
+const SUM_TAG: &[u8] = b"dappcenvote/sum";
+const SHARE_TAG: &[u8] = b"dappcenvote/share";
+
+#[contract]
+pub struct EncryptedBallotBox;
+
+#[contractimpl]
+impl EncryptedBallotBox {
+    /// Inicializa el contrato al desplegarlo, con el administrador y el contrato de encuestas
+    pub fn __constructor(env: Env, admin: Address, survey_contract: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        env.storage().instance().set(&DataKey::Survey, &survey_contract);
+        env.storage().instance().extend_ttl(100, 8640000);
+    }
+}
+
+#[contractimpl]
+impl EncryptedBallotInterface for EncryptedBallotBox {
+    /// Reemplaza el código del contrato conservando su almacenamiento (solo administrador)
+    /// Debe seguirse de `migrate` si la nueva versión cambia el formato de los datos
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Migra el almacenamiento a la versión de este código (solo administrador)
+    /// Sin efecto si el almacenamiento ya está en esa versión
+    /// La versión 1 es el formato original, por lo que aún no hay transformaciones
+    fn migrate(env: Env) {
+        Self::require_admin(&env);
+
+        let version = Self::version(env.clone());
+        if version >= CONTRACT_VERSION {
+            return; // Ya está al día; repetir la llamada no es un error
+        }
+
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+    }
+
+    /// Obtiene la versión del formato de almacenamiento
+    fn version(env: Env) -> u32 {
+        let key = DataKey::ContractVersion;
+        env.storage().instance().get(&key).unwrap_or(1)
+    }
+
+    /// Convierte una encuesta en elección cifrada (solo el creador, antes del inicio)
+    /// Desde entonces la encuesta rechaza los votos en claro
+    fn create_election(env: Env, survey_id: u64, trustees: Vec<Address>, threshold: u32) {
+        let survey_client = Self::survey_client(&env);
+        let survey = survey_client.get_survey(&survey_id).expect("Survey not found");
+        survey.creator.require_auth();
+
+        let election_key = DataKey::Election(survey_id);
+        if env.storage().persistent().has(&election_key) {
+            panic!("Election already exists");
+        }
+        if trustees.is_empty() || trustees.len() > MAX_TRUSTEES {
+            panic!("Trustees must be between 1 and 10");
+        }
+        if threshold == 0 || threshold > trustees.len() {
+            panic!("Threshold must be between 1 and the number of trustees");
+        }
+        for (i, trustee) in trustees.iter().enumerate() {
+            if trustees.iter().skip(i + 1).any(|other| other == trustee) {
+                panic!("Duplicate trustee");
+            }
+        }
+        if survey.candidates.len() > MAX_CANDIDATES {
+            panic!("Too many candidates for an encrypted election");
+        }
+
+        // La encuesta valida que siga en borrador y que tenga candidatos
+        survey_client.set_ballot_box(&survey_id, &env.current_contract_address());
+
+        let election = Election {
+            survey_id,
+            trustees: trustees.clone(),
+            threshold,
+            committed: 0,
+            public_key: None,
+            ballots: 0,
+            tallied: false,
+        };
+        Self::save_election(&env, &election);
+
+        let identity = Self::identity(&env).to_bytes();
+        let mut commitments = Vec::new(&env);
+        for _ in 0..threshold {
+            commitments.push_back(identity.clone());
+        }
+        Self::set_persistent(&env, &DataKey::Commitments(survey_id), &commitments);
+        let mut aggregate = Vec::new(&env);
+        for _ in survey.candidates.iter() {
+            aggregate.push_back(Ciphertext { a: identity.clone(), b: identity.clone() });
+        }
+        Self::set_persistent(&env, &DataKey::Aggregate(survey_id), &aggregate);
+
+        ElectionCreated { survey_id, trustees, threshold }.publish(&env);
+    }
+
+    /// Publica los compromisos de Feldman de un custodio, con la prueba de que
+    /// conoce el término independiente (solo el custodio, antes del inicio)
+    /// Cuando todos publican, la clave de la elección es la suma de los primeros
+    fn commit_key(
+        env: Env,
+        survey_id: u64,
+        trustee: Address,
+        commitments: Vec<BytesN<96>>,
+        proof: KeyProof,
+    ) {
+        trustee.require_auth();
+
+        let mut election = Self::load_election(&env, survey_id);
+        let index = Self::trustee_index(&env, &election, &trustee);
+        let survey = Self::load_survey(&env, survey_id);
+        if env.ledger().timestamp() >= survey.start_date {
+            panic!("Survey has already started");
+        }
+        let trustee_key = DataKey::TrusteeCommitments(survey_id, trustee);
+        if env.storage().persistent().has(&trustee_key) {
+            panic_with_error!(&env, EncryptedBallotError::AlreadyCommitted);
+        }
+        if commitments.len() != election.threshold {
+            panic!("Expected one commitment per threshold coefficient");
+        }
+
+        let bls = env.crypto().bls12_381();
+        for commitment in commitments.iter() {
+            if !bls.g1_is_in_subgroup(&G1Affine::from_bytes(commitment)) {
+                panic_with_error!(&env, EncryptedBallotError::InvalidProof);
+            }
+        }
+        let constant = G1Affine::from_bytes(commitments.get(0).unwrap());
+        let nonce = G1Affine::from_bytes(proof.commitment);
+        let c = Self::challenge(
+            &env,
+            KEY_TAG,
+            survey_id,
+            &Bytes::from_array(&env, &index.to_be_bytes()),
+            &[&constant, &nonce],
+        );
+        let expected = Self::commit(&env, &Self::generator(&env), &Fr::from_bytes(proof.response), &constant, &c);
+        if expected.to_bytes() != nonce.to_bytes() {
+            panic_with_error!(&env, EncryptedBallotError::InvalidProof);
+        }
+
+        let mut aggregate = Self::get_commitments(env.clone(), survey_id);
+        for (k, commitment) in commitments.iter().enumerate() {
+            let sum = bls.g1_add(
+                &G1Affine::from_bytes(aggregate.get(k as u32).unwrap()),
+                &G1Affine::from_bytes(commitment),
+            );
+            aggregate.set(k as u32, sum.to_bytes());
+        }
+        Self::set_persistent(&env, &DataKey::Commitments(survey_id), &aggregate);
+        Self::set_persistent(&env, &trustee_key, &commitments);
+
+        election.committed += 1;
+        if election.committed == election.trustees.len() {
+            let public_key = aggregate.get(0).unwrap();
+            election.public_key = Some(public_key.clone());
+            ElectionKeyReady { survey_id, public_key }.publish(&env);
+        }
+        Self::save_election(&env, &election);
+    }
+
+    /// Emite una papeleta cifrada durante la votación
+    /// Se exige lo mismo que en `vote` (electorado y registro) y se verifican las pruebas
+    fn cast_ballot(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        ballot: EncryptedBallot,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32> {
+        voter.require_auth();
+
+        let survey_client = Self::survey_client(&env);
+        if survey_client.is_paused(&PauseScope::Voting) {
+            panic_with_error!(&env, EncryptedBallotError::Paused);
+        }
+        let mut election = Self::load_election(&env, survey_id);
+        let public_key = election
+            .public_key
+            .clone()
+            .unwrap_or_else(|| panic_with_error!(&env, EncryptedBallotError::KeyNotReady));
+
+        let survey = Self::load_survey(&env, survey_id);
+        let current_time = env.ledger().timestamp();
+        if current_time < survey.start_date {
+            panic!("Survey has not started yet");
+        }
+        if current_time > survey.end_date {
+            panic!("Survey has ended");
+        }
+        if survey_client.is_cancelled(&survey_id) {
+            panic!("Survey has been cancelled");
+        }
+        if !survey_client.is_eligible(&survey_id, &voter, &proof) {
+            panic_with_error!(&env, EncryptedBallotError::NotEligible);
+        }
+        if let Some(registries) = survey_client.get_registries() {
+            if !UserRegistryClient::new(&env, &registries.user_registry).user_exists(&voter) {
+                panic_with_error!(&env, EncryptedBallotError::NotRegistered);
+            }
+        }
+
+        let ballot_key = DataKey::Ballot(survey_id, voter.clone());
+        if env.storage().persistent().has(&ballot_key) {
+            panic_with_error!(&env, EncryptedBallotError::AlreadyVoted);
+        }
+        if ballot.choices.len() != survey.candidates.len() {
+            panic!("Ballot must encrypt one choice per candidate");
+        }
+        if !Self::verify_ballot(&env, survey_id, &voter, &G1Affine::from_bytes(public_key), &ballot) {
+            panic_with_error!(&env, EncryptedBallotError::InvalidProof);
+        }
+
+        // Sumar los cifrados al agregado de cada candidato
+        let bls = env.crypto().bls12_381();
+        let mut aggregate = Self::get_aggregate(env.clone(), survey_id);
+        for (i, choice) in ballot.choices.iter().enumerate() {
+            let sum = aggregate.get(i as u32).unwrap();
+            aggregate.set(
+                i as u32,
+                Ciphertext {
+                    a: bls
+                        .g1_add(&G1Affine::from_bytes(sum.a), &G1Affine::from_bytes(choice.ciphertext.a))
+                        .to_bytes(),
+                    b: bls
+                        .g1_add(&G1Affine::from_bytes(sum.b), &G1Affine::from_bytes(choice.ciphertext.b))
+                        .to_bytes(),
+                },
+            );
+        }
+        Self::set_persistent(&env, &DataKey::Aggregate(survey_id), &aggregate);
+        let commitment = env.crypto().sha256(&ballot.clone().to_xdr(&env)).to_bytes();
+        Self::set_persistent(&env, &ballot_key, &ballot);
+
+        let voters_key = DataKey::Voters(survey_id);
+        let mut voters: Vec<Address> = env.storage().persistent().get(&voters_key).unwrap_or(Vec::new(&env));
+        voters.push_back(voter.clone());
+        Self::set_persistent(&env, &voters_key, &voters);
+
+        election.ballots += 1;
+        Self::save_election(&env, &election);
+        EncryptedVoteCast { survey_id, voter: voter.clone() }.publish(&env);
+
+        // El recibo solo compromete la papeleta cifrada
+        survey_client.record_receipt(&survey_id, &voter, &commitment)
+    }
+
+    /// Publica los descifrados parciales de un custodio, uno por candidato y en
+    /// su orden, tras el cierre (solo el custodio)
+    fn submit_shares(env: Env, survey_id: u64, trustee: Address, shares: Vec<DecryptionShare>) {
+        trustee.require_auth();
+
+        let election = Self::load_election(&env, survey_id);
+        let index = Self::trustee_index(&env, &election, &trustee);
+        let survey = Self::load_survey(&env, survey_id);
+        if env.ledger().timestamp() <= survey.end_date {
+            panic!("Survey has not ended yet");
+        }
+        if election.public_key.is_none() {
+            panic_with_error!(&env, EncryptedBallotError::KeyNotReady);
+        }
+        if election.tallied {
+            panic_with_error!(&env, EncryptedBallotError::AlreadyTallied);
+        }
+        let shares_key = DataKey::Shares(survey_id, trustee.clone());
+        if env.storage().persistent().has(&shares_key) {
+            panic_with_error!(&env, EncryptedBallotError::AlreadyShared);
+        }
+        let aggregate = Self::get_aggregate(env.clone(), survey_id);
+        if shares.len() != aggregate.len() {
+            panic!("Expected one share per candidate");
+        }
+
+        let verification_key = Self::verification_key(&env, survey_id, index);
+        let mut points = Vec::new(&env);
+        for (i, (ciphertext, share)) in aggregate.iter().zip(shares.iter()).enumerate() {
+            let mut context = Bytes::from_array(&env, &index.to_be_bytes());
+            context.extend_from_array(&(i as u32).to_be_bytes());
+            let valid = Self::verify_equality(
+                &env,
+                SHARE_TAG,
+                survey_id,
+                &context,
+                &G1Affine::from_bytes(ciphertext.a),
+                &verification_key,
+                &G1Affine::from_bytes(share.share.clone()),
+                &share.proof,
+            );
+            if !valid {
+                panic_with_error!(&env, EncryptedBallotError::InvalidProof);
+            }
+            points.push_back(share.share);
+        }
+        Self::set_persistent(&env, &shares_key, &points);
+
+        let submitted_key = DataKey::ShareTrustees(survey_id);
+        let mut submitted: Vec<u32> = env.storage().persistent().get(&submitted_key).unwrap_or(Vec::new(&env));
+        submitted.push_back(index);
+        Self::set_persistent(&env, &submitted_key, &submitted);
+        DecryptionShared { survey_id, trustee }.publish(&env);
+    }
+
+    /// Publica el conteo descifrado y lo entrega a la encuesta (cualquiera)
+    /// Se combinan los descifrados de los primeros `threshold` custodios y se
+    /// comprueba que el agregado de cada candidato descifra exactamente su conteo
+    fn publish_tally(env: Env, survey_id: u64, counts: Vec<u64>) {
+        let mut election = Self::load_election(&env, survey_id);
+        if election.tallied {
+            panic_with_error!(&env, EncryptedBallotError::AlreadyTallied);
+        }
+        let submitted: Vec<u32> = env.storage().persistent()
+            .get(&DataKey::ShareTrustees(survey_id))
+            .unwrap_or(Vec::new(&env));
+        if submitted.len() < election.threshold {
+            panic_with_error!(&env, EncryptedBallotError::NotEnoughShares);
+        }
+        let indices = submitted.slice(0..election.threshold);
+
+        let aggregate = Self::get_aggregate(env.clone(), survey_id);
+        if counts.len() != aggregate.len() {
+            panic!("Counts do not match candidates");
+        }
+        let total: u64 = counts.iter().sum();
+        if total != election.ballots {
+            panic_with_error!(&env, EncryptedBallotError::InvalidTally);
+        }
+
+        let bls = env.crypto().bls12_381();
+        let zero = Self::scalar(&env, 0);
+        let lambdas = Self::lagrange_at_zero(&env, &indices);
+        let mut trustee_shares = Vec::new(&env);
+        for index in indices.iter() {
+            let trustee = election.trustees.get(index - 1).unwrap();
+            let shares: Vec<BytesN<96>> = env.storage().persistent()
+                .get(&DataKey::Shares(survey_id, trustee))
+                .unwrap();
+            trustee_shares.push_back(shares);
+        }
+
+        // B − Σ λ_i·D_i − conteo·G debe ser el punto en el infinito
+        let identity = Self::identity(&env).to_bytes();
+        for (i, (ciphertext, count)) in aggregate.iter().zip(counts.iter()).enumerate() {
+            let mut points = Vec::new(&env);
+            let mut scalars = Vec::new(&env);
+            points.push_back(G1Affine::from_bytes(ciphertext.b));
+            scalars.push_back(Self::scalar(&env, 1));
+            for (shares, lambda) in trustee_shares.iter().zip(lambdas.iter()) {
+                points.push_back(G1Affine::from_bytes(shares.get(i as u32).unwrap()));
+                scalars.push_back(bls.fr_sub(&zero, &lambda));
+            }
+            points.push_back(Self::generator(&env));
+            scalars.push_back(bls.fr_sub(&zero, &Self::scalar(&env, count)));
+            if bls.g1_msm(points, scalars).to_bytes() != identity {
+                panic_with_error!(&env, EncryptedBallotError::InvalidTally);
+            }
+        }
+
+        election.tallied = true;
+        Self::save_election(&env, &election);
+        Self::set_persistent(&env, &DataKey::Tally(survey_id), &counts);
+        TallyPublished { survey_id, counts: counts.clone() }.publish(&env);
+
+        let voters: Vec<Address> = env.storage().persistent()
+            .get(&DataKey::Voters(survey_id))
+            .unwrap_or(Vec::new(&env));
+        Self::survey_client(&env).record_tally(&survey_id, &counts, &voters);
+    }
+
+    /// Obtiene una elección cifrada
+    fn get_election(env: Env, survey_id: u64) -> Option<Election> {
+        env.storage().persistent().get(&DataKey::Election(survey_id))
+    }
+
+    /// Obtiene los compromisos sumados de los custodios; el primero es la clave de la elección
+    fn get_commitments(env: Env, survey_id: u64) -> Vec<BytesN<96>> {
+        env.storage().persistent().get(&DataKey::Commitments(survey_id)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Obtiene los cifrados sumados de cada candidato
+    fn get_aggregate(env: Env, survey_id: u64) -> Vec<Ciphertext> {
+        env.storage().persistent().get(&DataKey::Aggregate(survey_id)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Obtiene la papeleta cifrada de un votante (si ya votó)
+    fn get_ballot(env: Env, survey_id: u64, voter: Address) -> Option<EncryptedBallot> {
+        env.storage().persistent().get(&DataKey::Ballot(survey_id, voter))
+    }
+
+    /// Obtiene los descifrados parciales de un custodio (si ya los publicó)
+    fn get_shares(env: Env, survey_id: u64, trustee: Address) -> Option<Vec<BytesN<96>>> {
+        env.storage().persistent().get(&DataKey::Shares(survey_id, trustee))
+    }
+
+    /// Obtiene el conteo descifrado (si ya se publicó)
+    fn get_tally(env: Env, survey_id: u64) -> Option<Vec<u64>> {
+        env.storage().persistent().get(&DataKey::Tally(survey_id))
+    }
+}
+
+impl EncryptedBallotBox {
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        admin
+    }
+
+    fn survey_client(env: &Env) -> SurveyClient<'_> {
+        let survey: Address = env.storage().instance()
+            .get(&DataKey::Survey)
+            .expect("Contract not initialized");
+        SurveyClient::new(env, &survey)
+    }
+
+    fn load_survey(env: &Env, survey_id: u64) -> Survey {
+        Self::survey_client(env).get_survey(&survey_id).expect("Survey not found")
+    }
+
+    fn load_election(env: &Env, survey_id: u64) -> Election {
+        Self::get_election(env.clone(), survey_id).expect("Election not found")
+    }
+
+    fn save_election(env: &Env, election: &Election) {
+        Self::set_persistent(env, &DataKey::Election(election.survey_id), election);
+    }
+
+    fn set_persistent<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(key, 100, 8640000);
+    }
+
+    // Índice del custodio en la elección (posición + 1)
+    fn trustee_index(env: &Env, election: &Election, trustee: &Address) -> u32 {
+        match election.trustees.first_index_of(trustee) {
+            Some(position) => position + 1,
+            None => panic_with_error!(env, EncryptedBallotError::NotTrustee),
+        }
+    }
+
+    fn generator(env: &Env) -> G1Affine {
+        G1Affine::from_bytes(bytesn!(env, 0x17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1))
+    }
+
+    // Punto en el infinito: solo la bandera de infinito activa
+    fn identity(env: &Env) -> G1Affine {
+        let mut bytes = [0u8; 96];
+        bytes[0] = 0x40;
+        G1Affine::from_array(env, &bytes)
+    }
+
+    fn scalar(env: &Env, value: u64) -> Fr {
+        Fr::from_u256(U256::from_u128(env, value as u128))
+    }
+
+    // sha256(etiqueta || survey_id || contexto || puntos), reducido módulo r por el host
+    fn challenge(env: &Env, tag: &[u8], survey_id: u64, context: &Bytes, points: &[&G1Affine]) -> Fr {
+        let mut data = Bytes::from_slice(env, tag);
+        data.extend_from_array(&survey_id.to_be_bytes());
+        data.append(context);
+        for point in points {
+            data.extend_from_array(&point.to_array());
+        }
+        Fr::from_bytes(env.crypto().sha256(&data).to_bytes())
+    }
+
+    // s·base − c·point: el compromiso que una prueba válida debe reproducir
+    fn commit(env: &Env, base: &G1Affine, s: &Fr, point: &G1Affine, c: &Fr) -> G1Affine {
+        let bls = env.crypto().bls12_381();
+        let mut points = Vec::new(env);
+        points.push_back(base.clone());
+        points.push_back(point.clone());
+        let mut scalars = Vec::new(env);
+        scalars.push_back(s.clone());
+        scalars.push_back(bls.fr_sub(&Self::scalar(env, 0), c));
+        bls.g1_msm(points, scalars)
+    }
+
+    // Chaum-Pedersen: log_G(x_point) = log_base(z_point)
+    #[allow(clippy::too_many_arguments)]
+    fn verify_equality(
+        env: &Env,
+        tag: &[u8],
+        survey_id: u64,
+        context: &Bytes,
+        base: &G1Affine,
+        x_point: &G1Affine,
+        z_point: &G1Affine,
+        proof: &EqualityProof,
+    ) -> bool {
+        let commitment_g = G1Affine::from_bytes(proof.commitment_g.clone());
+        let commitment_h = G1Affine::from_bytes(proof.commitment_h.clone());
+        let c = Self::challenge(env, tag, survey_id, context, &[base, x_point, z_point, &commitment_g, &commitment_h]);
+        let s = Fr::from_bytes(proof.response.clone());
+        Self::commit(env, &Self::generator(env), &s, x_point, &c).to_bytes() == commitment_g.to_bytes()
+            && Self::commit(env, base, &s, z_point, &c).to_bytes() == commitment_h.to_bytes()
+    }
+
+    // Cada cifrado contiene 0 o 1 y la suma de todos contiene exactamente 1
+    fn verify_ballot(
+        env: &Env,
+        survey_id: u64,
+        voter: &Address,
+        public_key: &G1Affine,
+        ballot: &EncryptedBallot,
+    ) -> bool {
+        let bls = env.crypto().bls12_381();
+        let g = Self::generator(env);
+        let minus_g = -&g;
+        let zero = Self::scalar(env, 0);
+        let voter_xdr = voter.clone().to_xdr(env);
+
+        let mut sum_a = Self::identity(env);
+        let mut sum_b = Self::identity(env);
+        for (i, choice) in ballot.choices.iter().enumerate() {
+            let a = G1Affine::from_bytes(choice.ciphertext.a);
+            let b = G1Affine::from_bytes(choice.ciphertext.b);
+            let b_minus_g = bls.g1_add(&b, &minus_g);
+            let (c0, c1) = (Fr::from_bytes(choice.proof.challenge_zero), Fr::from_bytes(choice.proof.challenge_one));
+            let (s0, s1) = (Fr::from_bytes(choice.proof.response_zero), Fr::from_bytes(choice.proof.response_one));
+
+            // Rama 0: B = r·H; rama 1: B − G = r·H
+            let zero_g = Self::commit(env, &g, &s0, &a, &c0);
+            let zero_h = Self::commit(env, public_key, &s0, &b, &c0);
+            let one_g = Self::commit(env, &g, &s1, &a, &c1);
+            let one_h = Self::commit(env, public_key, &s1, &b_minus_g, &c1);
+
+            let mut context = voter_xdr.clone();
+            context.extend_from_array(&(i as u32).to_be_bytes());
+            let c = Self::challenge(
+                env,
+                BIT_TAG,
+                survey_id,
+                &context,
+                &[public_key, &a, &b, &zero_g, &zero_h, &one_g, &one_h],
+            );
+            if bls.fr_sub(&bls.fr_add(&c0, &c1), &c) != zero {
+                return false;
+            }
+
+            sum_a = bls.g1_add(&sum_a, &a);
+            sum_b = bls.g1_add(&sum_b, &b);
+        }
+
+        let sum_b_minus_g = bls.g1_add(&sum_b, &minus_g);
+        Self::verify_equality(env, SUM_TAG, survey_id, &voter_xdr, public_key, &sum_a, &sum_b_minus_g, &ballot.sum_proof)
+    }
+
+    // Σ index^k · C_k sobre los compromisos sumados
+    fn verification_key(env: &Env, survey_id: u64, index: u32) -> G1Affine {
+        let bls = env.crypto().bls12_381();
+        let x = Self::scalar(env, index as u64);
+        let mut power = Self::scalar(env, 1);
+        let mut points = Vec::new(env);
+        let mut scalars = Vec::new(env);
+        for commitment in Self::get_commitments(env.clone(), survey_id).iter() {
+            points.push_back(G1Affine::from_bytes(commitment));
+            scalars.push_back(power.clone());
+            power = bls.fr_mul(&power, &x);
+        }
+        bls.g1_msm(points, scalars)
+    }
+
+    // λ_i = Π k / (k − i) para k ≠ i
+    fn lagrange_at_zero(env: &Env, indices: &Vec<u32>) -> Vec<Fr> {
+        let bls = env.crypto().bls12_381();
+        let mut lambdas = Vec::new(env);
+        for i in indices.iter() {
+            let mut numerator = Self::scalar(env, 1);
+            let mut denominator = Self::scalar(env, 1);
+            for k in indices.iter().filter(|&k| k != i) {
+                numerator = bls.fr_mul(&numerator, &Self::scalar(env, k as u64));
+                denominator = bls.fr_mul(
+                    &denominator,
+                    &bls.fr_sub(&Self::scalar(env, k as u64), &Self::scalar(env, i as u64)),
+                );
+            }
+            lambdas.push_back(bls.fr_mul(&numerator, &bls.fr_inv(&denominator)));
+        }
+        lambdas
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use dappcenvote_trustee as trustee;
+    use dappcenvote_types::encrypted_ballot::{BitProof, EncryptedChoice};
+    use rand::{rngs::StdRng, SeedableRng};
+    use soroban_sdk::{testutils::{Address as _, Ledger}, String};
+    use survey::{SurveyContract, SurveyContractClient};
+
+    struct Setup<'a> {
+        env: Env,
+        survey: SurveyContractClient<'a>,
+        client: EncryptedBallotBoxClient<'a>,
+        survey_id: u64,
+        candidates: Vec<Address>,
+        trustees: Vec<Address>,
+        dealings: std::vec::Vec<trustee::Dealing>,
+        rng: StdRng,
+    }
+
+    fn point(env: &Env, point: &trustee::Point) -> BytesN<96> {
+        BytesN::from_array(env, &point.0)
+    }
+
+    fn scalar(env: &Env, scalar: &trustee::Scalar) -> BytesN<32> {
+        BytesN::from_array(env, &scalar.0)
+    }
+
+    fn equality_proof(env: &Env, proof: &trustee::EqualityProof) -> EqualityProof {
+        EqualityProof {
+            commitment_g: point(env, &proof.commitment_g),
+            commitment_h: point(env, &proof.commitment_h),
+            response: scalar(env, &proof.response),
+        }
+    }
+
+    fn to_contract_ballot(env: &Env, ballot: &trustee::EncryptedBallot) -> EncryptedBallot {
+        let mut choices = Vec::new(env);
+        for choice in &ballot.choices {
+            choices.push_back(EncryptedChoice {
+                ciphertext: Ciphertext { a: point(env, &choice.ciphertext.a), b: point(env, &choice.ciphertext.b) },
+                proof: BitProof {
+                    challenge_zero: scalar(env, &choice.proof.challenge_zero),
+                    challenge_one: scalar(env, &choice.proof.challenge_one),
+                    response_zero: scalar(env, &choice.proof.response_zero),
+                    response_one: scalar(env, &choice.proof.response_one),
+                },
+            });
+        }
+        EncryptedBallot { choices, sum_proof: equality_proof(env, &ballot.sum_proof) }
+    }
+
+    fn to_trustee_aggregate(aggregate: &Vec<Ciphertext>) -> std::vec::Vec<trustee::Ciphertext> {
+        aggregate
+            .iter()
+            .map(|c| trustee::Ciphertext { a: trustee::Point(c.a.to_array()), b: trustee::Point(c.b.to_array()) })
+            .collect()
+    }
+
+    // Encuesta de tres candidatos entre 1000 y 3000 con tres custodios, de los que bastan dos
+    fn setup<'a>() -> Setup<'a> {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(500);
+
+        let admin = Address::generate(&env);
+        let survey = SurveyContractClient::new(&env, &env.register(SurveyContract, (admin.clone(),)));
+        let client = EncryptedBallotBoxClient::new(
+            &env,
+            &env.register(EncryptedBallotBox, (admin.clone(), survey.address.clone())),
+        );
+        survey.add_ballot_box_contract(&client.address);
+
+        let mut candidates = Vec::new(&env);
+        for _ in 0..3 {
+            candidates.push_back(Address::generate(&env));
+        }
+        let survey_id = survey.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Consejo estudiantil"),
+            &String::from_str(&env, "Voto secreto"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+
+        let mut trustees = Vec::new(&env);
+        for _ in 0..3 {
+            trustees.push_back(Address::generate(&env));
+        }
+        client.create_election(&survey_id, &trustees, &2);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let dealings = (1..=3).map(|i| trustee::deal(survey_id, i, 2, 3, &mut rng).unwrap()).collect();
+        Setup { env, survey, client, survey_id, candidates, trustees, dealings, rng }
+    }
+
+    fn commit_all(s: &Setup) {
+        for (trustee, dealing) in s.trustees.iter().zip(&s.dealings) {
+            let mut commitments = Vec::new(&s.env);
+            for commitment in &dealing.commitments {
+                commitments.push_back(point(&s.env, commitment));
+            }
+            let proof = KeyProof {
+                commitment: point(&s.env, &dealing.proof.commitment),
+                response: scalar(&s.env, &dealing.proof.response),
+            };
+            s.client.commit_key(&s.survey_id, &trustee, &commitments, &proof);
+        }
+    }
+
+    fn voter_xdr(env: &Env, voter: &Address) -> std::vec::Vec<u8> {
+        let xdr = voter.clone().to_xdr(env);
+        let mut bytes = std::vec![0u8; xdr.len() as usize];
+        xdr.copy_into_slice(&mut bytes);
+        bytes
+    }
+
+    fn ballot(s: &mut Setup, voter: &Address, choice: usize) -> EncryptedBallot {
+        let public_key = trustee::Point(s.client.get_election(&s.survey_id).unwrap().public_key.unwrap().to_array());
+        let encrypted = trustee::encrypt_ballot(
+            &public_key,
+            s.survey_id,
+            &voter_xdr(&s.env, voter),
+            choice,
+            s.candidates.len() as usize,
+            &mut s.rng,
+        )
+        .unwrap();
+        to_contract_ballot(&s.env, &encrypted)
+    }
+
+    // Llave secreta del custodio `index`: suma de lo que le enviaron todos
+    fn secret(s: &Setup, index: u32) -> trustee::Scalar {
+        let shares: std::vec::Vec<_> = s.dealings.iter().map(|d| d.shares[index as usize - 1]).collect();
+        trustee::combine_shares(&shares)
+    }
+
+    fn submit_shares(s: &mut Setup, index: u32, secret: &trustee::Scalar) {
+        let aggregate = to_trustee_aggregate(&s.client.get_aggregate(&s.survey_id));
+        let shares = trustee::decrypt_shares(secret, index, s.survey_id, &aggregate, &mut s.rng).unwrap();
+        let mut contract_shares = Vec::new(&s.env);
+        for share in &shares {
+            contract_shares.push_back(DecryptionShare {
+                share: point(&s.env, &share.share),
+                proof: equality_proof(&s.env, &share.proof),
+            });
+        }
+        let trustee = s.trustees.get(index - 1).unwrap();
+        s.client.submit_shares(&s.survey_id, &trustee, &contract_shares);
+    }
+
+    #[test]
+    fn test_encrypted_election() {
+        let mut s = setup();
+        commit_all(&s);
+        let election = s.client.get_election(&s.survey_id).unwrap();
+        assert_eq!(election.committed, 3);
+        assert_eq!(election.public_key, Some(s.client.get_commitments(&s.survey_id).get(0).unwrap()));
+
+        s.env.ledger().set_timestamp(1500);
+        let voters: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&s.env)).collect();
+        for (voter, choice) in voters.iter().zip([1, 2, 1]) {
+            let ballot = ballot(&mut s, voter, choice);
+            let receipt = s.client.cast_ballot(&s.survey_id, voter, &ballot, &None);
+            assert!(s.survey.verify_receipt(&s.survey_id, &receipt));
+        }
+        assert_eq!(s.client.get_election(&s.survey_id).unwrap().ballots, 3);
+        assert_eq!(s.survey.get_receipt_chain(&s.survey_id).length, 3);
+        // La encuesta ya no acepta votos en claro
+        assert!(s.survey.try_vote(&s.survey_id, &voters[0], &s.candidates.get(0).unwrap(), &None).is_err());
+
+        // Solo el agregado se descifra, con dos de los tres custodios
+        s.env.ledger().set_timestamp(3001);
+        let (first, third) = (secret(&s, 1), secret(&s, 3));
+        submit_shares(&mut s, 3, &third);
+        submit_shares(&mut s, 1, &first);
+
+        let aggregate = to_trustee_aggregate(&s.client.get_aggregate(&s.survey_id));
+        let shares: std::vec::Vec<(u32, std::vec::Vec<trustee::Point>)> = [3u32, 1]
+            .iter()
+            .map(|&i| {
+                let trustee = s.trustees.get(i - 1).unwrap();
+                let points = s.client.get_shares(&s.survey_id, &trustee).unwrap();
+                (i, points.iter().map(|p| trustee::Point(p.to_array())).collect())
+            })
+            .collect();
+        let counts = trustee::tally(&aggregate, &shares, 3).unwrap();
+        assert_eq!(counts, std::vec![0, 2, 1]);
+
+        let mut tally = Vec::new(&s.env);
+        for count in counts {
+            tally.push_back(count);
+        }
+        s.client.publish_tally(&s.survey_id, &tally);
+        assert_eq!(s.client.get_tally(&s.survey_id), Some(tally));
+
+        // La encuesta recibe el conteo y el padrón, y se finaliza como cualquier otra
+        let results = s.survey.get_results(&s.survey_id);
+        assert_eq!(results.get(1).unwrap().votes, 2);
+        assert_eq!(results.get(2).unwrap().votes, 1);
+        assert_eq!(s.survey.get_total_votes(&s.survey_id), 3);
+        s.survey.finalize_survey(&s.survey_id);
+        assert_eq!(s.survey.get_certificate(&s.survey_id).unwrap().total_votes, 3);
+    }
+
+    #[test]
+    fn test_rejects_invalid_ballots() {
+        let mut s = setup();
+        commit_all(&s);
+        s.env.ledger().set_timestamp(1500);
+        let voter = Address::generate(&s.env);
+        let other = Address::generate(&s.env);
+
+        // Una papeleta ligada a otro votante no vale
+        let copied = ballot(&mut s, &other, 0);
+        assert_eq!(
+            s.client.try_cast_ballot(&s.survey_id, &voter, &copied, &None),
+            Err(Ok(EncryptedBallotError::InvalidProof.into()))
+        );
+
+        // Cambiar un cifrado rompe su prueba
+        let mut tampered = ballot(&mut s, &voter, 0);
+        let mut choice = tampered.choices.get(0).unwrap();
+        let second = tampered.choices.get(1).unwrap();
+        choice.ciphertext = second.ciphertext;
+        tampered.choices.set(0, choice);
+        assert_eq!(
+            s.client.try_cast_ballot(&s.survey_id, &voter, &tampered, &None),
+            Err(Ok(EncryptedBallotError::InvalidProof.into()))
+        );
+
+        let valid = ballot(&mut s, &voter, 2);
+        s.client.cast_ballot(&s.survey_id, &voter, &valid, &None);
+        assert_eq!(s.client.get_ballot(&s.survey_id, &voter), Some(valid.clone()));
+        assert_eq!(
+            s.client.try_cast_ballot(&s.survey_id, &voter, &valid, &None),
+            Err(Ok(EncryptedBallotError::AlreadyVoted.into()))
+        );
+    }
+
+    #[test]
+    fn test_key_ceremony() {
+        let mut s = setup();
+        let trustee = s.trustees.get(0).unwrap();
+        let dealing = &s.dealings[0];
+        let mut commitments = Vec::new(&s.env);
+        for commitment in &dealing.commitments {
+            commitments.push_back(point(&s.env, commitment));
+        }
+
+        // La prueba de otro custodio (índice 2) no vale para el índice 1
+        let foreign = trustee::deal(s.survey_id, 2, 2, 3, &mut s.rng).unwrap();
+        let mut foreign_commitments = Vec::new(&s.env);
+        for commitment in &foreign.commitments {
+            foreign_commitments.push_back(point(&s.env, commitment));
+        }
+        let foreign_proof = KeyProof {
+            commitment: point(&s.env, &foreign.proof.commitment),
+            response: scalar(&s.env, &foreign.proof.response),
+        };
+        assert_eq!(
+            s.client.try_commit_key(&s.survey_id, &trustee, &foreign_commitments, &foreign_proof),
+            Err(Ok(EncryptedBallotError::InvalidProof.into()))
+        );
+        assert_eq!(
+            s.client.try_commit_key(&s.survey_id, &Address::generate(&s.env), &foreign_commitments, &foreign_proof),
+            Err(Ok(EncryptedBallotError::NotTrustee.into()))
+        );
+
+        let proof = KeyProof {
+            commitment: point(&s.env, &dealing.proof.commitment),
+            response: scalar(&s.env, &dealing.proof.response),
+        };
+        s.client.commit_key(&s.survey_id, &trustee, &commitments, &proof);
+        assert_eq!(
+            s.client.try_commit_key(&s.survey_id, &trustee, &commitments, &proof),
+            Err(Ok(EncryptedBallotError::AlreadyCommitted.into()))
+        );
+        assert_eq!(s.client.get_election(&s.survey_id).unwrap().public_key, None);
+
+        // Sin clave no se puede votar
+        s.env.ledger().set_timestamp(1500);
+        let voter = Address::generate(&s.env);
+        let empty = EncryptedBallot { choices: Vec::new(&s.env), sum_proof: EqualityProof {
+            commitment_g: EncryptedBallotBox::identity(&s.env).to_bytes(),
+            commitment_h: EncryptedBallotBox::identity(&s.env).to_bytes(),
+            response: BytesN::from_array(&s.env, &[0; 32]),
+        } };
+        assert_eq!(
+            s.client.try_cast_ballot(&s.survey_id, &voter, &empty, &None),
+            Err(Ok(EncryptedBallotError::KeyNotReady.into()))
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_shares_and_tallies() {
+        let mut s = setup();
+        commit_all(&s);
+        s.env.ledger().set_timestamp(1500);
+        for choice in [0, 0] {
+            let voter = Address::generate(&s.env);
+            let ballot = ballot(&mut s, &voter, choice);
+            s.client.cast_ballot(&s.survey_id, &voter, &ballot, &None);
+        }
+        s.env.ledger().set_timestamp(3001);
+
+        // Descifrar con la llave del custodio 2 haciéndose pasar por el 1
+        let second = secret(&s, 2);
+        let aggregate = to_trustee_aggregate(&s.client.get_aggregate(&s.survey_id));
+        let forged = trustee::decrypt_shares(&second, 1, s.survey_id, &aggregate, &mut s.rng).unwrap();
+        let mut contract_shares = Vec::new(&s.env);
+        for share in &forged {
+            contract_shares.push_back(DecryptionShare { share: point(&s.env, &share.share), proof: equality_proof(&s.env, &share.proof) });
+        }
+        assert_eq!(
+            s.client.try_submit_shares(&s.survey_id, &s.trustees.get(0).unwrap(), &contract_shares),
+            Err(Ok(EncryptedBallotError::InvalidProof.into()))
+        );
+
+        submit_shares(&mut s, 2, &second);
+        let mut counts = Vec::new(&s.env);
+        for count in [2u64, 0, 0] {
+            counts.push_back(count);
+        }
+        assert_eq!(
+            s.client.try_publish_tally(&s.survey_id, &counts),
+            Err(Ok(EncryptedBallotError::NotEnoughShares.into()))
+        );
+
+        let third = secret(&s, 3);
+        submit_shares(&mut s, 3, &third);
+        let mut wrong = Vec::new(&s.env);
+        for count in [0u64, 2, 0] {
+            wrong.push_back(count);
+        }
+        assert_eq!(
+            s.client.try_publish_tally(&s.survey_id, &wrong),
+            Err(Ok(EncryptedBallotError::InvalidTally.into()))
+        );
+        s.client.publish_tally(&s.survey_id, &counts);
+        assert_eq!(
+            s.client.try_publish_tally(&s.survey_id, &counts),
+            Err(Ok(EncryptedBallotError::AlreadyTallied.into()))
+        );
+    }
+}
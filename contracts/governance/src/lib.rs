@@ -0,0 +1,596 @@
+#![no_std]
+use dappcenvote_types::governance::{
+    Ballot, BallotCast, DataKey, GovernanceConfig, GovernanceError, GovernanceInterface, Proposal,
+    ProposalAction, ProposalCreated, ProposalExecuted, ProposalStatus,
+};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, IntoVal, String, Symbol, Val, Vec};
+
+const CONTRACT_VERSION: u32 = 1;
+const VOTING_PERIOD_SECS: u64 = 7 * 24 * 60 * 60; // Una semana
+const TIMELOCK_SECS: u64 = 2 * 24 * 60 * 60;      // Dos días
+const MAX_BPS: u32 = 10_000;
+
+#[contract]
+pub struct Governance;
+
+#[contractimpl]
+impl Governance {
+    /// Inicializa el contrato al desplegarlo, con la dirección del administrador y la
+    /// configuración por defecto (mayoría simple, quórum de un voto)
+    pub fn __constructor(env: Env, admin: Address) {
+        let config = GovernanceConfig {
+            voting_period: VOTING_PERIOD_SECS,
+            timelock: TIMELOCK_SECS,
+            quorum: 1,
+            threshold_bps: MAX_BPS / 2,
+        };
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().extend_ttl(100, 8640000);
+
+        let count_key = DataKey::ProposalCount;
+        env.storage().persistent().set(&count_key, &0u64);
+        env.storage().persistent().extend_ttl(&count_key, 100, 8640000);
+    }
+}
+
+#[contractimpl]
+impl GovernanceInterface for Governance {
+    /// Migra el almacenamiento a la versión de este código (solo administrador)
+    /// Sin efecto si el almacenamiento ya está en esa versión
+    /// La versión 1 es el formato original, por lo que aún no hay transformaciones
+    fn migrate(env: Env) {
+        Self::require_admin(&env);
+
+        let version = Self::version(env.clone());
+        if version >= CONTRACT_VERSION {
+            return; // Ya está al día; repetir la llamada no es un error
+        }
+
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+    }
+
+    /// Obtiene la versión del formato de almacenamiento
+    fn version(env: Env) -> u32 {
+        let key = DataKey::ContractVersion;
+        env.storage().instance().get(&key).unwrap_or(1)
+    }
+
+    /// Cambia los parámetros de votación (solo administrador, antes de la primera propuesta)
+    /// Después solo puede cambiarlos una propuesta `Call` a este contrato
+    /// Las propuestas ya creadas conservan los parámetros con que se crearon
+    fn set_config(env: Env, config: GovernanceConfig) {
+        Self::require_bootstrap(&env);
+
+        Self::store_config(&env, &config);
+    }
+
+    /// Obtiene los parámetros de votación vigentes
+    fn get_config(env: Env) -> GovernanceConfig {
+        env.storage().instance().get(&DataKey::Config).expect("Contract not initialized")
+    }
+
+    /// Agrega un miembro con derecho a proponer y votar (solo administrador, antes
+    /// de la primera propuesta; después, solo con una propuesta `Call` a este contrato)
+    /// No puede votar las propuestas creadas antes de su alta
+    fn add_member(env: Env, member: Address) {
+        Self::require_bootstrap(&env);
+
+        Self::store_member(&env, member);
+    }
+
+    /// Quita un miembro, con las mismas reglas que `add_member`
+    /// Sus votos ya emitidos se conservan
+    fn remove_member(env: Env, member: Address) {
+        Self::require_bootstrap(&env);
+
+        Self::drop_member(&env, member);
+    }
+
+    /// Verifica si una dirección es miembro
+    fn is_member(env: Env, member: Address) -> bool {
+        let key = DataKey::Member(member);
+        env.storage().persistent().get(&key).unwrap_or(false)
+    }
+
+    /// Crea una propuesta con una acción opcional (solo miembros)
+    /// La votación empieza de inmediato y dura `voting_period`
+    fn propose(
+        env: Env,
+        proposer: Address,
+        title: String,
+        description: String,
+        action: ProposalAction,
+    ) -> u64 {
+        proposer.require_auth();
+        Self::require_member(&env, &proposer);
+
+        if title.is_empty() {
+            panic!("Title cannot be empty");
+        }
+
+        let count_key = DataKey::ProposalCount;
+        let proposal_id: u64 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+
+        let config = Self::get_config(env.clone());
+        let start_time = env.ledger().timestamp();
+        let end_time = start_time + config.voting_period;
+        let proposal = Proposal {
+            proposal_id,
+            proposer: proposer.clone(),
+            title: title.clone(),
+            description,
+            action,
+            start_time,
+            end_time,
+            eta: end_time + config.timelock,
+            quorum: config.quorum,
+            threshold_bps: config.threshold_bps,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            executed: false,
+        };
+        Self::save_proposal(&env, &proposal);
+
+        env.storage().persistent().set(&count_key, &proposal_id);
+        env.storage().persistent().extend_ttl(&count_key, 100, 8640000);
+
+        ProposalCreated { proposal_id, proposer, title, end_time, eta: proposal.eta }.publish(&env);
+
+        proposal_id
+    }
+
+    /// Registra el voto de un miembro mientras la propuesta está en votación
+    /// Solo votan quienes ya eran miembros cuando se creó la propuesta
+    fn cast_ballot(env: Env, proposal_id: u64, voter: Address, ballot: Ballot) {
+        voter.require_auth();
+        Self::require_member(&env, &voter);
+
+        let mut proposal = Self::load_proposal(&env, proposal_id);
+        let member_since: u64 = env.storage().persistent()
+            .get(&DataKey::MemberSince(voter.clone()))
+            .unwrap_or(0);
+        if member_since > proposal.start_time {
+            panic_with_error!(&env, GovernanceError::NotMember);
+        }
+        if env.ledger().timestamp() > proposal.end_time {
+            panic_with_error!(&env, GovernanceError::VotingClosed);
+        }
+
+        let ballot_key = DataKey::Ballot(proposal_id, voter.clone());
+        if env.storage().persistent().has(&ballot_key) {
+            panic_with_error!(&env, GovernanceError::AlreadyVoted);
+        }
+        env.storage().persistent().set(&ballot_key, &ballot);
+        env.storage().persistent().extend_ttl(&ballot_key, 100, 8640000);
+
+        match ballot {
+            Ballot::For => proposal.votes_for += 1,
+            Ballot::Against => proposal.votes_against += 1,
+            Ballot::Abstain => proposal.votes_abstain += 1,
+        }
+        Self::save_proposal(&env, &proposal);
+
+        BallotCast { proposal_id, voter, ballot }.publish(&env);
+    }
+
+    /// Ejecuta la acción de una propuesta aprobada una vez vencido el timelock
+    /// Cualquiera puede llamarla; el contrato destino ve a Governance como invocador
+    /// El código de Governance solo se reemplaza así, con una propuesta `Upgrade`
+    fn execute(env: Env, proposal_id: u64) -> Val {
+        let mut proposal = Self::load_proposal(&env, proposal_id);
+        match Self::status_of(&env, &proposal) {
+            ProposalStatus::Passed => {}
+            ProposalStatus::Executed => panic_with_error!(&env, GovernanceError::AlreadyExecuted),
+            _ => panic_with_error!(&env, GovernanceError::NotPassed),
+        }
+        if env.ledger().timestamp() < proposal.eta {
+            panic_with_error!(&env, GovernanceError::TimelockActive);
+        }
+        if let ProposalAction::None = proposal.action {
+            panic_with_error!(&env, GovernanceError::NoAction);
+        }
+
+        // Marcar como ejecutada antes de la llamada externa
+        proposal.executed = true;
+        Self::save_proposal(&env, &proposal);
+
+        let (contract, function, result) = match proposal.action {
+            // Un contrato no puede llamarse a sí mismo: sus propias funciones se aplican aquí
+            ProposalAction::Call(call) if call.contract == env.current_contract_address() => {
+                Self::call_self(&env, &call.function, &call.args);
+                (call.contract, call.function, Val::VOID.into())
+            }
+            ProposalAction::Call(call) => {
+                let result: Val = env.invoke_contract(&call.contract, &call.function, call.args);
+                (call.contract, call.function, result)
+            }
+            // Conserva el almacenamiento; `migrate` se llama después con el código nuevo
+            ProposalAction::Upgrade(new_wasm_hash) => {
+                env.deployer().update_current_contract_wasm(new_wasm_hash);
+                (env.current_contract_address(), Symbol::new(&env, "upgrade"), Val::VOID.into())
+            }
+            ProposalAction::None => unreachable!(),
+        };
+        ProposalExecuted { proposal_id, contract, function }.publish(&env);
+        result
+    }
+
+    /// Obtiene una propuesta por su ID
+    fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
+        let key = DataKey::Proposal(proposal_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Obtiene el estado de una propuesta en el momento actual
+    fn get_status(env: Env, proposal_id: u64) -> ProposalStatus {
+        let proposal = Self::load_proposal(&env, proposal_id);
+        Self::status_of(&env, &proposal)
+    }
+
+    /// Obtiene el voto de un miembro en una propuesta (si ya votó)
+    fn get_ballot(env: Env, proposal_id: u64, voter: Address) -> Option<Ballot> {
+        let key = DataKey::Ballot(proposal_id, voter);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Obtiene el número total de propuestas
+    fn get_proposal_count(env: Env) -> u64 {
+        let key = DataKey::ProposalCount;
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+}
+
+impl Governance {
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        admin
+    }
+
+    // Sin propuestas aún, el administrador configura el contrato; después solo
+    // una propuesta aprobada, que `execute` aplica con `call_self`
+    fn require_bootstrap(env: &Env) {
+        if Self::get_proposal_count(env.clone()) > 0 {
+            panic_with_error!(env, GovernanceError::ProposalRequired);
+        }
+        Self::require_admin(env);
+    }
+
+    fn call_self(env: &Env, function: &Symbol, args: &Vec<Val>) {
+        let arg = args.get(0).expect("Missing argument");
+        if *function == Symbol::new(env, "add_member") {
+            Self::store_member(env, arg.into_val(env));
+        } else if *function == Symbol::new(env, "remove_member") {
+            Self::drop_member(env, arg.into_val(env));
+        } else if *function == Symbol::new(env, "set_config") {
+            Self::store_config(env, &arg.into_val(env));
+        } else {
+            panic!("Governance can only call add_member, remove_member or set_config on itself");
+        }
+    }
+
+    fn store_config(env: &Env, config: &GovernanceConfig) {
+        if config.voting_period == 0 {
+            panic!("Voting period must be greater than zero");
+        }
+        if config.threshold_bps == 0 || config.threshold_bps > MAX_BPS {
+            panic!("Threshold must be between 1 and 10000 basis points");
+        }
+        env.storage().instance().set(&DataKey::Config, config);
+    }
+
+    fn store_member(env: &Env, member: Address) {
+        let key = DataKey::Member(member.clone());
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 100, 8640000);
+        let since_key = DataKey::MemberSince(member);
+        env.storage().persistent().set(&since_key, &env.ledger().timestamp());
+        env.storage().persistent().extend_ttl(&since_key, 100, 8640000);
+    }
+
+    fn drop_member(env: &Env, member: Address) {
+        env.storage().persistent().remove(&DataKey::Member(member.clone()));
+        env.storage().persistent().remove(&DataKey::MemberSince(member));
+    }
+
+    fn require_member(env: &Env, address: &Address) {
+        if !Self::is_member(env.clone(), address.clone()) {
+            panic_with_error!(env, GovernanceError::NotMember);
+        }
+    }
+
+    fn load_proposal(env: &Env, proposal_id: u64) -> Proposal {
+        Self::get_proposal(env.clone(), proposal_id).expect("Proposal not found")
+    }
+
+    fn save_proposal(env: &Env, proposal: &Proposal) {
+        let key = DataKey::Proposal(proposal.proposal_id);
+        env.storage().persistent().set(&key, proposal);
+        env.storage().persistent().extend_ttl(&key, 100, 8640000);
+    }
+
+    // Aprobada si alcanza el quórum (las abstenciones cuentan) y los votos a favor
+    // superan `threshold_bps` de los votos emitidos a favor o en contra
+    fn status_of(env: &Env, proposal: &Proposal) -> ProposalStatus {
+        if proposal.executed {
+            return ProposalStatus::Executed;
+        }
+        if env.ledger().timestamp() <= proposal.end_time {
+            return ProposalStatus::Active;
+        }
+
+        let decisive = proposal.votes_for + proposal.votes_against;
+        let total = decisive + proposal.votes_abstain;
+        let approval = proposal.votes_for as u128 * MAX_BPS as u128;
+        if total >= proposal.quorum && approval > proposal.threshold_bps as u128 * decisive as u128 {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use dappcenvote_types::governance::{ContractCall, GovernanceClient};
+    use soroban_sdk::{
+        contract, contractimpl, symbol_short,
+        testutils::{Address as _, Ledger},
+        vec, IntoVal, Symbol,
+    };
+
+    mod user_registry_wasm {
+        soroban_sdk::contractimport!(file = "../user_registry/testdata/user_registry.wasm");
+    }
+
+    // Contrato destino de prueba: guarda el valor recibido si lo llama su dueño
+    #[contract]
+    struct Target;
+
+    #[contractimpl]
+    impl Target {
+        pub fn set_value(env: Env, owner: Address, value: u32) -> u32 {
+            owner.require_auth();
+            env.storage().instance().set(&symbol_short!("value"), &value);
+            value
+        }
+
+        pub fn value(env: Env) -> u32 {
+            env.storage().instance().get(&symbol_short!("value")).unwrap_or(0)
+        }
+    }
+
+    fn setup(env: &Env) -> (GovernanceClient<'_>, std::vec::Vec<Address>) {
+        env.mock_all_auths();
+        let client = GovernanceClient::new(env, &env.register(Governance, (Address::generate(env),)));
+        client.set_config(&GovernanceConfig {
+            voting_period: 1000,
+            timelock: 500,
+            quorum: 3,
+            threshold_bps: 5000,
+        });
+
+        let members: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(env)).collect();
+        for member in &members {
+            client.add_member(member);
+        }
+        (client, members)
+    }
+
+    fn set_value_proposal(env: &Env, client: &GovernanceClient, proposer: &Address) -> (u64, Address) {
+        let target = env.register(Target, ());
+        let action = ProposalAction::Call(ContractCall {
+            contract: target.clone(),
+            function: Symbol::new(env, "set_value"),
+            args: vec![env, client.address.into_val(env), 42u32.into_val(env)],
+        });
+        let title = String::from_str(env, "Fijar valor");
+        let description = String::from_str(env, "Prueba");
+        let proposal_id = client.propose(proposer, &title, &description, &action);
+        (proposal_id, target)
+    }
+
+    fn vote_all(client: &GovernanceClient, proposal_id: u64, members: &[Address], ballots: &[Ballot]) {
+        for (member, ballot) in members.iter().zip(ballots) {
+            client.cast_ballot(&proposal_id, member, ballot);
+        }
+    }
+
+    #[test]
+    fn test_proposal_passes_and_executes_after_timelock() {
+        let env = Env::default();
+        let (client, members) = setup(&env);
+
+        env.ledger().set_timestamp(100);
+        let (proposal_id, target) = set_value_proposal(&env, &client, &members[0]);
+        vote_all(&client, proposal_id, &members, &[Ballot::For, Ballot::For, Ballot::Against, Ballot::Abstain]);
+        assert_eq!(client.get_status(&proposal_id), ProposalStatus::Active);
+
+        let proposal = client.get_proposal(&proposal_id).unwrap();
+        assert_eq!((proposal.votes_for, proposal.votes_against, proposal.votes_abstain), (2, 1, 1));
+        assert_eq!((proposal.end_time, proposal.eta), (1100, 1600));
+        assert_eq!(client.get_ballot(&proposal_id, &members[3]), Some(Ballot::Abstain));
+
+        env.ledger().set_timestamp(1101);
+        assert_eq!(client.get_status(&proposal_id), ProposalStatus::Passed);
+        assert_eq!(client.try_execute(&proposal_id).err(), Some(Ok(GovernanceError::TimelockActive.into())));
+
+        env.ledger().set_timestamp(1600);
+        client.execute(&proposal_id);
+        let target = TargetClient::new(&env, &target);
+        assert_eq!(target.value(), 42);
+        assert_eq!(client.get_status(&proposal_id), ProposalStatus::Executed);
+        assert_eq!(client.try_execute(&proposal_id).err(), Some(Ok(GovernanceError::AlreadyExecuted.into())));
+    }
+
+    #[test]
+    fn test_proposal_rejected_without_majority_or_quorum() {
+        let env = Env::default();
+        let (client, members) = setup(&env);
+
+        // Empate: no supera el 50 %
+        let (tied, _) = set_value_proposal(&env, &client, &members[0]);
+        vote_all(&client, tied, &members, &[Ballot::For, Ballot::Against, Ballot::Abstain]);
+        // Mayoría a favor pero sin quórum
+        let (few, _) = set_value_proposal(&env, &client, &members[0]);
+        vote_all(&client, few, &members, &[Ballot::For, Ballot::For]);
+        // Solo abstenciones
+        let (abstained, _) = set_value_proposal(&env, &client, &members[0]);
+        vote_all(&client, abstained, &members, &[Ballot::Abstain, Ballot::Abstain, Ballot::Abstain]);
+
+        env.ledger().set_timestamp(5000);
+        for proposal_id in [tied, few, abstained] {
+            assert_eq!(client.get_status(&proposal_id), ProposalStatus::Rejected);
+            assert_eq!(client.try_execute(&proposal_id).err(), Some(Ok(GovernanceError::NotPassed.into())));
+        }
+    }
+
+    #[test]
+    fn test_supermajority_threshold() {
+        let env = Env::default();
+        let (client, members) = setup(&env);
+        client.set_config(&GovernanceConfig { voting_period: 1000, timelock: 0, quorum: 1, threshold_bps: 6667 });
+
+        let (two_thirds, _) = set_value_proposal(&env, &client, &members[0]);
+        vote_all(&client, two_thirds, &members, &[Ballot::For, Ballot::For, Ballot::Against]);
+        let (three_quarters, _) = set_value_proposal(&env, &client, &members[0]);
+        vote_all(
+            &client,
+            three_quarters,
+            &members,
+            &[Ballot::For, Ballot::For, Ballot::For, Ballot::Against],
+        );
+
+        env.ledger().set_timestamp(1001);
+        assert_eq!(client.get_status(&two_thirds), ProposalStatus::Rejected);
+        assert_eq!(client.get_status(&three_quarters), ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_ballot_rules() {
+        let env = Env::default();
+        let (client, members) = setup(&env);
+        let (proposal_id, _) = set_value_proposal(&env, &client, &members[0]);
+
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client.try_cast_ballot(&proposal_id, &outsider, &Ballot::For),
+            Err(Ok(GovernanceError::NotMember.into()))
+        );
+        client.cast_ballot(&proposal_id, &members[1], &Ballot::For);
+        assert_eq!(
+            client.try_cast_ballot(&proposal_id, &members[1], &Ballot::Against),
+            Err(Ok(GovernanceError::AlreadyVoted.into()))
+        );
+
+        env.ledger().set_timestamp(1001);
+        assert_eq!(
+            client.try_cast_ballot(&proposal_id, &members[2], &Ballot::For),
+            Err(Ok(GovernanceError::VotingClosed.into()))
+        );
+    }
+
+    #[test]
+    fn test_proposal_without_action() {
+        let env = Env::default();
+        let (client, members) = setup(&env);
+
+        let title = String::from_str(&env, "Consulta no vinculante");
+        let proposal_id = client.propose(&members[0], &title, &String::from_str(&env, ""), &ProposalAction::None);
+        vote_all(&client, proposal_id, &members, &[Ballot::For, Ballot::For, Ballot::For]);
+
+        env.ledger().set_timestamp(2000);
+        assert_eq!(client.get_status(&proposal_id), ProposalStatus::Passed);
+        assert_eq!(client.try_execute(&proposal_id).err(), Some(Ok(GovernanceError::NoAction.into())));
+    }
+
+    #[test]
+    fn test_upgrade_only_through_proposal() {
+        let env = Env::default();
+        let (client, members) = setup(&env);
+        let new_hash = env.deployer().upload_contract_wasm(user_registry_wasm::WASM);
+
+        let title = String::from_str(&env, "Actualizar gobernanza");
+        let action = ProposalAction::Upgrade(new_hash);
+        let proposal_id = client.propose(&members[0], &title, &title, &action);
+        vote_all(&client, proposal_id, &members, &[Ballot::For, Ballot::For, Ballot::For]);
+
+        env.ledger().set_timestamp(1001);
+        assert_eq!(client.try_execute(&proposal_id).err(), Some(Ok(GovernanceError::TimelockActive.into())));
+
+        env.ledger().set_timestamp(1500);
+        client.execute(&proposal_id);
+        // El contrato ya corre el wasm de la propuesta
+        let upgraded = user_registry_wasm::Client::new(&env, &client.address);
+        assert_eq!(upgraded.get_user_count(), 0);
+    }
+
+    #[test]
+    fn test_membership_changes_through_proposals() {
+        let env = Env::default();
+        let (client, members) = setup(&env);
+
+        // Tras la primera propuesta el administrador ya no cambia miembros ni parámetros
+        env.ledger().set_timestamp(100);
+        let newcomer = Address::generate(&env);
+        let action = ProposalAction::Call(ContractCall {
+            contract: client.address.clone(),
+            function: Symbol::new(&env, "add_member"),
+            args: vec![&env, newcomer.into_val(&env)],
+        });
+        let title = String::from_str(&env, "Nuevo miembro");
+        let admission = client.propose(&members[0], &title, &title, &action);
+        let required = Err(Ok(GovernanceError::ProposalRequired.into()));
+        assert_eq!(client.try_add_member(&Address::generate(&env)), required);
+        assert_eq!(client.try_remove_member(&members[1]), required);
+        let config = GovernanceConfig { voting_period: 1000, timelock: 0, quorum: 1, threshold_bps: 5000 };
+        assert_eq!(client.try_set_config(&config), required);
+
+        vote_all(&client, admission, &members, &[Ballot::For, Ballot::For, Ballot::For]);
+        env.ledger().set_timestamp(1000);
+        let (pending, _) = set_value_proposal(&env, &client, &members[0]);
+        env.ledger().set_timestamp(1600);
+        client.execute(&admission);
+        assert!(client.is_member(&newcomer));
+
+        // No vota las propuestas abiertas antes de su alta, pero sí las siguientes
+        env.ledger().set_timestamp(1700);
+        assert_eq!(
+            client.try_cast_ballot(&pending, &newcomer, &Ballot::For),
+            Err(Ok(GovernanceError::NotMember.into()))
+        );
+        let (later, _) = set_value_proposal(&env, &client, &members[0]);
+        client.cast_ballot(&later, &newcomer, &Ballot::For);
+    }
+
+    #[test]
+    fn test_only_members_propose() {
+        let env = Env::default();
+        let (client, members) = setup(&env);
+
+        client.remove_member(&members[0]);
+        assert!(!client.is_member(&members[0]));
+        let title = String::from_str(&env, "Propuesta");
+        assert_eq!(
+            client.try_propose(&members[0], &title, &title, &ProposalAction::None),
+            Err(Ok(GovernanceError::NotMember.into()))
+        );
+        assert_eq!(client.get_proposal_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Threshold must be between 1 and 10000 basis points")]
+    fn test_invalid_threshold() {
+        let env = Env::default();
+        let (client, _members) = setup(&env);
+        client.set_config(&GovernanceConfig { voting_period: 1000, timelock: 0, quorum: 1, threshold_bps: 10_001 });
+    }
+}
@@ -0,0 +1,180 @@
+//! Sistema de restricciones R1CS del voto anónimo: pertenencia al grupo por una
+//! ruta de Merkle, nullifier de la encuesta y señal (el candidato elegido).
+use std::ops::{Add, Sub};
+
+use ark_bls12_381::Fr;
+use ark_ff::{Field, One, Zero};
+
+use crate::{hash, mimc_constants};
+
+/// Combinación lineal Σ coeficiente·z[variable]
+#[derive(Clone, Debug, Default)]
+pub struct Lc(pub Vec<(usize, Fr)>);
+
+impl Lc {
+    pub fn var(index: usize) -> Self {
+        Lc(vec![(index, Fr::one())])
+    }
+
+    pub fn constant(value: Fr) -> Self {
+        Lc(vec![(0, value)])
+    }
+}
+
+impl Add for Lc {
+    type Output = Lc;
+
+    fn add(mut self, other: Lc) -> Lc {
+        self.0.extend(other.0);
+        self
+    }
+}
+
+impl Sub for Lc {
+    type Output = Lc;
+
+    fn sub(mut self, other: Lc) -> Lc {
+        self.0.extend(other.0.into_iter().map(|(index, coeff)| (index, -coeff)));
+        self
+    }
+}
+
+/// Restricciones A·z ∘ B·z = C·z con su asignación z = [1, entradas públicas, testigos]
+#[derive(Clone, Debug)]
+pub struct ConstraintSystem {
+    pub num_inputs: usize, // Incluye la constante 1
+    pub assignment: Vec<Fr>,
+    pub constraints: Vec<(Lc, Lc, Lc)>,
+}
+
+impl Default for ConstraintSystem {
+    fn default() -> Self {
+        ConstraintSystem { num_inputs: 1, assignment: vec![Fr::one()], constraints: Vec::new() }
+    }
+}
+
+impl ConstraintSystem {
+    // Las entradas públicas se reservan antes que cualquier testigo
+    fn input(&mut self, value: Fr) -> Lc {
+        assert_eq!(self.num_inputs, self.assignment.len(), "inputs must be allocated first");
+        self.num_inputs += 1;
+        self.witness(value)
+    }
+
+    fn witness(&mut self, value: Fr) -> Lc {
+        self.assignment.push(value);
+        Lc::var(self.assignment.len() - 1)
+    }
+
+    fn enforce(&mut self, a: Lc, b: Lc, c: Lc) {
+        self.constraints.push((a, b, c));
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.assignment.len()
+    }
+
+    pub fn eval(&self, lc: &Lc) -> Fr {
+        lc.0.iter().map(|(index, coeff)| self.assignment[*index] * coeff).sum()
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.constraints
+            .iter()
+            .all(|(a, b, c)| self.eval(a) * self.eval(b) == self.eval(c))
+    }
+
+    // hash(left, right) = MiMC_left(right) + left + right, tres restricciones por ronda
+    fn hash(&mut self, left: Lc, right: Lc) -> Lc {
+        let mut x = right.clone();
+        for c in mimc_constants() {
+            let t = x + left.clone() + Lc::constant(*c);
+            let t_value = self.eval(&t);
+            let t2 = self.witness(t_value.square());
+            self.enforce(t.clone(), t.clone(), t2.clone());
+            let t4 = self.witness(t_value.square().square());
+            self.enforce(t2.clone(), t2, t4.clone());
+            let t5 = self.witness(t_value.pow([5]));
+            self.enforce(t4, t, t5.clone());
+            x = t5;
+        }
+        x + left.clone() + left + right
+    }
+}
+
+/// Circuito del voto: entradas públicas [raíz, nullifier, ámbito, señal]
+///
+/// Con `siblings[i]` el hermano en el nivel `i` y el bit `i` de `index` a 1 si
+/// el nodo actual es el hijo derecho. La estructura no depende de los valores,
+/// así que la configuración se hace con una asignación a cero.
+pub fn vote_circuit(
+    nullifier: Fr,
+    trapdoor: Fr,
+    siblings: &[Fr],
+    index: u64,
+    scope: Fr,
+    signal: Fr,
+) -> ConstraintSystem {
+    let mut leaf = hash(nullifier, trapdoor);
+    for (level, sibling) in siblings.iter().enumerate() {
+        leaf = if (index >> level) & 1 == 1 { hash(*sibling, leaf) } else { hash(leaf, *sibling) };
+    }
+
+    let mut cs = ConstraintSystem::default();
+    let root = cs.input(leaf);
+    let nullifier_hash = cs.input(hash(nullifier, scope));
+    let scope = cs.input(scope);
+    let signal_value = signal;
+    let signal = cs.input(signal);
+
+    let secret = cs.witness(nullifier);
+    let trapdoor = cs.witness(trapdoor);
+    let mut current = cs.hash(secret.clone(), trapdoor);
+    for (level, sibling) in siblings.iter().enumerate() {
+        let bit_value = Fr::from((index >> level) & 1);
+        let bit = cs.witness(bit_value);
+        cs.enforce(bit.clone(), Lc::constant(Fr::one()) - bit.clone(), Lc::default());
+        let sibling_value = *sibling;
+        let sibling = cs.witness(sibling_value);
+
+        // left = actual + bit·(hermano − actual); right = actual + hermano − left
+        let current_value = cs.eval(&current);
+        let left_value = if bit_value.is_zero() { current_value } else { sibling_value };
+        let left = cs.witness(left_value);
+        cs.enforce(bit, sibling.clone() - current.clone(), left.clone() - current.clone());
+        let right = current + sibling - left.clone();
+        current = cs.hash(left, right);
+    }
+    cs.enforce(current, Lc::constant(Fr::one()), root);
+
+    let computed = cs.hash(secret, scope);
+    cs.enforce(computed, Lc::constant(Fr::one()), nullifier_hash);
+
+    // La señal no participa en nada más; sin esta restricción no quedaría ligada a la prueba
+    let square = cs.witness(signal_value.square());
+    cs.enforce(signal.clone(), signal, square);
+    cs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{merkle_path, merkle_root};
+
+    #[test]
+    fn test_vote_circuit_is_satisfied() {
+        let members: Vec<Fr> = (1..=3u64).map(|i| hash(Fr::from(i), Fr::from(i + 10))).collect();
+        let (siblings, index) = merkle_path(&members, 2, 4);
+        let cs = vote_circuit(Fr::from(3u64), Fr::from(13u64), &siblings, index, Fr::from(7u64), Fr::from(1u64));
+
+        assert!(cs.is_satisfied());
+        assert_eq!(cs.num_inputs, 5);
+        assert_eq!(cs.assignment[1], merkle_root(&members, 4));
+
+        // Una identidad fuera del grupo no reproduce la raíz
+        let outsider = vote_circuit(Fr::from(4u64), Fr::from(14u64), &siblings, index, Fr::from(7u64), Fr::from(1u64));
+        let mut forged = outsider.clone();
+        forged.assignment[1] = cs.assignment[1];
+        assert!(!forged.is_satisfied());
+    }
+}
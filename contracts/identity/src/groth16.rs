@@ -0,0 +1,175 @@
+//! Groth16 sobre BLS12-381 para los sistemas de `circuit`, con la misma
+//! reducción de R1CS a QAP que libsnark: a cada entrada pública se le añade la
+//! restricción z_i·0 = 0 para que sus polinomios sean linealmente independientes.
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, scalar_mul::fixed_base::FixedBase, CurveGroup, Group, ScalarMul, VariableBaseMSM};
+use ark_ff::{FftField, Field, One, PrimeField, UniformRand, Zero};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+
+use crate::circuit::{ConstraintSystem, Lc};
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifyingKey {
+    pub alpha_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub gamma_g2: G2Affine,
+    pub delta_g2: G2Affine,
+    pub ic: Vec<G1Affine>, // Uno por entrada pública, más el de la constante 1
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ProvingKey {
+    pub vk: VerifyingKey,
+    pub beta_g1: G1Affine,
+    pub delta_g1: G1Affine,
+    pub a_query: Vec<G1Affine>,
+    pub b_g1_query: Vec<G1Affine>,
+    pub b_g2_query: Vec<G2Affine>,
+    pub h_query: Vec<G1Affine>,
+    pub l_query: Vec<G1Affine>, // Solo para los testigos
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+// Filas A, B, C de la QAP: las restricciones y después una por entrada pública
+fn rows(cs: &ConstraintSystem) -> Vec<(Lc, Lc, Lc)> {
+    let mut rows = cs.constraints.clone();
+    rows.extend((0..cs.num_inputs).map(|i| (Lc::var(i), Lc::default(), Lc::default())));
+    rows
+}
+
+fn domain(rows: usize) -> Radix2EvaluationDomain<Fr> {
+    Radix2EvaluationDomain::new(rows).expect("circuit too large for the BLS12-381 FFT domain")
+}
+
+fn batch_mul<G: ScalarMul + CurveGroup<ScalarField = Fr>>(base: G, scalars: &[Fr]) -> Vec<G::Affine> {
+    let window = FixedBase::get_mul_window_size(scalars.len());
+    let table = FixedBase::get_window_table(Fr::MODULUS_BIT_SIZE as usize, window, base);
+    let points = FixedBase::msm::<G>(Fr::MODULUS_BIT_SIZE as usize, window, &table, scalars);
+    G::normalize_batch(&points)
+}
+
+/// Configuración de confianza de un circuito. Quien conozca la aleatoriedad
+/// usada aquí puede falsificar pruebas, así que debe descartarse.
+pub fn setup<R: RngCore + CryptoRng>(cs: &ConstraintSystem, rng: &mut R) -> ProvingKey {
+    let tau = Fr::rand(rng);
+    let alpha = Fr::rand(rng);
+    let beta = Fr::rand(rng);
+    let gamma = Fr::rand(rng);
+    let delta = Fr::rand(rng);
+
+    let rows = rows(cs);
+    let domain = domain(rows.len());
+    let lagrange = domain.evaluate_all_lagrange_coefficients(tau);
+    let mut u = vec![Fr::zero(); cs.num_variables()];
+    let mut v = vec![Fr::zero(); cs.num_variables()];
+    let mut w = vec![Fr::zero(); cs.num_variables()];
+    for ((a, b, c), l) in rows.iter().zip(&lagrange) {
+        a.0.iter().for_each(|(i, coeff)| u[*i] += *coeff * l);
+        b.0.iter().for_each(|(i, coeff)| v[*i] += *coeff * l);
+        c.0.iter().for_each(|(i, coeff)| w[*i] += *coeff * l);
+    }
+
+    let gamma_inverse = gamma.inverse().expect("gamma is non-zero");
+    let delta_inverse = delta.inverse().expect("delta is non-zero");
+    let combined = |i: usize| beta * u[i] + alpha * v[i] + w[i];
+    let ic: Vec<Fr> = (0..cs.num_inputs).map(|i| combined(i) * gamma_inverse).collect();
+    let l: Vec<Fr> = (cs.num_inputs..cs.num_variables()).map(|i| combined(i) * delta_inverse).collect();
+    let vanishing = domain.evaluate_vanishing_polynomial(tau) * delta_inverse;
+    let h: Vec<Fr> = (0..domain.size() - 1)
+        .scan(vanishing, |acc, _| {
+            let value = *acc;
+            *acc *= tau;
+            Some(value)
+        })
+        .collect();
+
+    let g1 = G1Projective::generator();
+    let g2 = G2Projective::generator();
+    ProvingKey {
+        vk: VerifyingKey {
+            alpha_g1: (g1 * alpha).into_affine(),
+            beta_g2: (g2 * beta).into_affine(),
+            gamma_g2: (g2 * gamma).into_affine(),
+            delta_g2: (g2 * delta).into_affine(),
+            ic: batch_mul(g1, &ic),
+        },
+        beta_g1: (g1 * beta).into_affine(),
+        delta_g1: (g1 * delta).into_affine(),
+        a_query: batch_mul(g1, &u),
+        b_g1_query: batch_mul(g1, &v),
+        b_g2_query: batch_mul(g2, &v),
+        h_query: batch_mul(g1, &h),
+        l_query: batch_mul(g1, &l),
+    }
+}
+
+// Coeficientes de h(x) = (a(x)·b(x) − c(x)) / (xⁿ − 1), evaluando en una clase lateral
+fn quotient(cs: &ConstraintSystem, domain: &Radix2EvaluationDomain<Fr>) -> Vec<Fr> {
+    let rows = rows(cs);
+    let mut a = vec![Fr::zero(); domain.size()];
+    let mut b = vec![Fr::zero(); domain.size()];
+    let mut c = vec![Fr::zero(); domain.size()];
+    for (row, (a_lc, b_lc, c_lc)) in rows.iter().enumerate() {
+        a[row] = cs.eval(a_lc);
+        b[row] = cs.eval(b_lc);
+        c[row] = cs.eval(c_lc);
+    }
+
+    let coset = domain.get_coset(Fr::GENERATOR).expect("generator is outside the domain");
+    for evals in [&mut a, &mut b, &mut c] {
+        domain.ifft_in_place(evals);
+        coset.fft_in_place(evals);
+    }
+    let vanishing_inverse = domain
+        .evaluate_vanishing_polynomial(Fr::GENERATOR)
+        .inverse()
+        .expect("vanishing polynomial is non-zero on the coset");
+    let mut h: Vec<Fr> = a
+        .iter()
+        .zip(&b)
+        .zip(&c)
+        .map(|((a, b), c)| (*a * b - c) * vanishing_inverse)
+        .collect();
+    coset.ifft_in_place(&mut h);
+    h.truncate(domain.size() - 1);
+    h
+}
+
+pub fn prove<R: RngCore + CryptoRng>(pk: &ProvingKey, cs: &ConstraintSystem, rng: &mut R) -> Proof {
+    let r = Fr::rand(rng);
+    let s = Fr::rand(rng);
+    let z = &cs.assignment;
+    let h = quotient(cs, &domain(cs.constraints.len() + cs.num_inputs));
+
+    let a = pk.vk.alpha_g1 + G1Projective::msm_unchecked(&pk.a_query, z) + pk.delta_g1 * r;
+    let b = pk.vk.beta_g2 + G2Projective::msm_unchecked(&pk.b_g2_query, z) + pk.vk.delta_g2 * s;
+    let b_g1 = pk.beta_g1 + G1Projective::msm_unchecked(&pk.b_g1_query, z) + pk.delta_g1 * s;
+    let c = G1Projective::msm_unchecked(&pk.l_query, &z[cs.num_inputs..])
+        + G1Projective::msm_unchecked(&pk.h_query, &h)
+        + a * s
+        + b_g1 * r
+        - pk.delta_g1 * (r * s);
+
+    Proof { a: a.into_affine(), b: b.into_affine(), c: c.into_affine() }
+}
+
+/// e(A, B) = e(α, β)·e(Σ entradaᵢ·ICᵢ, γ)·e(C, δ), como en el contrato
+pub fn verify(vk: &VerifyingKey, inputs: &[Fr], proof: &Proof) -> bool {
+    if inputs.len() + 1 != vk.ic.len() {
+        return false;
+    }
+    let prepared = G1Projective::msm_unchecked(&vk.ic[1..], inputs) + vk.ic[0];
+    let result = Bls12_381::multi_pairing(
+        [-proof.a, vk.alpha_g1, prepared.into_affine(), proof.c],
+        [proof.b, vk.beta_g2, vk.gamma_g2, vk.delta_g2],
+    );
+    result.0.is_one()
+}
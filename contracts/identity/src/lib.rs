@@ -0,0 +1,402 @@
+//! Herramienta de identidades para el voto anónimo del contrato
+//! `anonymous_vote`, al estilo de Semaphore: cada usuario guarda en secreto un
+//! `nullifier` y un `trapdoor` y publica solo su compromiso
+//! `hash(nullifier, trapdoor)`, que entra como hoja en el árbol de Merkle del
+//! grupo de la encuesta. Para votar prueba con Groth16 que conoce una hoja del
+//! árbol y publica `hash(nullifier, survey_id)`, que impide votar dos veces sin
+//! revelar cuál de las hojas es la suya.
+//!
+//! El hash es MiMC (x⁵, 110 rondas) sobre Fr de BLS12-381 en modo
+//! Miyaguchi-Preneel, el mismo que el contrato calcula con las funciones host.
+pub mod circuit;
+pub mod groth16;
+
+use std::fmt::Write as _;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, ensure, Result};
+use ark_bls12_381::{Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, Field, PrimeField, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const TREE_DEPTH: usize = 16;
+pub const MIMC_ROUNDS: usize = 110;
+pub const MIMC_TAG: &[u8] = b"dappcenvote/mimc";
+
+/// Escalar de Fr en big-endian
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Scalar(#[serde(with = "hex::serde")] pub [u8; 32]);
+
+/// Punto de G1 sin comprimir, en el formato de las funciones host de Soroban
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct G1Point(#[serde(with = "hex::serde")] pub [u8; 96]);
+
+/// Punto de G2 sin comprimir (c1 antes que c0), en el formato de Soroban
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct G2Point(#[serde(with = "hex::serde")] pub [u8; 192]);
+
+impl Scalar {
+    pub fn from_field(value: Fr) -> Self {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&value.into_bigint().to_bytes_be());
+        Scalar(out)
+    }
+
+    pub fn to_field(self) -> Fr {
+        Fr::from_be_bytes_mod_order(&self.0)
+    }
+}
+
+impl G1Point {
+    fn from_affine(point: &G1Affine) -> Self {
+        let mut out = [0u8; 96];
+        point.serialize_uncompressed(&mut out[..]).expect("G1 points are 96 bytes uncompressed");
+        G1Point(out)
+    }
+
+    fn to_affine(self) -> Result<G1Affine> {
+        G1Affine::deserialize_uncompressed(&self.0[..]).map_err(|_| anyhow!("invalid G1 point"))
+    }
+}
+
+impl G2Point {
+    fn from_affine(point: &G2Affine) -> Self {
+        let mut out = [0u8; 192];
+        point.serialize_uncompressed(&mut out[..]).expect("G2 points are 192 bytes uncompressed");
+        G2Point(out)
+    }
+
+    fn to_affine(self) -> Result<G2Affine> {
+        G2Affine::deserialize_uncompressed(&self.0[..]).map_err(|_| anyhow!("invalid G2 point"))
+    }
+}
+
+/// Clave de verificación con el formato de `set_verifying_key`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyingKey {
+    pub alpha: G1Point,
+    pub beta: G2Point,
+    pub gamma: G2Point,
+    pub delta: G2Point,
+    pub ic: Vec<G1Point>,
+}
+
+impl VerifyingKey {
+    pub fn from_groth16(vk: &groth16::VerifyingKey) -> Self {
+        VerifyingKey {
+            alpha: G1Point::from_affine(&vk.alpha_g1),
+            beta: G2Point::from_affine(&vk.beta_g2),
+            gamma: G2Point::from_affine(&vk.gamma_g2),
+            delta: G2Point::from_affine(&vk.delta_g2),
+            ic: vk.ic.iter().map(G1Point::from_affine).collect(),
+        }
+    }
+
+    pub fn to_groth16(&self) -> Result<groth16::VerifyingKey> {
+        Ok(groth16::VerifyingKey {
+            alpha_g1: self.alpha.to_affine()?,
+            beta_g2: self.beta.to_affine()?,
+            gamma_g2: self.gamma.to_affine()?,
+            delta_g2: self.delta.to_affine()?,
+            ic: self.ic.iter().map(|point| point.to_affine()).collect::<Result<_>>()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Groth16Proof {
+    pub a: G1Point,
+    pub b: G2Point,
+    pub c: G1Point,
+}
+
+impl Groth16Proof {
+    fn from_groth16(proof: &groth16::Proof) -> Self {
+        Groth16Proof {
+            a: G1Point::from_affine(&proof.a),
+            b: G2Point::from_affine(&proof.b),
+            c: G1Point::from_affine(&proof.c),
+        }
+    }
+
+    fn to_groth16(&self) -> Result<groth16::Proof> {
+        Ok(groth16::Proof { a: self.a.to_affine()?, b: self.b.to_affine()?, c: self.c.to_affine()? })
+    }
+}
+
+/// Identidad de un votante; `nullifier` y `trapdoor` nunca salen de su equipo
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identity {
+    pub nullifier: Scalar,
+    pub trapdoor: Scalar,
+    pub commitment: Scalar,
+}
+
+impl Identity {
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let nullifier = Fr::rand(rng);
+        let trapdoor = Fr::rand(rng);
+        Identity {
+            nullifier: Scalar::from_field(nullifier),
+            trapdoor: Scalar::from_field(trapdoor),
+            commitment: Scalar::from_field(hash(nullifier, trapdoor)),
+        }
+    }
+}
+
+/// Argumentos de `cast_vote`, salvo la encuesta y la dirección del candidato
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vote {
+    pub candidate: u32,
+    pub nullifier_hash: Scalar,
+    pub proof: Groth16Proof,
+}
+
+/// Datos precalculados para las pruebas del contrato
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub survey_id: u64,
+    pub verifying_key: VerifyingKey,
+    pub members: Vec<Scalar>,
+    pub root: Scalar,
+    pub votes: Vec<Vote>,
+}
+
+/// Constantes de ronda: c₀ = 0 y cᵢ = sha256(etiqueta || i) reducido módulo r
+pub fn mimc_constants() -> &'static [Fr] {
+    static CONSTANTS: OnceLock<Vec<Fr>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        (0..MIMC_ROUNDS as u32)
+            .map(|i| {
+                if i == 0 {
+                    return Fr::zero();
+                }
+                let mut hasher = Sha256::new();
+                hasher.update(MIMC_TAG);
+                hasher.update(i.to_be_bytes());
+                Fr::from_be_bytes_mod_order(&hasher.finalize())
+            })
+            .collect()
+    })
+}
+
+/// MiMC con clave k: x ← (x + k + cᵢ)⁵ en cada ronda, y al final x + k
+pub fn mimc(mut x: Fr, k: Fr) -> Fr {
+    for c in mimc_constants() {
+        x = (x + k + c).pow([5]);
+    }
+    x + k
+}
+
+/// Compresión de dos elementos: MiMC_left(right) + left + right
+pub fn hash(left: Fr, right: Fr) -> Fr {
+    mimc(right, left) + left + right
+}
+
+/// Hojas vacías de cada nivel: zeros[0] = 0 y zeros[i + 1] = hash(zeros[i], zeros[i])
+pub fn zeros(depth: usize) -> Vec<Fr> {
+    let mut zeros = vec![Fr::zero()];
+    for level in 0..depth - 1 {
+        zeros.push(hash(zeros[level], zeros[level]));
+    }
+    zeros
+}
+
+pub fn scope(survey_id: u64) -> Fr {
+    Fr::from(survey_id)
+}
+
+fn parent_layer(layer: &[Fr], zero: Fr) -> Vec<Fr> {
+    layer
+        .chunks(2)
+        .map(|pair| hash(pair[0], pair.get(1).copied().unwrap_or(zero)))
+        .collect()
+}
+
+/// Raíz del árbol con las hojas en orden de alta y el resto vacías
+pub fn merkle_root(members: &[Fr], depth: usize) -> Fr {
+    let zeros = zeros(depth);
+    let mut layer = members.to_vec();
+    for zero in &zeros {
+        if layer.is_empty() {
+            layer.push(*zero);
+        }
+        layer = parent_layer(&layer, *zero);
+    }
+    layer[0]
+}
+
+/// Hermanos de la hoja `index`, de abajo arriba, e índice cuyos bits indican el lado
+pub fn merkle_path(members: &[Fr], index: usize, depth: usize) -> (Vec<Fr>, u64) {
+    let zeros = zeros(depth);
+    let mut layer = members.to_vec();
+    let mut siblings = Vec::with_capacity(depth);
+    let mut position = index;
+    for zero in &zeros {
+        siblings.push(layer.get(position ^ 1).copied().unwrap_or(*zero));
+        layer = parent_layer(&layer, *zero);
+        position /= 2;
+    }
+    (siblings, index as u64)
+}
+
+fn blank_circuit(depth: usize) -> circuit::ConstraintSystem {
+    circuit::vote_circuit(Fr::zero(), Fr::zero(), &vec![Fr::zero(); depth], 0, Fr::zero(), Fr::zero())
+}
+
+/// Configuración de confianza del circuito de voto para árboles de `depth` niveles
+pub fn setup<R: RngCore + CryptoRng>(depth: usize, rng: &mut R) -> groth16::ProvingKey {
+    groth16::setup(&blank_circuit(depth), rng)
+}
+
+/// Prueba de que `identity` pertenece al grupo `members` y vota por `candidate`
+pub fn prove_vote<R: RngCore + CryptoRng>(
+    pk: &groth16::ProvingKey,
+    depth: usize,
+    identity: &Identity,
+    members: &[Scalar],
+    survey_id: u64,
+    candidate: u32,
+    rng: &mut R,
+) -> Result<Vote> {
+    ensure!(pk.vk.ic.len() == 5, "proving key is not for the vote circuit");
+    ensure!(members.len() <= 1 << depth, "group is larger than the tree");
+    let index = members
+        .iter()
+        .position(|member| *member == identity.commitment)
+        .ok_or_else(|| anyhow!("identity is not a member of the group"))?;
+
+    let leaves: Vec<Fr> = members.iter().map(|member| member.to_field()).collect();
+    let (siblings, index) = merkle_path(&leaves, index, depth);
+    let cs = circuit::vote_circuit(
+        identity.nullifier.to_field(),
+        identity.trapdoor.to_field(),
+        &siblings,
+        index,
+        scope(survey_id),
+        Fr::from(candidate),
+    );
+    ensure!(cs.is_satisfied(), "identity does not match its commitment");
+    ensure!(cs.num_variables() == pk.a_query.len(), "proving key was made for another tree depth");
+
+    Ok(Vote {
+        candidate,
+        nullifier_hash: Scalar::from_field(cs.assignment[2]),
+        proof: Groth16Proof::from_groth16(&groth16::prove(pk, &cs, rng)),
+    })
+}
+
+/// Verifica un voto como lo hace el contrato
+pub fn verify_vote(vk: &VerifyingKey, root: &Scalar, survey_id: u64, vote: &Vote) -> Result<bool> {
+    let inputs = [root.to_field(), vote.nullifier_hash.to_field(), scope(survey_id), Fr::from(vote.candidate)];
+    Ok(groth16::verify(&vk.to_groth16()?, &inputs, &vote.proof.to_groth16()?))
+}
+
+fn limbs(value: &Fr) -> String {
+    let bytes = value.into_bigint().to_bytes_be();
+    let limbs: Vec<String> = bytes
+        .chunks(8)
+        .map(|chunk| format!("0x{:016x}", u64::from_be_bytes(chunk.try_into().unwrap())))
+        .collect();
+    format!("[{}]", limbs.join(", "))
+}
+
+/// Fuente de `anonymous_vote/src/constants.rs`: cada elemento como cuatro u64
+/// big-endian, listos para `U256::from_parts`
+pub fn render_constants() -> String {
+    let mut out = String::new();
+    writeln!(out, "// Generado con `dappcenvote-identity constants`; no editar a mano").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "// Niveles del árbol de identidades de cada grupo").unwrap();
+    writeln!(out, "pub const TREE_DEPTH: u32 = {TREE_DEPTH};").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "// Constantes de ronda de MiMC: c₀ = 0 y cᵢ = sha256(\"dappcenvote/mimc\" || i) mod r").unwrap();
+    writeln!(out, "pub const MIMC_CONSTANTS: [[u64; 4]; {MIMC_ROUNDS}] = [").unwrap();
+    for c in mimc_constants() {
+        writeln!(out, "    {},", limbs(c)).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "// Nodos de un subárbol vacío en cada nivel").unwrap();
+    writeln!(out, "pub const ZEROS: [[u64; 4]; {TREE_DEPTH}] = [").unwrap();
+    for zero in zeros(TREE_DEPTH) {
+        writeln!(out, "    {},", limbs(&zero)).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    out
+}
+
+/// Configuración, grupo y votos deterministas para las pruebas del contrato
+pub fn fixture<R: RngCore + CryptoRng>(survey_id: u64, candidates: &[u32], rng: &mut R) -> Result<Fixture> {
+    let pk = setup(TREE_DEPTH, rng);
+    let identities: Vec<Identity> = candidates.iter().map(|_| Identity::generate(rng)).collect();
+    let members: Vec<Scalar> = identities.iter().map(|identity| identity.commitment).collect();
+    let leaves: Vec<Fr> = members.iter().map(|member| member.to_field()).collect();
+    let votes = identities
+        .iter()
+        .zip(candidates)
+        .map(|(identity, candidate)| prove_vote(&pk, TREE_DEPTH, identity, &members, survey_id, *candidate, rng))
+        .collect::<Result<_>>()?;
+
+    Ok(Fixture {
+        survey_id,
+        verifying_key: VerifyingKey::from_groth16(&pk.vk),
+        root: Scalar::from_field(merkle_root(&leaves, TREE_DEPTH)),
+        members,
+        votes,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_prove_and_verify_vote() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let pk = setup(2, &mut rng);
+        let vk = VerifyingKey::from_groth16(&pk.vk);
+        let identities: Vec<Identity> = (0..3).map(|_| Identity::generate(&mut rng)).collect();
+        let members: Vec<Scalar> = identities.iter().map(|identity| identity.commitment).collect();
+        let root = Scalar::from_field(merkle_root(&members.iter().map(|m| m.to_field()).collect::<Vec<_>>(), 2));
+
+        let vote = prove_vote(&pk, 2, &identities[1], &members, 9, 2, &mut rng).unwrap();
+        assert!(verify_vote(&vk, &root, 9, &vote).unwrap());
+
+        // El nullifier depende solo de la identidad y la encuesta
+        let again = prove_vote(&pk, 2, &identities[1], &members, 9, 0, &mut rng).unwrap();
+        assert_eq!(again.nullifier_hash, vote.nullifier_hash);
+        let other = prove_vote(&pk, 2, &identities[1], &members, 10, 2, &mut rng).unwrap();
+        assert_ne!(other.nullifier_hash, vote.nullifier_hash);
+
+        // La prueba no sirve para otra señal, otra encuesta u otro grupo
+        let mut changed = vote.clone();
+        changed.candidate = 0;
+        assert!(!verify_vote(&vk, &root, 9, &changed).unwrap());
+        assert!(!verify_vote(&vk, &root, 10, &vote).unwrap());
+        assert!(!verify_vote(&vk, &members[0], 9, &vote).unwrap());
+
+        let outsider = Identity::generate(&mut rng);
+        assert!(prove_vote(&pk, 2, &outsider, &members, 9, 2, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_committed_artifacts_are_current() {
+        assert_eq!(include_str!("../../anonymous_vote/src/constants.rs"), render_constants());
+
+        let fixture: Fixture =
+            serde_json::from_str(include_str!("../../anonymous_vote/fixtures/votes.json")).unwrap();
+        let leaves: Vec<Fr> = fixture.members.iter().map(|member| member.to_field()).collect();
+        assert_eq!(Scalar::from_field(merkle_root(&leaves, TREE_DEPTH)), fixture.root);
+        for vote in &fixture.votes {
+            assert!(verify_vote(&fixture.verifying_key, &fixture.root, fixture.survey_id, vote).unwrap());
+        }
+    }
+}
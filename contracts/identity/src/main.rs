@@ -0,0 +1,129 @@
+//! `dappcenvote-identity`: identidades, configuración y pruebas del voto
+//! anónimo. Lee y escribe JSON con el mismo formato que `stellar contract
+//! invoke` usa para los getters y argumentos del contrato.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use clap::{Parser, Subcommand};
+use rand::{rngs::OsRng, rngs::StdRng, SeedableRng};
+use serde::{de::DeserializeOwned, Serialize};
+
+use dappcenvote_identity::{groth16::ProvingKey, Identity, Scalar, VerifyingKey, TREE_DEPTH};
+
+#[derive(Parser)]
+#[command(name = "dappcenvote-identity", version, about = "Identity tool for anonymous DappCenvote votes")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate a new identity; only its commitment goes to `join_group`
+    New,
+    /// Run the trusted setup, write the proving key and print the verifying key
+    Setup {
+        /// Where to write the proving key
+        #[arg(long)]
+        proving_key: PathBuf,
+    },
+    /// Prove membership and print the arguments for `cast_vote`
+    Prove {
+        #[arg(long)]
+        proving_key: PathBuf,
+        /// JSON file with the output of `new`
+        #[arg(long)]
+        identity: PathBuf,
+        /// JSON file with the output of `get_members`
+        #[arg(long)]
+        members: PathBuf,
+        #[arg(long)]
+        survey_id: u64,
+        /// Position of the chosen candidate in the survey
+        #[arg(long)]
+        candidate: u32,
+    },
+    /// Check a vote against the verifying key and the group root
+    Verify {
+        /// JSON file with the output of `get_group_verifying_key`
+        #[arg(long)]
+        verifying_key: PathBuf,
+        /// Hex-encoded root from `get_group`
+        #[arg(long)]
+        root: String,
+        #[arg(long)]
+        survey_id: u64,
+        /// JSON file with the output of `prove`
+        #[arg(long)]
+        vote: PathBuf,
+    },
+    /// Print the constants compiled into the contract
+    Constants,
+    /// Print deterministic test fixtures for the contract
+    Fixtures {
+        #[arg(long, default_value_t = 1)]
+        survey_id: u64,
+        /// Candidate position chosen by each fixture voter
+        #[arg(long = "candidate", default_values_t = [0, 1, 1])]
+        candidates: Vec<u32>,
+        #[arg(long, default_value_t = 46)]
+        seed: u64,
+    },
+}
+
+fn main() -> Result<()> {
+    let mut rng = OsRng;
+
+    match Cli::parse().command {
+        Commands::New => print_json(&Identity::generate(&mut rng)),
+        Commands::Setup { proving_key } => {
+            let pk = dappcenvote_identity::setup(TREE_DEPTH, &mut rng);
+            let mut bytes = Vec::new();
+            pk.serialize_compressed(&mut bytes).map_err(|err| anyhow!("failed to serialize the proving key: {err}"))?;
+            fs::write(&proving_key, bytes).with_context(|| format!("failed to write {}", proving_key.display()))?;
+            print_json(&VerifyingKey::from_groth16(&pk.vk))
+        }
+        Commands::Prove { proving_key, identity, members, survey_id, candidate } => {
+            let bytes = fs::read(&proving_key).with_context(|| format!("failed to read {}", proving_key.display()))?;
+            let pk = ProvingKey::deserialize_compressed(&bytes[..]).map_err(|_| anyhow!("invalid proving key"))?;
+            let identity: Identity = read_json(&identity)?;
+            let members: Vec<Scalar> = read_json(&members)?;
+            print_json(&dappcenvote_identity::prove_vote(
+                &pk, TREE_DEPTH, &identity, &members, survey_id, candidate, &mut rng,
+            )?)
+        }
+        Commands::Verify { verifying_key, root, survey_id, vote } => {
+            let mut bytes = [0u8; 32];
+            hex::decode_to_slice(root.trim(), &mut bytes).context("expected 32 hex-encoded bytes")?;
+            let valid = dappcenvote_identity::verify_vote(
+                &read_json(&verifying_key)?,
+                &Scalar(bytes),
+                survey_id,
+                &read_json(&vote)?,
+            )?;
+            ensure!(valid, "vote does not verify against this key and root");
+            println!("vote is valid");
+            Ok(())
+        }
+        Commands::Constants => {
+            print!("{}", dappcenvote_identity::render_constants());
+            Ok(())
+        }
+        Commands::Fixtures { survey_id, candidates, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            print_json(&dappcenvote_identity::fixture(survey_id, &candidates, &mut rng)?)
+        }
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let text = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("unexpected JSON in {}", path.display()))
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
@@ -0,0 +1,396 @@
+use std::path::Path;
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::event::{Event, EventPage, RpcEvent};
+use crate::{ProfileVersion, VoteResult};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY,
+    ledger INTEGER NOT NULL,
+    contract_id TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS surveys (
+    contract_id TEXT NOT NULL,
+    survey_id INTEGER NOT NULL,
+    creator TEXT NOT NULL,
+    name TEXT NOT NULL,
+    start_date INTEGER NOT NULL,
+    end_date INTEGER NOT NULL,
+    created_ledger INTEGER NOT NULL,
+    finalized_total INTEGER,
+    PRIMARY KEY (contract_id, survey_id)
+);
+CREATE TABLE IF NOT EXISTS survey_candidates (
+    contract_id TEXT NOT NULL,
+    survey_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    candidate TEXT NOT NULL,
+    PRIMARY KEY (contract_id, survey_id, position)
+);
+CREATE TABLE IF NOT EXISTS votes (
+    contract_id TEXT NOT NULL,
+    survey_id INTEGER NOT NULL,
+    voter TEXT NOT NULL,
+    candidate TEXT, -- NULL si la encuesta oculta sus resultados
+    ledger INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, survey_id, voter)
+);
+CREATE TABLE IF NOT EXISTS responses (
+    contract_id TEXT NOT NULL,
+    survey_id INTEGER NOT NULL,
+    voter TEXT NOT NULL,
+    answers TEXT, -- NULL si la encuesta oculta sus resultados
+    ledger INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, survey_id, voter)
+);
+CREATE TABLE IF NOT EXISTS cancellations (
+    contract_id TEXT NOT NULL,
+    survey_id INTEGER NOT NULL,
+    cancelled_by TEXT NOT NULL,
+    ledger INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, survey_id)
+);
+CREATE TABLE IF NOT EXISTS refunds (
+    contract_id TEXT NOT NULL,
+    survey_id INTEGER NOT NULL,
+    voter TEXT NOT NULL,
+    token TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    ledger INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, survey_id, voter)
+);
+CREATE TABLE IF NOT EXISTS tallies (
+    contract_id TEXT NOT NULL,
+    survey_id INTEGER NOT NULL,
+    candidate TEXT NOT NULL,
+    votes INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, survey_id, candidate)
+);
+CREATE TABLE IF NOT EXISTS receipts (
+    contract_id TEXT NOT NULL,
+    survey_id INTEGER NOT NULL,
+    receipt TEXT NOT NULL,
+    head TEXT NOT NULL,
+    ledger INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, survey_id, receipt)
+);
+CREATE TABLE IF NOT EXISTS users (
+    contract_id TEXT NOT NULL,
+    wallet TEXT NOT NULL,
+    registered_at INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, wallet)
+);
+CREATE TABLE IF NOT EXISTS candidates (
+    contract_id TEXT NOT NULL,
+    wallet TEXT NOT NULL,
+    name TEXT NOT NULL,
+    registered_at INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, wallet)
+);
+CREATE TABLE IF NOT EXISTS candidate_profiles (
+    contract_id TEXT NOT NULL,
+    wallet TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    profile_uri TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    ledger INTEGER NOT NULL,
+    PRIMARY KEY (contract_id, wallet, version)
+);
+CREATE TABLE IF NOT EXISTS sync_state (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+";
+
+/// Base de datos SQLite reconstruida exclusivamente a partir de eventos
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Database { conn })
+    }
+
+    /// Aplica una página de eventos y guarda su cursor; retorna cuántos eventos eran nuevos
+    pub fn ingest(&mut self, page: &EventPage) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut applied = 0;
+        for raw in &page.events {
+            if let Some(event) = Event::decode(raw)? {
+                if apply(&tx, raw, &event)? {
+                    applied += 1;
+                }
+            }
+        }
+        if let Some(cursor) = &page.cursor {
+            tx.execute(
+                "INSERT OR REPLACE INTO sync_state (key, value) VALUES ('cursor', ?1)",
+                params![cursor],
+            )?;
+        }
+        tx.commit()?;
+        Ok(applied)
+    }
+
+    /// Cursor de `getEvents` desde el que continuar la sincronización
+    pub fn cursor(&self) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM sync_state WHERE key = 'cursor'", [], |row| row.get(0))
+            .optional()?)
+    }
+
+    /// Conteo por candidato en el orden de la encuesta, como `get_results`
+    pub fn results(&self, contract_id: &str, survey_id: u64) -> Result<Vec<VoteResult>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.candidate, COALESCE(t.votes, 0)
+             FROM survey_candidates c
+             LEFT JOIN tallies t
+               ON t.contract_id = c.contract_id AND t.survey_id = c.survey_id AND t.candidate = c.candidate
+             WHERE c.contract_id = ?1 AND c.survey_id = ?2
+             ORDER BY c.position",
+        )?;
+        let rows = stmt.query_map(params![contract_id, survey_id as i64], |row| {
+            Ok(VoteResult { candidate: row.get(0)?, votes: row.get::<_, i64>(1)? as u64 })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Votantes directos de una encuesta, en orden de emisión
+    pub fn voters(&self, contract_id: &str, survey_id: u64) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT voter FROM votes WHERE contract_id = ?1 AND survey_id = ?2 ORDER BY ledger, rowid",
+        )?;
+        let rows = stmt.query_map(params![contract_id, survey_id as i64], |row| row.get(0))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Respuestas a un cuestionario: votante y opción elegida en cada pregunta
+    /// (`None` si la encuesta ocultaba sus resultados)
+    pub fn responses(&self, contract_id: &str, survey_id: u64) -> Result<Vec<(String, Option<Vec<u32>>)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT voter, answers FROM responses WHERE contract_id = ?1 AND survey_id = ?2 ORDER BY ledger, rowid",
+        )?;
+        let rows = stmt.query_map(params![contract_id, survey_id as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })?;
+        rows.map(|row| {
+            let (voter, answers) = row?;
+            Ok((voter, answers.map(|answers| serde_json::from_str(&answers)).transpose()?))
+        })
+        .collect()
+    }
+
+    /// Quién canceló una encuesta, si fue cancelada
+    pub fn cancelled_by(&self, contract_id: &str, survey_id: u64) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT cancelled_by FROM cancellations WHERE contract_id = ?1 AND survey_id = ?2",
+                params![contract_id, survey_id as i64],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Fees devueltos en una encuesta cancelada: votante, token y monto
+    pub fn refunds(&self, contract_id: &str, survey_id: u64) -> Result<Vec<(String, String, i128)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT voter, token, amount FROM refunds WHERE contract_id = ?1 AND survey_id = ?2 ORDER BY ledger, rowid",
+        )?;
+        let rows = stmt.query_map(params![contract_id, survey_id as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?;
+        rows.map(|row| {
+            let (voter, token, amount) = row?;
+            Ok((voter, token, amount.parse()?))
+        })
+        .collect()
+    }
+
+    /// Recibos de voto de una encuesta en el orden de la cadena, con la cabeza tras cada uno
+    pub fn receipts(&self, contract_id: &str, survey_id: u64) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT receipt, head FROM receipts WHERE contract_id = ?1 AND survey_id = ?2 ORDER BY ledger, rowid",
+        )?;
+        let rows = stmt.query_map(params![contract_id, survey_id as i64], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Número de usuarios registrados en un UserRegistry
+    pub fn user_count(&self, contract_id: &str) -> Result<u64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM users WHERE contract_id = ?1",
+            params![contract_id],
+            |row| row.get(0),
+        )?;
+        Ok(count as u64)
+    }
+
+    /// Versiones del perfil de un candidato, de la más antigua a la vigente
+    pub fn profile_history(&self, contract_id: &str, wallet: &str) -> Result<Vec<ProfileVersion>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT version, profile_uri, content_hash, ledger FROM candidate_profiles
+             WHERE contract_id = ?1 AND wallet = ?2 ORDER BY version",
+        )?;
+        let rows = stmt.query_map(params![contract_id, wallet], |row| {
+            Ok(ProfileVersion {
+                version: row.get(0)?,
+                profile_uri: row.get(1)?,
+                content_hash: row.get(2)?,
+                ledger: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Diferencias entre el conteo indexado y un snapshot de `get_results`
+    pub fn compare(&self, contract_id: &str, survey_id: u64, snapshot: &[VoteResult]) -> Result<Vec<String>> {
+        let indexed = self.results(contract_id, survey_id)?;
+        let mut mismatches = Vec::new();
+        for expected in snapshot {
+            match indexed.iter().find(|result| result.candidate == expected.candidate) {
+                Some(found) if found.votes == expected.votes => {}
+                Some(found) => mismatches.push(format!(
+                    "{}: chain has {} votes, index has {}",
+                    expected.candidate, expected.votes, found.votes
+                )),
+                None => mismatches.push(format!("{}: missing from index", expected.candidate)),
+            }
+        }
+        for found in &indexed {
+            if !snapshot.iter().any(|expected| expected.candidate == found.candidate) {
+                mismatches.push(format!("{}: not in chain results", found.candidate));
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+// Aplica un evento una sola vez; retorna false si ya se había indexado
+fn apply(tx: &Transaction, raw: &RpcEvent, event: &Event) -> Result<bool> {
+    let inserted = tx.execute(
+        "INSERT OR IGNORE INTO events (id, ledger, contract_id) VALUES (?1, ?2, ?3)",
+        params![raw.id, raw.ledger, raw.contract_id],
+    )?;
+    if inserted == 0 {
+        return Ok(false);
+    }
+
+    let contract = &raw.contract_id;
+    match event {
+        Event::SurveyCreated { survey_id, creator, name, start_date, end_date, candidates } => {
+            tx.execute(
+                "INSERT OR REPLACE INTO surveys
+                 (contract_id, survey_id, creator, name, start_date, end_date, created_ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    contract,
+                    *survey_id as i64,
+                    creator,
+                    name,
+                    *start_date as i64,
+                    *end_date as i64,
+                    raw.ledger
+                ],
+            )?;
+            for (position, candidate) in candidates.iter().enumerate() {
+                tx.execute(
+                    "INSERT OR REPLACE INTO survey_candidates (contract_id, survey_id, position, candidate)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![contract, *survey_id as i64, position as i64, candidate],
+                )?;
+            }
+        }
+        Event::VoteCast { survey_id, voter, candidate } => {
+            tx.execute(
+                "INSERT OR REPLACE INTO votes (contract_id, survey_id, voter, candidate, ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![contract, *survey_id as i64, voter, candidate, raw.ledger],
+            )?;
+        }
+        Event::ResponseSubmitted { survey_id, voter, answers } => {
+            tx.execute(
+                "INSERT OR REPLACE INTO responses (contract_id, survey_id, voter, answers, ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    contract,
+                    *survey_id as i64,
+                    voter,
+                    answers.as_ref().map(serde_json::to_string).transpose()?,
+                    raw.ledger
+                ],
+            )?;
+        }
+        Event::TallyChanged { survey_id, candidate, delta } => {
+            tx.execute(
+                "INSERT INTO tallies (contract_id, survey_id, candidate, votes) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (contract_id, survey_id, candidate) DO UPDATE SET votes = votes + ?4",
+                params![contract, *survey_id as i64, candidate, delta],
+            )?;
+        }
+        Event::SurveyFinalized { survey_id, total_votes } => {
+            tx.execute(
+                "UPDATE surveys SET finalized_total = ?3 WHERE contract_id = ?1 AND survey_id = ?2",
+                params![contract, *survey_id as i64, *total_votes as i64],
+            )?;
+        }
+        Event::SurveyCancelled { survey_id, cancelled_by } => {
+            tx.execute(
+                "INSERT OR REPLACE INTO cancellations (contract_id, survey_id, cancelled_by, ledger)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![contract, *survey_id as i64, cancelled_by, raw.ledger],
+            )?;
+        }
+        Event::FeeRefunded { survey_id, voter, token, amount } => {
+            // SQLite no tiene enteros de 128 bits; el monto se guarda como texto
+            tx.execute(
+                "INSERT OR REPLACE INTO refunds (contract_id, survey_id, voter, token, amount, ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![contract, *survey_id as i64, voter, token, amount.to_string(), raw.ledger],
+            )?;
+        }
+        Event::ReceiptIssued { survey_id, receipt, head } => {
+            tx.execute(
+                "INSERT OR REPLACE INTO receipts (contract_id, survey_id, receipt, head, ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![contract, *survey_id as i64, receipt, head, raw.ledger],
+            )?;
+        }
+        Event::UserRegistered { wallet, timestamp } => {
+            tx.execute(
+                "INSERT OR REPLACE INTO users (contract_id, wallet, registered_at) VALUES (?1, ?2, ?3)",
+                params![contract, wallet, *timestamp as i64],
+            )?;
+        }
+        Event::CandidateRegistered { wallet, name, timestamp } => {
+            tx.execute(
+                "INSERT OR REPLACE INTO candidates (contract_id, wallet, name, registered_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![contract, wallet, name, *timestamp as i64],
+            )?;
+        }
+        Event::ProfileUpdated { wallet, version, profile_uri, content_hash } => {
+            tx.execute(
+                "INSERT OR REPLACE INTO candidate_profiles
+                 (contract_id, wallet, version, profile_uri, content_hash, ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![contract, wallet, version, profile_uri, content_hash, raw.ledger],
+            )?;
+        }
+    }
+    Ok(true)
+}
@@ -0,0 +1,254 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use stellar_xdr::curr::{Limits, ReadXdr, ScAddress, ScMap, ScVal};
+
+/// Evento tal como lo entrega `getEvents` de Stellar RPC (topics y value en XDR base64)
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEvent {
+    pub id: String,
+    pub ledger: u32,
+    pub contract_id: String,
+    pub topic: Vec<String>,
+    pub value: String,
+}
+
+/// Página de resultados de `getEvents`; también es el formato de los fixtures grabados
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPage {
+    pub events: Vec<RpcEvent>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub latest_ledger: u32,
+}
+
+/// Eventos de los contratos de DappCenvote que el indexador entiende
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    SurveyCreated {
+        survey_id: u64,
+        creator: String,
+        name: String,
+        start_date: u64,
+        end_date: u64,
+        candidates: Vec<String>,
+    },
+    /// `candidate` es `None` mientras la encuesta oculta sus resultados
+    VoteCast { survey_id: u64, voter: String, candidate: Option<String> },
+    ResponseSubmitted { survey_id: u64, voter: String, answers: Option<Vec<u32>> },
+    TallyChanged { survey_id: u64, candidate: String, delta: i64 },
+    SurveyFinalized { survey_id: u64, total_votes: u64 },
+    SurveyCancelled { survey_id: u64, cancelled_by: String },
+    FeeRefunded { survey_id: u64, voter: String, token: String, amount: i128 },
+    ReceiptIssued { survey_id: u64, receipt: String, head: String },
+    UserRegistered { wallet: String, timestamp: u64 },
+    CandidateRegistered { wallet: String, name: String, timestamp: u64 },
+    ProfileUpdated {
+        wallet: String,
+        version: u32,
+        profile_uri: String,
+        content_hash: String,
+    },
+}
+
+impl Event {
+    /// Decodifica un evento de RPC; retorna `None` si no es de DappCenvote
+    pub fn decode(raw: &RpcEvent) -> Result<Option<Event>> {
+        let topics = raw
+            .topic
+            .iter()
+            .map(|topic| ScVal::from_xdr_base64(topic, Limits::none()))
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("invalid topic XDR in event {}", raw.id))?;
+        let Some(ScVal::Symbol(name)) = topics.first() else {
+            return Ok(None);
+        };
+        let name = String::from_utf8(name.to_vec())?;
+        let value = ScVal::from_xdr_base64(&raw.value, Limits::none())
+            .with_context(|| format!("invalid value XDR in event {}", raw.id))?;
+        let data = Data::new(&value);
+        let topic = |index: usize| {
+            topics.get(index).ok_or_else(|| anyhow!("event {} is missing topic {index}", raw.id))
+        };
+
+        let event = match name.as_str() {
+            "survey_created" => Event::SurveyCreated {
+                survey_id: u64_of(topic(1)?)?,
+                creator: data.address("creator")?,
+                name: data.string("name")?,
+                start_date: data.u64("start_date")?,
+                end_date: data.u64("end_date")?,
+                candidates: data.addresses("candidates")?,
+            },
+            "vote_cast" => Event::VoteCast {
+                survey_id: u64_of(topic(1)?)?,
+                voter: data.address("voter")?,
+                candidate: data.optional_address("candidate")?,
+            },
+            "response_submitted" => Event::ResponseSubmitted {
+                survey_id: u64_of(topic(1)?)?,
+                voter: data.address("voter")?,
+                answers: data.optional_u32s("answers")?,
+            },
+            "tally_changed" => Event::TallyChanged {
+                survey_id: u64_of(topic(1)?)?,
+                candidate: data.address("candidate")?,
+                delta: data.i64("delta")?,
+            },
+            "survey_finalized" => Event::SurveyFinalized {
+                survey_id: u64_of(topic(1)?)?,
+                total_votes: data.u64("total_votes")?,
+            },
+            "survey_cancelled" => Event::SurveyCancelled {
+                survey_id: u64_of(topic(1)?)?,
+                cancelled_by: data.address("cancelled_by")?,
+            },
+            "fee_refunded" => Event::FeeRefunded {
+                survey_id: u64_of(topic(1)?)?,
+                voter: data.address("voter")?,
+                token: data.address("token")?,
+                amount: data.i128("amount")?,
+            },
+            "receipt_issued" => Event::ReceiptIssued {
+                survey_id: u64_of(topic(1)?)?,
+                receipt: data.hex("receipt")?,
+                head: data.hex("head")?,
+            },
+            "user_registered" => Event::UserRegistered {
+                wallet: address_of(topic(1)?)?,
+                timestamp: data.u64("timestamp")?,
+            },
+            "candidate_registered" => Event::CandidateRegistered {
+                wallet: address_of(topic(1)?)?,
+                name: data.string("name")?,
+                timestamp: data.u64("timestamp")?,
+            },
+            "profile_updated" => Event::ProfileUpdated {
+                wallet: address_of(topic(1)?)?,
+                version: data.u32("version")?,
+                profile_uri: data.string("profile_uri")?,
+                content_hash: data.hex("content_hash")?,
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+// Campos del mapa de datos que publica `#[contractevent]`
+struct Data<'a>(Option<&'a ScMap>);
+
+impl<'a> Data<'a> {
+    fn new(value: &'a ScVal) -> Self {
+        match value {
+            ScVal::Map(Some(map)) => Data(Some(map)),
+            _ => Data(None),
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<&'a ScVal> {
+        self.0
+            .into_iter()
+            .flat_map(|map| map.iter())
+            .find(|entry| matches!(&entry.key, ScVal::Symbol(key) if key.as_slice() == name.as_bytes()))
+            .map(|entry| &entry.val)
+            .ok_or_else(|| anyhow!("event data is missing `{name}`"))
+    }
+
+    fn u64(&self, name: &str) -> Result<u64> {
+        u64_of(self.field(name)?)
+    }
+
+    fn u32(&self, name: &str) -> Result<u32> {
+        match self.field(name)? {
+            ScVal::U32(value) => Ok(*value),
+            other => bail!("`{name}` is not a u32: {other:?}"),
+        }
+    }
+
+    fn i64(&self, name: &str) -> Result<i64> {
+        match self.field(name)? {
+            ScVal::I64(value) => Ok(*value),
+            other => bail!("`{name}` is not an i64: {other:?}"),
+        }
+    }
+
+    fn i128(&self, name: &str) -> Result<i128> {
+        match self.field(name)? {
+            ScVal::I128(parts) => Ok(((parts.hi as i128) << 64) | parts.lo as i128),
+            other => bail!("`{name}` is not an i128: {other:?}"),
+        }
+    }
+
+    fn string(&self, name: &str) -> Result<String> {
+        match self.field(name)? {
+            ScVal::String(value) => Ok(String::from_utf8(value.to_vec())?),
+            other => bail!("`{name}` is not a string: {other:?}"),
+        }
+    }
+
+    fn hex(&self, name: &str) -> Result<String> {
+        match self.field(name)? {
+            ScVal::Bytes(value) => Ok(value.iter().map(|byte| format!("{byte:02x}")).collect()),
+            other => bail!("`{name}` is not bytes: {other:?}"),
+        }
+    }
+
+    fn address(&self, name: &str) -> Result<String> {
+        address_of(self.field(name)?)
+    }
+
+    fn optional_address(&self, name: &str) -> Result<Option<String>> {
+        match self.field(name)? {
+            ScVal::Void => Ok(None),
+            value => address_of(value).map(Some),
+        }
+    }
+
+    fn optional_u32s(&self, name: &str) -> Result<Option<Vec<u32>>> {
+        match self.field(name)? {
+            ScVal::Void => Ok(None),
+            _ => self.u32s(name).map(Some),
+        }
+    }
+
+    fn u32s(&self, name: &str) -> Result<Vec<u32>> {
+        match self.field(name)? {
+            ScVal::Vec(Some(items)) => items
+                .iter()
+                .map(|item| match item {
+                    ScVal::U32(value) => Ok(*value),
+                    other => bail!("`{name}` has a non-u32 item: {other:?}"),
+                })
+                .collect(),
+            ScVal::Vec(None) => Ok(Vec::new()),
+            other => bail!("`{name}` is not a vec: {other:?}"),
+        }
+    }
+
+    fn addresses(&self, name: &str) -> Result<Vec<String>> {
+        match self.field(name)? {
+            ScVal::Vec(Some(items)) => items.iter().map(address_of).collect(),
+            ScVal::Vec(None) => Ok(Vec::new()),
+            other => bail!("`{name}` is not a vec: {other:?}"),
+        }
+    }
+}
+
+fn u64_of(value: &ScVal) -> Result<u64> {
+    match value {
+        ScVal::U64(value) => Ok(*value),
+        other => bail!("expected u64, found {other:?}"),
+    }
+}
+
+fn address_of(value: &ScVal) -> Result<String> {
+    match value {
+        ScVal::Address(address @ (ScAddress::Account(_) | ScAddress::Contract(_))) => {
+            Ok(address.to_string())
+        }
+        other => bail!("expected address, found {other:?}"),
+    }
+}
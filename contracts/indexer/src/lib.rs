@@ -0,0 +1,318 @@
+//! Indexador de eventos de DappCenvote: consume los eventos de los contratos
+//! (desde `getEvents` de Stellar RPC o desde un fixture JSON grabado) y
+//! reconstruye encuestas, votos, conteos y registros en SQLite.
+//!
+//! La base solo se alimenta de eventos de la cadena, por lo que cualquier
+//! discrepancia con `get_results` se detecta con [`Database::compare`].
+pub mod db;
+pub mod event;
+pub mod rpc;
+
+use serde::{Deserialize, Serialize};
+
+pub use db::Database;
+pub use event::{Event, EventPage, RpcEvent};
+pub use rpc::RpcClient;
+
+/// Resultado por candidato, con el mismo formato JSON que `get_results`
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct VoteResult {
+    pub candidate: String,
+    pub votes: u64,
+}
+
+/// Versión indexada del perfil de un candidato; content_hash en hexadecimal
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ProfileVersion {
+    pub version: u32,
+    pub profile_uri: String,
+    pub content_hash: String,
+    pub ledger: u32,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use candidate_registry::CandidateRegistry;
+    use dappcenvote_types::candidate_registry::CandidateRegistryClient;
+    use dappcenvote_types::survey::{DelegationScope, Question, ResultsVisibility, SurveyClient};
+    use dappcenvote_types::user_registry::UserRegistryClient;
+    use soroban_sdk::testutils::{Address as _, Events as _, Ledger};
+    use soroban_sdk::xdr::{Limits, ScAddress, ScVal, WriteXdr};
+    use soroban_sdk::token::StellarAssetClient;
+    use soroban_sdk::{vec, Address, BytesN, Env, String, TryFromVal};
+    use survey::SurveyContract;
+    use user_registry::UserRegistry;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/events.json");
+    const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/results.json");
+
+    fn strkey(address: &Address) -> std::string::String {
+        ScAddress::from(address).to_string()
+    }
+
+    // Guarda como eventos de RPC los publicados por la última invocación
+    fn record(env: &Env, events: &mut Vec<RpcEvent>) {
+        for (contract, topics, data) in env.events().all().iter() {
+            let base64 = |val| {
+                ScVal::try_from_val(env, &val).unwrap().to_xdr_base64(Limits::none()).unwrap()
+            };
+            events.push(RpcEvent {
+                id: format!("{:019}-{:010}", env.ledger().sequence(), events.len()),
+                ledger: env.ledger().sequence(),
+                contract_id: strkey(&contract),
+                topic: topics.iter().map(base64).collect(),
+                value: base64(data),
+            });
+        }
+    }
+
+    // Elección completa con delegación; retorna los eventos, el id del contrato
+    // de encuestas y el resultado de `get_results`
+    fn run_election() -> (EventPage, std::string::String, Vec<VoteResult>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let mut events = Vec::new();
+
+        let admin = Address::generate(&env);
+        let users = UserRegistryClient::new(&env, &env.register(UserRegistry, (admin.clone(),)));
+        let candidates = CandidateRegistryClient::new(&env, &env.register(CandidateRegistry, (admin.clone(),)));
+        let surveys = SurveyClient::new(&env, &env.register(SurveyContract, (admin.clone(),)));
+        surveys.set_registries(&users.address, &candidates.address);
+
+        let text = |value: &str| String::from_str(&env, value);
+        let mut candidate_list = soroban_sdk::Vec::new(&env);
+        for (name, rfc) in [("Ana Torres", "TOAA800101ABC"), ("Beto Ruiz", "RUBB750505XYZ")] {
+            let wallet = Address::generate(&env);
+            candidates.register_candidate(&wallet, &text(name), &text(rfc));
+            record(&env, &mut events);
+            let uri = text("ipfs://bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+            let hash = BytesN::from_array(&env, &[1; 32]);
+            candidates.update_profile(&wallet, &uri, &hash);
+            record(&env, &mut events);
+            candidate_list.push_back(wallet);
+        }
+        let mut voters = Vec::new();
+        for name in ["Carla", "Diego", "Elena", "Fernanda"] {
+            let wallet = Address::generate(&env);
+            let (phone, email) = (text("5551234567"), text("votante@example.com"));
+            users.register_user(&wallet, &text(name), &text("Pérez"), &text("García"), &phone, &email);
+            record(&env, &mut events);
+            voters.push(wallet);
+        }
+
+        // Carla delega en Elena; Diego también, pero luego vota directamente
+        surveys.delegate(&voters[0], &voters[2], &DelegationScope::Global);
+        surveys.delegate(&voters[1], &voters[2], &DelegationScope::Global);
+        let (name, description) = (text("Elección"), text("Indexador"));
+        let survey_id =
+            surveys.create_survey(&voters[3], &name, &description, &1000, &2000, &candidate_list);
+        record(&env, &mut events);
+
+        env.ledger().set_timestamp(1500);
+        env.ledger().set_sequence_number(10);
+        let ana = candidate_list.get(0).unwrap();
+        let beto = candidate_list.get(1).unwrap();
+        for (voter, candidate) in [(&voters[2], &beto), (&voters[1], &ana), (&voters[3], &ana)] {
+            surveys.vote(&survey_id, voter, candidate, &None);
+            record(&env, &mut events);
+        }
+
+        env.ledger().set_timestamp(2001);
+        surveys.finalize_survey(&survey_id);
+        record(&env, &mut events);
+
+        let results = surveys
+            .get_results(&survey_id)
+            .iter()
+            .map(|result| VoteResult { candidate: strkey(&result.candidate), votes: result.votes })
+            .collect();
+        let cursor = Some("0000000000000000010-0000000099".into());
+        let page = EventPage { events, cursor, latest_ledger: 10 };
+        (page, strkey(&surveys.address), results)
+    }
+
+    #[test]
+    fn test_index_matches_get_results() {
+        let (page, survey_contract, results) = run_election();
+        let mut db = Database::in_memory().unwrap();
+        db.ingest(&page).unwrap();
+
+        assert_eq!(db.results(&survey_contract, 1).unwrap(), results);
+        assert_eq!(results.iter().map(|r| r.votes).collect::<Vec<_>>(), [2, 2]);
+        assert!(db.compare(&survey_contract, 1, &results).unwrap().is_empty());
+        assert_eq!(db.voters(&survey_contract, 1).unwrap().len(), 3);
+        let receipts = db.receipts(&survey_contract, 1).unwrap();
+        assert_eq!(receipts.len(), 3);
+        assert!(receipts.iter().all(|(receipt, head)| receipt.len() == 64 && receipt != head));
+        assert_eq!(db.cursor().unwrap().as_deref(), Some("0000000000000000010-0000000099"));
+    }
+
+    #[test]
+    fn test_ingest_is_idempotent() {
+        let (page, survey_contract, results) = run_election();
+        let mut db = Database::in_memory().unwrap();
+        assert_eq!(db.ingest(&page).unwrap(), page.events.len());
+        assert_eq!(db.ingest(&page).unwrap(), 0);
+        assert_eq!(db.results(&survey_contract, 1).unwrap(), results);
+    }
+
+    #[test]
+    fn test_compare_reports_mismatch() {
+        let (page, survey_contract, mut results) = run_election();
+        let mut db = Database::in_memory().unwrap();
+        db.ingest(&page).unwrap();
+
+        results[0].votes += 1;
+        let mismatches = db.compare(&survey_contract, 1, &results).unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].contains("chain has 3 votes, index has 2"));
+    }
+
+    #[test]
+    fn test_replay_recorded_fixture() {
+        let read = |path| std::fs::read_to_string(path).unwrap();
+        let page: EventPage = serde_json::from_str(&read(FIXTURE)).unwrap();
+        let snapshot: Vec<VoteResult> = serde_json::from_str(&read(SNAPSHOT)).unwrap();
+        let mut db = Database::in_memory().unwrap();
+        db.ingest(&page).unwrap();
+
+        let contract_of = |matches: fn(&Event) -> bool| {
+            page.events
+                .iter()
+                .find(|raw| Event::decode(raw).unwrap().as_ref().is_some_and(matches))
+                .map(|raw| raw.contract_id.clone())
+                .unwrap()
+        };
+        let survey_contract = contract_of(|event| matches!(event, Event::SurveyCreated { .. }));
+        let registry = contract_of(|event| matches!(event, Event::UserRegistered { .. }));
+
+        assert!(db.compare(&survey_contract, 1, &snapshot).unwrap().is_empty());
+        assert_eq!(db.user_count(&registry).unwrap(), 4);
+
+        let candidate_registry = contract_of(|event| matches!(event, Event::CandidateRegistered { .. }));
+        let wallet = page
+            .events
+            .iter()
+            .find_map(|raw| match Event::decode(raw).unwrap() {
+                Some(Event::ProfileUpdated { wallet, .. }) => Some(wallet),
+                _ => None,
+            })
+            .unwrap();
+        let history = db.profile_history(&candidate_registry, &wallet).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].profile_uri, "ipfs://bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+    }
+
+    #[test]
+    fn test_index_responses_cancellations_and_refunds() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let mut events = Vec::new();
+
+        let admin = Address::generate(&env);
+        let surveys = SurveyClient::new(&env, &env.register(SurveyContract, (admin.clone(),)));
+        let text = |value: &str| String::from_str(&env, value);
+        let (creator, voter) = (Address::generate(&env), Address::generate(&env));
+
+        // Cuestionario respondido
+        let options = vec![&env, text("Metro"), text("Bici")];
+        let question = Question { prompt: text("¿Cómo llegas al trabajo?"), options };
+        let questionnaire = surveys.create_questionnaire(
+            &creator,
+            &text("Transporte"),
+            &text("Indexador"),
+            &1000,
+            &2000,
+            &vec![&env, question],
+        );
+        // Encuesta con fee que se cancela y reembolsa
+        let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        StellarAssetClient::new(&env, &token).mint(&voter, &1000);
+        let candidates = vec![&env, Address::generate(&env)];
+        let (name, description) = (text("Con fee"), text("Indexador"));
+        let survey_id = surveys.create_survey(&creator, &name, &description, &1000, &2000, &candidates);
+        surveys.set_survey_fee(&survey_id, &token, &250);
+        record(&env, &mut events);
+
+        env.ledger().set_timestamp(1500);
+        surveys.submit_response(&questionnaire, &voter, &vec![&env, 1u32], &None);
+        record(&env, &mut events);
+        surveys.vote(&survey_id, &voter, &candidates.get(0).unwrap(), &None);
+        record(&env, &mut events);
+        surveys.cancel_survey(&survey_id, &creator);
+        record(&env, &mut events);
+        surveys.claim_refund(&survey_id, &voter);
+        record(&env, &mut events);
+
+        let mut db = Database::in_memory().unwrap();
+        db.ingest(&EventPage { events, cursor: None, latest_ledger: 0 }).unwrap();
+        let contract = strkey(&surveys.address);
+        assert_eq!(db.responses(&contract, questionnaire).unwrap(), [(strkey(&voter), Some(std::vec![1]))]);
+        assert_eq!(db.cancelled_by(&contract, survey_id).unwrap(), Some(strkey(&creator)));
+        assert_eq!(db.cancelled_by(&contract, questionnaire).unwrap(), None);
+        assert_eq!(db.refunds(&contract, survey_id).unwrap(), [(strkey(&voter), strkey(&token), 250)]);
+    }
+
+    #[test]
+    fn test_hidden_results_indexed_at_finalize() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let mut events = Vec::new();
+
+        let admin = Address::generate(&env);
+        let surveys = SurveyClient::new(&env, &env.register(SurveyContract, (admin.clone(),)));
+        let text = |value: &str| String::from_str(&env, value);
+        let creator = Address::generate(&env);
+        let candidates = vec![&env, Address::generate(&env), Address::generate(&env)];
+        let (name, description) = (text("Secreta"), text("Indexador"));
+        let survey_id = surveys.create_survey(&creator, &name, &description, &1000, &2000, &candidates);
+        record(&env, &mut events);
+        surveys.set_results_visibility(&survey_id, &ResultsVisibility::AfterFinalize, &false);
+        let question = Question { prompt: text("¿Secreta?"), options: vec![&env, text("Sí"), text("No")] };
+        let questionnaire =
+            surveys.create_questionnaire(&creator, &name, &description, &1000, &2000, &vec![&env, question]);
+        surveys.set_results_visibility(&questionnaire, &ResultsVisibility::AfterClose, &false);
+
+        env.ledger().set_timestamp(1500);
+        for choice in [1, 1, 0] {
+            surveys.vote(&survey_id, &Address::generate(&env), &candidates.get(choice).unwrap(), &None);
+            record(&env, &mut events);
+        }
+        let respondent = Address::generate(&env);
+        surveys.submit_response(&questionnaire, &respondent, &vec![&env, 0u32], &None);
+        record(&env, &mut events);
+
+        // Durante la votación los eventos no revelan candidatos ni conteos
+        let mut db = Database::in_memory().unwrap();
+        db.ingest(&EventPage { events: events.clone(), cursor: None, latest_ledger: 0 }).unwrap();
+        let contract = strkey(&surveys.address);
+        assert_eq!(db.voters(&contract, survey_id).unwrap().len(), 3);
+        assert!(db.results(&contract, survey_id).unwrap().iter().all(|result| result.votes == 0));
+        assert!(events.iter().all(|raw| !matches!(
+            Event::decode(raw).unwrap(),
+            Some(
+                Event::VoteCast { candidate: Some(_), .. }
+                    | Event::ResponseSubmitted { answers: Some(_), .. }
+                    | Event::TallyChanged { .. }
+            )
+        )));
+        assert_eq!(db.responses(&contract, questionnaire).unwrap(), [(strkey(&respondent), None)]);
+
+        env.ledger().set_timestamp(2001);
+        surveys.finalize_survey(&survey_id);
+        record(&env, &mut events);
+        db.ingest(&EventPage { events, cursor: None, latest_ledger: 0 }).unwrap();
+        let votes: Vec<u64> = db.results(&contract, survey_id).unwrap().iter().map(|result| result.votes).collect();
+        assert_eq!(votes, [1, 2]);
+    }
+
+    // Regenera testdata/: cargo test -p dappcenvote-indexer -- --ignored record_fixture
+    #[test]
+    #[ignore]
+    fn record_fixture() {
+        let (page, _, results) = run_election();
+        std::fs::write(FIXTURE, serde_json::to_string_pretty(&page).unwrap() + "\n").unwrap();
+        std::fs::write(SNAPSHOT, serde_json::to_string_pretty(&results).unwrap() + "\n").unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+
+use dappcenvote_indexer::{Database, EventPage, RpcClient, VoteResult};
+
+const PAGE_LIMIT: u32 = 200;
+
+#[derive(Parser)]
+#[command(name = "dappcenvote-indexer", version, about = "Index DappCenvote contract events into SQLite")]
+struct Cli {
+    /// SQLite database to build
+    #[arg(long, global = true, default_value = "dappcenvote.db")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Fetch events from Stellar RPC, resuming from the stored cursor
+    Sync {
+        #[arg(long, env = "STELLAR_RPC_URL", default_value = "https://soroban-testnet.stellar.org")]
+        rpc_url: String,
+        /// Contract ids to index (survey, user_registry, candidate_registry)
+        #[arg(long = "contract", required = true)]
+        contracts: Vec<String>,
+        /// First ledger to read when the database has no cursor yet; defaults to
+        /// the oldest ledger the RPC node still retains (about 7 days on public RPC)
+        #[arg(long)]
+        start_ledger: Option<u32>,
+        /// Keep polling for new events
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Index a recorded getEvents result instead of querying RPC
+    Replay { fixture: PathBuf },
+    /// Print the indexed results of a survey as JSON
+    Results {
+        #[arg(long)]
+        contract: String,
+        #[arg(long)]
+        survey_id: u64,
+    },
+    /// Compare the indexed results with a `get_results` snapshot
+    Check {
+        #[arg(long)]
+        contract: String,
+        #[arg(long)]
+        survey_id: u64,
+        /// JSON output of `stellar contract invoke -- get_results`
+        snapshot: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut db = Database::open(&cli.db)?;
+
+    match cli.command {
+        Commands::Sync { rpc_url, contracts, start_ledger, follow } => {
+            let rpc = RpcClient::new(rpc_url);
+            let start_ledger = match start_ledger {
+                Some(ledger) => ledger,
+                None if db.cursor()?.is_some() => 0, // Se continúa desde el cursor
+                None => {
+                    let oldest = rpc.oldest_ledger()?;
+                    println!("no --start-ledger given; starting at the oldest retained ledger {oldest}");
+                    oldest
+                }
+            };
+            loop {
+                let cursor = db.cursor()?;
+                let page = rpc.get_events(&contracts, start_ledger, cursor.as_deref(), PAGE_LIMIT)?;
+                let applied = db.ingest(&page)?;
+                println!("indexed {applied} events (latest ledger {})", page.latest_ledger);
+                if page.events.len() < PAGE_LIMIT as usize {
+                    if !follow {
+                        break;
+                    }
+                    thread::sleep(Duration::from_secs(5));
+                }
+            }
+        }
+        Commands::Replay { fixture } => {
+            let text = fs::read_to_string(&fixture)
+                .with_context(|| format!("failed to read {}", fixture.display()))?;
+            let page: EventPage = serde_json::from_str(&text)?;
+            println!("indexed {} events", db.ingest(&page)?);
+        }
+        Commands::Results { contract, survey_id } => {
+            let results = db.results(&contract, survey_id)?;
+            println!("{}", serde_json::to_string_pretty(&results)?);
+        }
+        Commands::Check { contract, survey_id, snapshot } => {
+            let text = fs::read_to_string(&snapshot)
+                .with_context(|| format!("failed to read {}", snapshot.display()))?;
+            let snapshot: Vec<VoteResult> = serde_json::from_str(&text)?;
+            let mismatches = db.compare(&contract, survey_id, &snapshot)?;
+            if !mismatches.is_empty() {
+                for mismatch in &mismatches {
+                    eprintln!("{mismatch}");
+                }
+                bail!("index disagrees with chain on {} candidates", mismatches.len());
+            }
+            println!("index matches chain results for survey {survey_id}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+
+use crate::event::EventPage;
+
+/// Cliente mínimo de Stellar RPC para `getEvents`
+pub struct RpcClient {
+    url: String,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        RpcClient { url: url.into() }
+    }
+
+    /// Pide una página de eventos de los contratos indicados. La primera
+    /// llamada parte de `start_ledger`; las siguientes continúan desde `cursor`.
+    pub fn get_events(
+        &self,
+        contract_ids: &[String],
+        start_ledger: u32,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<EventPage> {
+        let mut params = json!({
+            "filters": [{ "type": "contract", "contractIds": contract_ids }],
+            "pagination": { "limit": limit },
+        });
+        match cursor {
+            Some(cursor) => params["pagination"]["cursor"] = json!(cursor),
+            None => params["startLedger"] = json!(start_ledger),
+        }
+
+        let result = self.call("getEvents", params)?;
+        serde_json::from_value(result).context("unexpected getEvents result")
+    }
+
+    /// Ledger más antiguo que el nodo aún conserva (`getHealth`); los eventos
+    /// anteriores a la ventana de retención ya no se pueden pedir
+    pub fn oldest_ledger(&self) -> Result<u32> {
+        let result = self.call("getHealth", json!({}))?;
+        let oldest = result["oldestLedger"].as_u64().context("getHealth did not return oldestLedger")?;
+        Ok(oldest as u32)
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = ureq::post(&self.url)
+            .send_json(request)
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            bail!("{method} returned an error: {error}");
+        }
+        Ok(response["result"].clone())
+    }
+}
@@ -0,0 +1,106 @@
+use candidate_registry::CandidateRegistry;
+use dappcenvote_types::candidate_registry::CandidateRegistryClient;
+use dappcenvote_types::governance::GovernanceClient;
+use dappcenvote_types::survey::SurveyClient;
+use dappcenvote_types::user_registry::UserRegistryClient;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use governance::Governance;
+use soroban_sdk::{Address, Env, String, Vec};
+use survey::SurveyContract;
+use user_registry::UserRegistry;
+
+// Los contratos desplegados con el mismo administrador y vinculados entre sí
+pub struct Election<'a> {
+    pub env: Env,
+    pub admin: Address,
+    pub users: UserRegistryClient<'a>,
+    pub candidates: CandidateRegistryClient<'a>,
+    pub surveys: SurveyClient<'a>,
+    pub governance: GovernanceClient<'a>,
+}
+
+impl Election<'_> {
+    /// Registra los contratos con su administrador y los vincula en un `Env` nuevo,
+    /// en el mismo orden que `dappcenvote-cli deploy`
+    pub fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(0);
+
+        let admin = Address::generate(&env);
+        let users = UserRegistryClient::new(&env, &env.register(UserRegistry, (admin.clone(),)));
+        let candidates = CandidateRegistryClient::new(&env, &env.register(CandidateRegistry, (admin.clone(),)));
+        let surveys = SurveyClient::new(&env, &env.register(SurveyContract, (admin.clone(),)));
+        let governance = GovernanceClient::new(&env, &env.register(Governance, (admin.clone(),)));
+
+        surveys.set_registries(&users.address, &candidates.address);
+
+        Election { env, admin, users, candidates, surveys, governance }
+    }
+
+    /// Registra un votante en UserRegistry y retorna su wallet
+    pub fn register_voter(&self, first_name: &str) -> Address {
+        let wallet = Address::generate(&self.env);
+        let registered = self.users.register_user(
+            &wallet,
+            &self.string(first_name),
+            &self.string("Pérez"),
+            &self.string("García"),
+            &self.string("5551234567"),
+            &self.string("votante@example.com"),
+        );
+        assert!(registered);
+        wallet
+    }
+
+    /// Registra un candidato en CandidateRegistry y retorna su wallet
+    pub fn register_candidate(&self, name: &str, rfc: &str) -> Address {
+        let wallet = Address::generate(&self.env);
+        assert!(self.candidates.register_candidate(&wallet, &self.string(name), &self.string(rfc)));
+        wallet
+    }
+
+    /// Crea una encuesta con todos los candidatos registrados
+    pub fn create_survey(&self, creator: &Address, start_date: u64, end_date: u64) -> u64 {
+        self.surveys.create_survey(
+            creator,
+            &self.string("Elección"),
+            &self.string("Escenario de prueba"),
+            &start_date,
+            &end_date,
+            &self.candidates.get_all_candidates(),
+        )
+    }
+
+    /// Vota y confirma que el recibo quedó en la cadena de la encuesta
+    pub fn vote(&self, survey_id: u64, voter: &Address, candidate: &Address) -> bool {
+        let receipt = self.surveys.vote(&survey_id, voter, candidate, &None);
+        self.surveys.verify_receipt(&survey_id, &receipt)
+    }
+
+    /// Votos obtenidos por un candidato
+    pub fn votes_for(&self, survey_id: u64, candidate: &Address) -> u64 {
+        self.surveys
+            .get_results(&survey_id)
+            .iter()
+            .find(|result| &result.candidate == candidate)
+            .map(|result| result.votes)
+            .unwrap_or(0)
+    }
+
+    pub fn set_time(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+    }
+
+    pub fn voters(&self, names: &[&str]) -> Vec<Address> {
+        let mut voters = Vec::new(&self.env);
+        for name in names {
+            voters.push_back(self.register_voter(name));
+        }
+        voters
+    }
+
+    fn string(&self, value: &str) -> String {
+        String::from_str(&self.env, value)
+    }
+}
@@ -0,0 +1,13 @@
+//! Entorno de pruebas de extremo a extremo: registra `UserRegistry`,
+//! `CandidateRegistry` y `SurveyContract` en un mismo `Env` y ofrece
+//! atajos para armar escenarios de elección completos.
+//!
+//! Solo contiene pruebas: `cargo test -p integration-tests`.
+#![cfg_attr(not(test), no_std)]
+
+#[cfg(test)]
+mod election;
+#[cfg(test)]
+mod passkey;
+#[cfg(test)]
+mod scenarios;
@@ -0,0 +1,134 @@
+use crate::election::Election;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use dappcenvote_types::passkey_account::WebAuthnSignature;
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+use passkey_account::PasskeyAccount;
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{
+    Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization, InvokeContractArgs, Limits,
+    ScAddress, ScSymbol, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry,
+    SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials, VecM, WriteXdr,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, IntoVal, String, TryFromVal, Val, Vec};
+
+const RP_ID: &str = "localhost";
+
+// Una passkey de prueba y la cuenta inteligente que la usa como firmante
+struct Passkey {
+    key: SigningKey,
+    credential_id: std::vec::Vec<u8>,
+    account: Address,
+}
+
+impl Passkey {
+    fn deploy(env: &Env) -> Self {
+        let key = SigningKey::from_slice(&[0x22; 32]).unwrap();
+        let point = key.verifying_key().to_encoded_point(false);
+        let public_key: [u8; 65] = point.as_bytes().try_into().unwrap();
+        let credential_id = b"cenvote-integration-credential".to_vec();
+        let account = env.register(
+            PasskeyAccount,
+            (
+                String::from_str(env, RP_ID),
+                Bytes::from_slice(env, &credential_id),
+                BytesN::from_array(env, &public_key),
+            ),
+        );
+        Passkey { key, credential_id, account }
+    }
+
+    /// Autoriza una invocación como lo haría el navegador con navigator.credentials.get:
+    /// el challenge es el payload de la entrada de autorización
+    fn authorize(&self, env: &Env, contract: &Address, function: &str, args: Vec<Val>, nonce: i64) {
+        let invocation = SorobanAuthorizedInvocation {
+            function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+                contract_address: ScAddress::from(contract),
+                function_name: ScSymbol(function.try_into().unwrap()),
+                args: args
+                    .iter()
+                    .map(|arg| ScVal::try_from_val(env, &arg).unwrap())
+                    .collect::<std::vec::Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+            }),
+            sub_invocations: VecM::default(),
+        };
+        let signature_expiration_ledger = env.ledger().sequence() + 100;
+        let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+            network_id: Hash(env.ledger().network_id().to_array()),
+            nonce,
+            signature_expiration_ledger,
+            invocation: invocation.clone(),
+        });
+        let payload = Sha256::digest(preimage.to_xdr(Limits::none()).unwrap());
+
+        let mut authenticator_data = Sha256::digest(RP_ID.as_bytes()).to_vec();
+        authenticator_data.extend_from_slice(&[0x05, 0, 0, 0, 1]); // UP | UV, contador 1
+        let client_data_json = format!(
+            r#"{{"type":"webauthn.get","challenge":"{}","origin":"https://{}","crossOrigin":false}}"#,
+            URL_SAFE_NO_PAD.encode(payload),
+            RP_ID,
+        );
+        let mut message = authenticator_data.clone();
+        message.extend_from_slice(&Sha256::digest(client_data_json.as_bytes()));
+        let signature: Signature = self.key.sign(&message);
+        let signature = signature.normalize_s().unwrap_or(signature);
+
+        let assertion = WebAuthnSignature {
+            credential_id: Bytes::from_slice(env, &self.credential_id),
+            authenticator_data: Bytes::from_slice(env, &authenticator_data),
+            client_data_json: Bytes::from_slice(env, client_data_json.as_bytes()),
+            signature: BytesN::from_array(env, &signature.to_bytes().into()),
+        };
+        let assertion: Val = assertion.into_val(env);
+        env.set_auths(&[SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+                address: ScAddress::from(&self.account),
+                nonce,
+                signature_expiration_ledger,
+                signature: ScVal::try_from_val(env, &assertion).unwrap(),
+            }),
+            root_invocation: invocation,
+        }]);
+    }
+}
+
+#[test]
+fn test_passkey_account_registers_and_votes() {
+    let election = Election::setup();
+    let env = &election.env;
+    let ana = election.register_candidate("Ana Torres", "TOAA800101ABC");
+    let creator = election.register_voter("Fernanda");
+    let survey_id = election.create_survey(&creator, 1000, 2000);
+    let passkey = Passkey::deploy(env);
+    let wallet = &passkey.account;
+
+    // Sin autorizaciones simuladas: solo la aserción WebAuthn autoriza a la cuenta
+    let profile = (
+        String::from_str(env, "Gabriela"),
+        String::from_str(env, "Pérez"),
+        String::from_str(env, "García"),
+        String::from_str(env, "5551234567"),
+        String::from_str(env, "gabriela@example.com"),
+    );
+    let args = (
+        wallet.clone(),
+        profile.0.clone(),
+        profile.1.clone(),
+        profile.2.clone(),
+        profile.3.clone(),
+        profile.4.clone(),
+    );
+    passkey.authorize(env, &election.users.address, "register_user", args.into_val(env), 1);
+    assert!(election.users.register_user(wallet, &profile.0, &profile.1, &profile.2, &profile.3, &profile.4));
+
+    election.set_time(1500);
+    let proof: Option<Vec<BytesN<32>>> = None;
+    let args = (survey_id, wallet.clone(), ana.clone(), proof.clone());
+    passkey.authorize(env, &election.surveys.address, "vote", args.into_val(env), 2);
+    election.surveys.vote(&survey_id, wallet, &ana, &proof);
+    assert_eq!(election.votes_for(survey_id, &ana), 1);
+
+    // La misma autorización no se puede reutilizar
+    assert!(election.surveys.try_vote(&survey_id, wallet, &ana, &proof).is_err());
+}
@@ -0,0 +1,185 @@
+use crate::election::Election;
+use dappcenvote_types::candidate_registry::CandidateRegistryError;
+use dappcenvote_types::governance::{
+    Ballot, ContractCall, GovernanceConfig, ProposalAction, ProposalStatus,
+};
+use dappcenvote_types::survey::{DelegationScope, SurveyError};
+use dappcenvote_types::PauseScope;
+use soroban_sdk::testutils::{Address as _, MockAuth, MockAuthInvoke};
+use soroban_sdk::{vec, Address, IntoVal, String, Symbol};
+
+#[test]
+fn test_full_election() {
+    let election = Election::setup();
+    let ana = election.register_candidate("Ana Torres", "TOAA800101ABC");
+    let beto = election.register_candidate("Beto Ruiz", "RUBB750505XYZ");
+    let voters = election.voters(&["Carla", "Diego", "Elena"]);
+
+    let creator = election.register_voter("Fernanda");
+    let survey_id = election.create_survey(&creator, 1000, 2000);
+    let survey = election.surveys.get_survey(&survey_id).unwrap();
+    assert_eq!(survey.candidates, election.candidates.get_all_candidates());
+
+    election.set_time(1500);
+    assert!(election.vote(survey_id, &voters.get(0).unwrap(), &ana));
+    assert!(election.vote(survey_id, &voters.get(1).unwrap(), &ana));
+    assert!(election.vote(survey_id, &voters.get(2).unwrap(), &beto));
+
+    // Una wallet sin registro en UserRegistry no puede votar
+    let outsider = Address::generate(&election.env);
+    let result = election.surveys.try_vote(&survey_id, &outsider, &beto, &None);
+    assert_eq!(result, Err(Ok(SurveyError::NotRegistered.into())));
+    assert!(!election.surveys.has_voted(&survey_id, &outsider));
+
+    assert_eq!(election.votes_for(survey_id, &ana), 2);
+    assert_eq!(election.votes_for(survey_id, &beto), 1);
+    assert_eq!(election.surveys.get_total_votes(&survey_id), 3);
+
+    election.set_time(2001);
+    election.surveys.finalize_survey(&survey_id);
+    assert!(election.surveys.is_finalized(&survey_id));
+    assert_eq!(election.users.get_user_count(), 4);
+    assert_eq!(election.candidates.get_candidate_count(), 2);
+}
+
+#[test]
+fn test_unregistered_candidate_rejected() {
+    let election = Election::setup();
+    let ana = election.register_candidate("Ana Torres", "TOAA800101ABC");
+    let creator = election.register_voter("Carla");
+
+    let mut candidates = election.candidates.get_all_candidates();
+    candidates.push_back(Address::generate(&election.env));
+    let result = election.surveys.try_create_survey(
+        &creator,
+        &String::from_str(&election.env, "Elección"),
+        &String::from_str(&election.env, "Candidato sin registro"),
+        &1000,
+        &2000,
+        &candidates,
+    );
+    assert_eq!(result, Err(Ok(SurveyError::NotRegistered.into())));
+    assert_eq!(election.surveys.get_survey_count(), 0);
+
+    let survey_id = election.create_survey(&creator, 1000, 2000);
+    assert_eq!(election.surveys.get_survey(&survey_id).unwrap().candidates.len(), 1);
+    assert!(election.candidates.candidate_exists(&ana));
+}
+
+#[test]
+fn test_emergency_pause_across_contracts() {
+    let election = Election::setup();
+    let guardian = Address::generate(&election.env);
+    election.users.set_guardian(&guardian);
+    election.candidates.set_guardian(&guardian);
+    election.surveys.set_guardian(&guardian);
+
+    let ana = election.register_candidate("Ana Torres", "TOAA800101ABC");
+    let carla = election.register_voter("Carla");
+    let survey_id = election.create_survey(&carla, 1000, 2000);
+    election.set_time(1500);
+
+    // El guardián detiene registros y votación en los tres contratos
+    election.users.pause(&guardian, &PauseScope::Registration);
+    election.candidates.pause(&guardian, &PauseScope::Registration);
+    election.surveys.pause(&guardian, &PauseScope::Voting);
+
+    let result = election.candidates.try_register_candidate(
+        &Address::generate(&election.env),
+        &String::from_str(&election.env, "Beto Ruiz"),
+        &String::from_str(&election.env, "RUBB750505XYZ"),
+    );
+    assert_eq!(result, Err(Ok(CandidateRegistryError::Paused.into())));
+
+    let result = election.surveys.try_vote(&survey_id, &carla, &ana, &None);
+    assert_eq!(result, Err(Ok(SurveyError::Paused.into())));
+
+    // El administrador también puede reanudar la operación
+    election.users.unpause(&guardian, &PauseScope::Registration);
+    election.candidates.unpause(&guardian, &PauseScope::Registration);
+    election.surveys.unpause(&election.admin, &PauseScope::Voting);
+
+    assert!(election.vote(survey_id, &carla, &ana));
+    assert_eq!(election.votes_for(survey_id, &ana), 1);
+}
+
+#[test]
+fn test_delegated_votes_between_registered_users() {
+    let election = Election::setup();
+    let ana = election.register_candidate("Ana Torres", "TOAA800101ABC");
+    let beto = election.register_candidate("Beto Ruiz", "RUBB750505XYZ");
+    let voters = election.voters(&["Carla", "Diego", "Elena"]);
+    let (carla, diego, elena) = (
+        voters.get(0).unwrap(),
+        voters.get(1).unwrap(),
+        voters.get(2).unwrap(),
+    );
+
+    // Carla y Diego delegan en Elena para todas las encuestas
+    election.surveys.delegate(&carla, &elena, &DelegationScope::Global);
+    election.surveys.delegate(&diego, &elena, &DelegationScope::Global);
+
+    let survey_id = election.create_survey(&elena, 1000, 2000);
+    election.set_time(1500);
+
+    assert!(election.vote(survey_id, &elena, &beto));
+    assert_eq!(election.votes_for(survey_id, &beto), 3);
+    assert_eq!(election.surveys.get_cast_by(&survey_id, &carla), Some(elena.clone()));
+
+    // Diego recupera su voto votando directamente
+    assert!(election.vote(survey_id, &diego, &ana));
+    assert_eq!(election.votes_for(survey_id, &beto), 2);
+    assert_eq!(election.votes_for(survey_id, &ana), 1);
+    assert_eq!(election.surveys.get_total_votes(&survey_id), 2);
+
+    // Sin registro no se puede delegar
+    let outsider = Address::generate(&election.env);
+    let result = election.surveys.try_delegate(&outsider, &elena, &DelegationScope::Global);
+    assert_eq!(result, Err(Ok(SurveyError::NotRegistered.into())));
+}
+
+#[test]
+fn test_governance_changes_vote_fee() {
+    let election = Election::setup();
+    let env = &election.env;
+    let governance = &election.governance;
+    governance.set_config(&GovernanceConfig { voting_period: 1000, timelock: 500, quorum: 2, threshold_bps: 5000 });
+    let members = [Address::generate(env), Address::generate(env), Address::generate(env)];
+    for member in &members {
+        governance.add_member(member);
+    }
+    election.surveys.set_governance(&governance.address);
+
+    let new_fee: i128 = 2_000_000;
+    let action = ProposalAction::Call(ContractCall {
+        contract: election.surveys.address.clone(),
+        function: Symbol::new(env, "set_vote_fee"),
+        args: vec![env, new_fee.into_val(env)],
+    });
+    let title = String::from_str(env, "Subir el fee de votación a 0.2 XLM");
+    let proposal_id = governance.propose(&members[0], &title, &title, &action);
+    governance.cast_ballot(&proposal_id, &members[0], &Ballot::For);
+    governance.cast_ballot(&proposal_id, &members[1], &Ballot::For);
+    governance.cast_ballot(&proposal_id, &members[2], &Ballot::Against);
+
+    // El administrador ya no puede cambiar el fee directamente
+    election.set_time(1001);
+    let admin_call = MockAuth {
+        address: &election.admin,
+        invoke: &MockAuthInvoke {
+            contract: &election.surveys.address,
+            fn_name: "set_vote_fee",
+            args: (1i128,).into_val(env),
+            sub_invokes: &[],
+        },
+    };
+    assert!(election.surveys.mock_auths(&[admin_call]).try_set_vote_fee(&1).is_err());
+
+    // Tras el timelock cualquiera ejecuta la propuesta, sin firmas adicionales
+    election.set_time(1500);
+    assert_eq!(governance.get_status(&proposal_id), ProposalStatus::Passed);
+    env.set_auths(&[]);
+    governance.execute(&proposal_id);
+    assert_eq!(election.surveys.get_vote_fee(), new_fee);
+    assert_eq!(governance.get_status(&proposal_id), ProposalStatus::Executed);
+}
@@ -0,0 +1,509 @@
+#![no_std]
+//! Elecciones por listas. Cada candidato de la encuesta es el líder de un
+//! partido de `candidate_registry` y representa a su lista en la papeleta. Un
+//! voto por el partido cuenta para toda su lista; al finalizar, los escaños se
+//! reparten por D'Hondt o Sainte-Laguë y se asignan en el orden de la lista.
+use dappcenvote_types::{
+    candidate_registry::CandidateRegistryClient,
+    party_list::{
+        DataKey, PartyBallot, PartyBallotCreated, PartyListError, PartyListInterface, PartyResult,
+        PartyVoteCast, SeatMethod, SeatsAllocated,
+    },
+    survey::{ResultsVisibility, Survey, SurveyClient},
+    user_registry::UserRegistryClient,
+    PauseScope,
+};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, xdr::ToXdr, Address, BytesN, Env, Vec,
+};
+
+const CONTRACT_VERSION: u32 = 1;
+const MAX_SEATS: u32 = 500;
+
+#[contract]
+pub struct PartyListBallotBox;
+
+#[contractimpl]
+impl PartyListBallotBox {
+    /// Inicializa el contrato al desplegarlo, con el administrador y el contrato de encuestas
+    pub fn __constructor(env: Env, admin: Address, survey_contract: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        env.storage().instance().set(&DataKey::Survey, &survey_contract);
+        env.storage().instance().extend_ttl(100, 8640000);
+    }
+}
+
+#[contractimpl]
+impl PartyListInterface for PartyListBallotBox {
+    /// Reemplaza el código del contrato conservando su almacenamiento (solo administrador)
+    /// Debe seguirse de `migrate` si la nueva versión cambia el formato de los datos
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Migra el almacenamiento a la versión de este código (solo administrador)
+    /// Sin efecto si el almacenamiento ya está en esa versión
+    /// La versión 1 es el formato original, por lo que aún no hay transformaciones
+    fn migrate(env: Env) {
+        Self::require_admin(&env);
+
+        let version = Self::version(env.clone());
+        if version >= CONTRACT_VERSION {
+            return; // Ya está al día; repetir la llamada no es un error
+        }
+
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+    }
+
+    /// Obtiene la versión del formato de almacenamiento
+    fn version(env: Env) -> u32 {
+        let key = DataKey::ContractVersion;
+        env.storage().instance().get(&key).unwrap_or(1)
+    }
+
+    /// Convierte una encuesta en elección por listas (solo el creador, antes del inicio)
+    /// Cada candidato debe liderar un partido; su lista actual se fija para la elección
+    fn create_party_ballot(env: Env, survey_id: u64, seats: u32, method: SeatMethod) {
+        let survey_client = Self::survey_client(&env);
+        let survey = survey_client.get_survey(&survey_id).expect("Survey not found");
+        survey.creator.require_auth();
+
+        let ballot_key = DataKey::Ballot(survey_id);
+        if env.storage().persistent().has(&ballot_key) {
+            panic!("Party ballot already exists");
+        }
+        if seats == 0 || seats > MAX_SEATS {
+            panic!("Seats must be between 1 and 500");
+        }
+
+        // La encuesta valida que siga en borrador y que tenga candidatos
+        survey_client.set_ballot_box(&survey_id, &env.current_contract_address());
+
+        let registries = survey_client.get_registries().expect("Registries not set");
+        let registry = CandidateRegistryClient::new(&env, &registries.candidate_registry);
+        let mut parties = Vec::new(&env);
+        let mut slates = Vec::new(&env);
+        let mut counts = Vec::new(&env);
+        for candidate in survey.candidates.iter() {
+            let party = registry
+                .get_candidate_party(&candidate)
+                .and_then(|party_id| registry.get_party(&party_id))
+                .filter(|party| party.leader == candidate)
+                .expect("Candidate is not a party leader");
+            parties.push_back(party.party_id);
+            slates.push_back(party.members);
+            counts.push_back(0u64);
+        }
+
+        let ballot = PartyBallot {
+            survey_id,
+            parties: parties.clone(),
+            slates,
+            seats,
+            method,
+            counts,
+            allocation: Vec::new(&env),
+        };
+        Self::save_ballot(&env, &ballot);
+
+        PartyBallotCreated { survey_id, parties, seats, method }.publish(&env);
+    }
+
+    /// Vota por un partido, con las mismas condiciones que `vote` en la encuesta
+    /// Retorna el recibo de la encuesta, que compromete el partido elegido
+    fn vote_party(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        party_id: u64,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32> {
+        voter.require_auth();
+
+        let survey_client = Self::survey_client(&env);
+        if survey_client.is_paused(&PauseScope::Voting) {
+            panic_with_error!(&env, PartyListError::Paused);
+        }
+        let mut ballot = Self::load_ballot(&env, survey_id);
+        let survey = Self::load_survey(&env, survey_id);
+        let current_time = env.ledger().timestamp();
+        if current_time < survey.start_date {
+            panic!("Survey has not started yet");
+        }
+        if current_time > survey.end_date {
+            panic!("Survey has ended");
+        }
+        if survey_client.is_cancelled(&survey_id) {
+            panic!("Survey has been cancelled");
+        }
+        if !survey_client.is_eligible(&survey_id, &voter, &proof) {
+            panic_with_error!(&env, PartyListError::NotEligible);
+        }
+        if let Some(registries) = survey_client.get_registries() {
+            if !UserRegistryClient::new(&env, &registries.user_registry).user_exists(&voter) {
+                panic_with_error!(&env, PartyListError::NotRegistered);
+            }
+        }
+
+        let voted_key = DataKey::Voted(survey_id, voter.clone());
+        if env.storage().persistent().has(&voted_key) {
+            panic_with_error!(&env, PartyListError::AlreadyVoted);
+        }
+        let position = ballot.parties.first_index_of(party_id).expect("Party not on the ballot");
+
+        let votes = ballot.counts.get(position).unwrap();
+        ballot.counts.set(position, votes + 1);
+        Self::save_ballot(&env, &ballot);
+        Self::set_persistent(&env, &voted_key, &party_id);
+
+        // Como VoteCast: el partido solo se publica con resultados en vivo
+        let live = survey_client.get_results_policy(&survey_id).visibility == ResultsVisibility::Live;
+        let shown = if live { Some(party_id) } else { None };
+        PartyVoteCast { survey_id, voter: voter.clone(), party_id: shown }.publish(&env);
+
+        let commitment = env.crypto().sha256(&party_id.to_xdr(&env)).to_bytes();
+        survey_client.record_receipt(&survey_id, &voter, &commitment)
+    }
+
+    /// Reparte los escaños, entrega el conteo a la encuesta y la finaliza (cualquiera, tras el cierre)
+    /// Retorna los escaños de cada partido en el orden de la papeleta
+    fn finalize(env: Env, survey_id: u64) -> Vec<u32> {
+        let mut ballot = Self::load_ballot(&env, survey_id);
+        if !ballot.allocation.is_empty() {
+            panic_with_error!(&env, PartyListError::AlreadyFinalized);
+        }
+
+        ballot.allocation = allocate_seats(&env, &ballot);
+        Self::save_ballot(&env, &ballot);
+        SeatsAllocated {
+            survey_id,
+            counts: ballot.counts.clone(),
+            allocation: ballot.allocation.clone(),
+        }
+        .publish(&env);
+
+        // La encuesta comprueba que ya terminó y que no estaba cerrada
+        // Sin padrón: quién votó queda en las marcas Voted y en los eventos
+        let survey_client = Self::survey_client(&env);
+        survey_client.record_tally(&survey_id, &ballot.counts, &Vec::new(&env));
+        survey_client.finalize_survey(&survey_id);
+        ballot.allocation
+    }
+
+    /// Obtiene la elección por listas de una encuesta
+    /// Incluye los conteos, así que falla mientras la encuesta oculte sus resultados
+    fn get_party_ballot(env: Env, survey_id: u64) -> Option<PartyBallot> {
+        let ballot = env.storage().persistent().get(&DataKey::Ballot(survey_id))?;
+        Self::require_results_visible(&env, survey_id);
+        Some(ballot)
+    }
+
+    /// Obtiene votos, escaños y electos de cada partido en el orden de la papeleta
+    /// Los escaños son cero hasta `finalize`; falla mientras la encuesta oculte sus resultados
+    fn get_party_results(env: Env, survey_id: u64) -> Vec<PartyResult> {
+        let ballot = Self::load_ballot(&env, survey_id);
+        Self::require_results_visible(&env, survey_id);
+
+        let mut results = Vec::new(&env);
+        for (i, party_id) in ballot.parties.iter().enumerate() {
+            let seats = ballot.allocation.get(i as u32).unwrap_or(0);
+            let slate = ballot.slates.get(i as u32).unwrap();
+            results.push_back(PartyResult {
+                party_id,
+                votes: ballot.counts.get(i as u32).unwrap(),
+                seats,
+                elected: slate.slice(0..seats),
+            });
+        }
+        results
+    }
+}
+
+impl PartyListBallotBox {
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        admin
+    }
+
+    fn survey_client(env: &Env) -> SurveyClient<'_> {
+        let survey: Address = env.storage().instance()
+            .get(&DataKey::Survey)
+            .expect("Contract not initialized");
+        SurveyClient::new(env, &survey)
+    }
+
+    fn load_survey(env: &Env, survey_id: u64) -> Survey {
+        Self::survey_client(env).get_survey(&survey_id).expect("Survey not found")
+    }
+
+    fn load_ballot(env: &Env, survey_id: u64) -> PartyBallot {
+        env.storage().persistent()
+            .get(&DataKey::Ballot(survey_id))
+            .expect("Party ballot not found")
+    }
+
+    // La misma regla que aplica la encuesta a sus propios resultados
+    fn require_results_visible(env: &Env, survey_id: u64) {
+        let survey_client = Self::survey_client(env);
+        let visible = match survey_client.get_results_policy(&survey_id).visibility {
+            ResultsVisibility::Live => true,
+            ResultsVisibility::AfterClose => {
+                survey_client.is_finalized(&survey_id)
+                    || env.ledger().timestamp() > Self::load_survey(env, survey_id).end_date
+            }
+            ResultsVisibility::AfterFinalize => survey_client.is_finalized(&survey_id),
+        };
+        if !visible {
+            panic_with_error!(env, PartyListError::ResultsHidden);
+        }
+    }
+
+    fn save_ballot(env: &Env, ballot: &PartyBallot) {
+        Self::set_persistent(env, &DataKey::Ballot(ballot.survey_id), ballot);
+    }
+
+    fn set_persistent<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(key, 100, 8640000);
+    }
+}
+
+/// Promedios mayores: cada escaño va al partido con mayor votos / divisor(escaños ya
+/// obtenidos). Un partido sin votos o con la lista agotada no recibe más; en empate
+/// gana el primero de la papeleta. Puede quedar algún escaño sin asignar.
+fn allocate_seats(env: &Env, ballot: &PartyBallot) -> Vec<u32> {
+    let mut allocation = Vec::new(env);
+    for _ in ballot.parties.iter() {
+        allocation.push_back(0u32);
+    }
+
+    for _ in 0..ballot.seats {
+        // (posición, votos, divisor) del mejor cociente hasta ahora
+        let mut best: Option<(u32, u128, u128)> = None;
+        for (i, votes) in ballot.counts.iter().enumerate() {
+            let i = i as u32;
+            let won = allocation.get(i).unwrap();
+            if votes == 0 || won >= ballot.slates.get(i).unwrap().len() {
+                continue;
+            }
+            let divisor = match ballot.method {
+                SeatMethod::DHondt => won as u128 + 1,
+                SeatMethod::SainteLague => 2 * won as u128 + 1,
+            };
+            let better = match best {
+                Some((_, best_votes, best_divisor)) => votes as u128 * best_divisor > best_votes * divisor,
+                None => true,
+            };
+            if better {
+                best = Some((i, votes as u128, divisor));
+            }
+        }
+        match best {
+            Some((i, _, _)) => allocation.set(i, allocation.get(i).unwrap() + 1),
+            None => break,
+        }
+    }
+    allocation
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use candidate_registry::CandidateRegistry;
+    use dappcenvote_types::survey::ResultsVisibility;
+    use soroban_sdk::{testutils::{Address as _, Events, Ledger}, Map, String, Symbol, TryIntoVal, Val};
+    use survey::{SurveyContract, SurveyContractClient};
+    use user_registry::UserRegistry;
+    use dappcenvote_types::{
+        candidate_registry::CandidateRegistryClient, user_registry::UserRegistryClient,
+    };
+
+    struct Setup<'a> {
+        env: Env,
+        survey: SurveyContractClient<'a>,
+        users: UserRegistryClient<'a>,
+        client: PartyListBallotBoxClient<'a>,
+        survey_id: u64,
+        parties: Vec<u64>,
+        slates: Vec<Vec<Address>>,
+    }
+
+    // Tres partidos con listas de 3, 2 y 1 candidatos, en una encuesta entre 1000 y 3000
+    fn setup<'a>(seats: u32, method: SeatMethod) -> Setup<'a> {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(500);
+
+        let admin = Address::generate(&env);
+        let users = UserRegistryClient::new(&env, &env.register(UserRegistry, (admin.clone(),)));
+        let registry = CandidateRegistryClient::new(&env, &env.register(CandidateRegistry, (admin.clone(),)));
+        let survey = SurveyContractClient::new(&env, &env.register(SurveyContract, (admin.clone(),)));
+        survey.set_registries(&users.address, &registry.address);
+        let client = PartyListBallotBoxClient::new(
+            &env,
+            &env.register(PartyListBallotBox, (admin.clone(), survey.address.clone())),
+        );
+        survey.add_ballot_box_contract(&client.address);
+
+        let text = |value: &str| String::from_str(&env, value);
+        let mut parties = Vec::new(&env);
+        let mut slates = Vec::new(&env);
+        let mut leaders = Vec::new(&env);
+        for (name, size) in [("Partido Azul", 3), ("Partido Rojo", 2), ("Partido Verde", 1)] {
+            let mut slate = Vec::new(&env);
+            for _ in 0..size {
+                let wallet = Address::generate(&env);
+                registry.register_candidate(&wallet, &text("Candidato"), &text("RFC"));
+                slate.push_back(wallet);
+            }
+            let leader = slate.get(0).unwrap();
+            let party_id = registry.create_party(&leader, &text(name), &BytesN::from_array(&env, &[0; 32]));
+            for member in slate.slice(1..).iter() {
+                registry.add_party_member(&party_id, &member);
+            }
+            parties.push_back(party_id);
+            slates.push_back(slate);
+            leaders.push_back(leader);
+        }
+
+        let creator = Address::generate(&env);
+        let survey_id = survey.create_survey(&creator, &text("Congreso"), &text("Listas"), &1000, &3000, &leaders);
+        client.create_party_ballot(&survey_id, &seats, &method);
+        Setup { env, survey, users, client, survey_id, parties, slates }
+    }
+
+    fn register_voter(s: &Setup) -> Address {
+        let voter = Address::generate(&s.env);
+        let text = |value: &str| String::from_str(&s.env, value);
+        let (name, phone, email) = (text("Votante"), text("5551234567"), text("votante@example.com"));
+        s.users.register_user(&voter, &name, &name, &name, &phone, &email);
+        voter
+    }
+
+    fn vote(s: &Setup, party: u32, times: u32) {
+        for _ in 0..times {
+            let voter = register_voter(s);
+            let receipt = s.client.vote_party(&s.survey_id, &voter, &s.parties.get(party).unwrap(), &None);
+            assert!(s.survey.verify_receipt(&s.survey_id, &receipt));
+        }
+    }
+
+    #[test]
+    fn test_dhondt_election() {
+        let s = setup(4, SeatMethod::DHondt);
+        s.env.ledger().set_timestamp(1500);
+        vote(&s, 0, 5);
+        vote(&s, 1, 3);
+        vote(&s, 2, 1);
+
+        // El voto directo queda bloqueado y cada votante vota una sola vez
+        let voter = Address::generate(&s.env);
+        let leader = s.slates.get(0).unwrap().get(0).unwrap();
+        assert!(s.survey.try_vote(&s.survey_id, &voter, &leader, &None).is_err());
+
+        s.env.ledger().set_timestamp(3001);
+        let allocation = s.client.finalize(&s.survey_id);
+        assert_eq!(allocation, Vec::from_array(&s.env, [3u32, 1, 0]));
+        assert!(s.survey.is_finalized(&s.survey_id));
+        assert_eq!(s.survey.get_total_votes(&s.survey_id), 9);
+        assert!(s.survey.get_voters(&s.survey_id).is_empty());
+
+        // Los votos del partido aparecen en la encuesta bajo su líder
+        let results = s.survey.get_results(&s.survey_id);
+        assert_eq!(results.get(0).unwrap().votes, 5);
+
+        let party_results = s.client.get_party_results(&s.survey_id);
+        assert_eq!(party_results.get(0).unwrap().elected, s.slates.get(0).unwrap());
+        assert_eq!(party_results.get(1).unwrap().elected, s.slates.get(1).unwrap().slice(0..1));
+        assert_eq!(party_results.get(2).unwrap().seats, 0);
+
+        let result = s.client.try_finalize(&s.survey_id);
+        assert_eq!(result.err(), Some(Ok(PartyListError::AlreadyFinalized.into())));
+    }
+
+    #[test]
+    fn test_sainte_lague_and_exhausted_slates() {
+        // Sainte-Laguë favorece a los partidos pequeños frente a D'Hondt
+        let s = setup(4, SeatMethod::SainteLague);
+        s.env.ledger().set_timestamp(1500);
+        vote(&s, 0, 4);
+        vote(&s, 1, 2);
+        vote(&s, 2, 1);
+
+        // D'Hondt daría [3, 1, 0] con los mismos votos
+        s.env.ledger().set_timestamp(3001);
+        assert_eq!(s.client.finalize(&s.survey_id), Vec::from_array(&s.env, [2u32, 1, 1]));
+
+        // Con más escaños que candidatos, cada lista se agota y sobran escaños
+        let s = setup(10, SeatMethod::DHondt);
+        s.env.ledger().set_timestamp(1500);
+        vote(&s, 0, 1);
+        vote(&s, 1, 1);
+        s.env.ledger().set_timestamp(3001);
+        assert_eq!(s.client.finalize(&s.survey_id), Vec::from_array(&s.env, [3u32, 2, 0]));
+    }
+
+    // party_id del último PartyVoteCast publicado por la urna
+    fn last_party_vote(s: &Setup) -> Val {
+        let (_, _, data) = s.env.events().all().iter().rfind(|(contract, _, _)| *contract == s.client.address).unwrap();
+        let data: Map<Symbol, Val> = data.try_into_val(&s.env).unwrap();
+        data.get(Symbol::new(&s.env, "party_id")).unwrap()
+    }
+
+    #[test]
+    fn test_hidden_results() {
+        let s = setup(4, SeatMethod::DHondt);
+        s.survey.set_results_visibility(&s.survey_id, &ResultsVisibility::AfterFinalize, &false);
+
+        // Ni los eventos ni los conteos revelan el partido antes de finalizar
+        s.env.ledger().set_timestamp(1500);
+        s.client.vote_party(&s.survey_id, &register_voter(&s), &s.parties.get(1).unwrap(), &None);
+        assert!(last_party_vote(&s).is_void());
+        let result = s.client.try_get_party_results(&s.survey_id);
+        assert_eq!(result.err(), Some(Ok(PartyListError::ResultsHidden.into())));
+        let result = s.client.try_get_party_ballot(&s.survey_id);
+        assert_eq!(result.err(), Some(Ok(PartyListError::ResultsHidden.into())));
+
+        s.env.ledger().set_timestamp(3001);
+        s.client.finalize(&s.survey_id);
+        assert_eq!(s.client.get_party_results(&s.survey_id).get(1).unwrap().votes, 1);
+        assert_eq!(s.client.get_party_ballot(&s.survey_id).unwrap().counts.get(1).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_double_vote() {
+        let s = setup(4, SeatMethod::DHondt);
+        s.env.ledger().set_timestamp(1500);
+        let voter = register_voter(&s);
+        s.client.vote_party(&s.survey_id, &voter, &s.parties.get(0).unwrap(), &None);
+        let party_id: Option<u64> = last_party_vote(&s).try_into_val(&s.env).unwrap();
+        assert_eq!(party_id, s.parties.get(0));
+
+
+        let result = s.client.try_vote_party(&s.survey_id, &voter, &s.parties.get(1).unwrap(), &None);
+        assert_eq!(result.err(), Some(Ok(PartyListError::AlreadyVoted.into())));
+    }
+
+    #[test]
+    #[should_panic(expected = "Candidate is not a party leader")]
+    fn test_candidates_must_lead_parties() {
+        let s = setup(4, SeatMethod::DHondt);
+        let member = s.slates.get(0).unwrap().get(1).unwrap();
+        let text = |value: &str| String::from_str(&s.env, value);
+        let survey_id = s.survey.create_survey(
+            &Address::generate(&s.env),
+            &text("Congreso"),
+            &text("Listas"),
+            &1000,
+            &3000,
+            &Vec::from_array(&s.env, [member]),
+        );
+        s.client.create_party_ballot(&survey_id, &4, &SeatMethod::DHondt);
+    }
+}
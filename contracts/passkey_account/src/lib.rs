@@ -0,0 +1,329 @@
+#![no_std]
+use dappcenvote_types::passkey_account::{
+    DataKey, PasskeyAccountInterface, PasskeyError, WebAuthnSignature,
+};
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contractimpl,
+    crypto::Hash,
+    Bytes, BytesN, Env, String, Vec,
+};
+
+const MAX_CLIENT_DATA_LEN: u32 = 1024;
+const RP_ID_HASH_LEN: u32 = 32;
+const AUTH_DATA_FLAGS_INDEX: u32 = RP_ID_HASH_LEN; // Después del hash del rpId
+const FLAG_USER_PRESENT: u8 = 0x01;
+
+#[contract]
+pub struct PasskeyAccount;
+
+#[contractimpl]
+impl PasskeyAccount {
+    /// Crea la cuenta controlada por una passkey emitida para el RP ID `rp_id`
+    pub fn __constructor(env: Env, rp_id: String, credential_id: Bytes, public_key: BytesN<65>) {
+        let rp_id_hash = env.crypto().sha256(&rp_id.to_bytes()).to_bytes();
+        env.storage().instance().set(&DataKey::RpIdHash, &rp_id_hash);
+        Self::store_signer(&env, &credential_id, &public_key);
+        env.storage().instance().set(&DataKey::SignerCount, &1u32);
+        env.storage().instance().extend_ttl(100, 8640000);
+    }
+}
+
+#[contractimpl]
+impl PasskeyAccountInterface for PasskeyAccount {
+    /// Registra otra passkey (p. ej. un segundo dispositivo); requiere la firma de la cuenta
+    fn add_signer(env: Env, credential_id: Bytes, public_key: BytesN<65>) {
+        env.current_contract_address().require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Signer(credential_id.clone())) {
+            let count: u32 = env.storage().instance().get(&DataKey::SignerCount).unwrap_or(0);
+            env.storage().instance().set(&DataKey::SignerCount, &(count + 1));
+        }
+        Self::store_signer(&env, &credential_id, &public_key);
+    }
+
+    /// Elimina una passkey; la última no se puede eliminar
+    fn remove_signer(env: Env, credential_id: Bytes) -> Result<(), PasskeyError> {
+        env.current_contract_address().require_auth();
+
+        let key = DataKey::Signer(credential_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(PasskeyError::UnknownSigner);
+        }
+        let count: u32 = env.storage().instance().get(&DataKey::SignerCount).unwrap_or(0);
+        if count <= 1 {
+            return Err(PasskeyError::LastSigner);
+        }
+        env.storage().persistent().remove(&key);
+        env.storage().instance().set(&DataKey::SignerCount, &(count - 1));
+        Ok(())
+    }
+
+    /// Obtiene la llave pública de una passkey registrada
+    fn get_signer(env: Env, credential_id: Bytes) -> Option<BytesN<65>> {
+        let key = DataKey::Signer(credential_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Obtiene el número de passkeys registradas
+    fn get_signer_count(env: Env) -> u32 {
+        let key = DataKey::SignerCount;
+        env.storage().instance().get(&key).unwrap_or(0)
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for PasskeyAccount {
+    type Signature = WebAuthnSignature;
+    type Error = PasskeyError;
+
+    /// Verifica una aserción WebAuthn cuyo challenge es el payload de autorización
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signature: WebAuthnSignature,
+        _auth_contexts: Vec<Context>,
+    ) -> Result<(), PasskeyError> {
+        let public_key: BytesN<65> = env.storage().persistent()
+            .get(&DataKey::Signer(signature.credential_id.clone()))
+            .ok_or(PasskeyError::UnknownSigner)?;
+
+        // La aserción debe ser para el RP ID de la aplicación, no para otro sitio
+        if signature.authenticator_data.len() <= AUTH_DATA_FLAGS_INDEX {
+            return Err(PasskeyError::InvalidClientData);
+        }
+        let rp_id_hash: BytesN<32> = env.storage().instance().get(&DataKey::RpIdHash).unwrap();
+        if signature.authenticator_data.slice(..RP_ID_HASH_LEN) != Bytes::from(rp_id_hash) {
+            return Err(PasskeyError::RpIdMismatch);
+        }
+
+        // El autenticador debe confirmar la presencia del usuario
+        let flags = signature.authenticator_data
+            .get(AUTH_DATA_FLAGS_INDEX)
+            .ok_or(PasskeyError::InvalidClientData)?;
+        if flags & FLAG_USER_PRESENT == 0 {
+            return Err(PasskeyError::UserNotPresent);
+        }
+
+        // clientDataJSON debe ser de tipo webauthn.get y firmar este payload
+        let client_data = signature.client_data_json.clone();
+        let len = client_data.len();
+        if len > MAX_CLIENT_DATA_LEN {
+            return Err(PasskeyError::InvalidClientData);
+        }
+        let mut buf = [0u8; MAX_CLIENT_DATA_LEN as usize];
+        let client_data = &mut buf[..len as usize];
+        signature.client_data_json.copy_into_slice(client_data);
+
+        if !contains(client_data, br#""type":"webauthn.get""#) {
+            return Err(PasskeyError::InvalidClientData);
+        }
+        let mut challenge = [0u8; 56];
+        challenge[..13].copy_from_slice(br#""challenge":""#);
+        base64url_encode(&signature_payload.to_array(), &mut challenge[13..56]);
+        if !contains(client_data, &challenge) {
+            return Err(PasskeyError::ChallengeMismatch);
+        }
+
+        // Mensaje firmado: authenticatorData || sha256(clientDataJSON)
+        let mut message = signature.authenticator_data.clone();
+        message.extend_from_array(&env.crypto().sha256(&signature.client_data_json).to_array());
+        let digest = env.crypto().sha256(&message);
+        env.crypto().secp256r1_verify(&public_key, &digest, &signature.signature);
+
+        Ok(())
+    }
+}
+
+impl PasskeyAccount {
+    fn store_signer(env: &Env, credential_id: &Bytes, public_key: &BytesN<65>) {
+        let key = DataKey::Signer(credential_id.clone());
+        env.storage().persistent().set(&key, public_key);
+        env.storage().persistent().extend_ttl(&key, 100, 8640000);
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+// Codifica 32 bytes en base64url sin relleno (43 caracteres), como en clientDataJSON
+fn base64url_encode(input: &[u8; 32], out: &mut [u8]) {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut o = 0;
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+
+        let chars = chunk.len() + 1;
+        for i in 0..chars {
+            out[o] = ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize];
+            o += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{Address, IntoVal};
+
+    // Aserción WebAuthn fija generada con una llave P-256 de prueba (0x11 * 32)
+    // para el origen https://localhost:4321 (RP ID "localhost")
+    const PUBLIC_KEY: &str = "040217e617f0b6443928278f96999e69a23a4f2c152bdf6d6cdf66e5b80282d4ed194a7debcb97712d2dda3ca85aa8765a56f45fc758599652f2897c65306e5794";
+    const PAYLOAD: &str = "6b47cbc7b422edf896a7e89cb4ca2cfab0ec2d5c3570d783a5b6b6b393de5140";
+    const AUTH_DATA: &str = "49960de5880e8c687434170f6476605b8fe4aeb9a28632c7995cf3ba831d97630500000001";
+    const CLIENT_DATA_JSON: &str = r#"{"type":"webauthn.get","challenge":"a0fLx7Qi7fiWp-ictMos-rDsLVw1cNeDpba2s5PeUUA","origin":"https://localhost:4321","crossOrigin":false}"#;
+    const SIGNATURE: &str = "e604596063573df688123541bb92c48b09b7ba4a287f3adf413c719f0889cea81cd72bd36bc533f7783fb68e6bea949bcb0ec1497cbe9316b52c64dca7abcff2";
+
+    fn hex_bytes<const N: usize>(hex: &str) -> [u8; N] {
+        let mut out = [0u8; N];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        out
+    }
+
+    fn credential_id(env: &Env) -> Bytes {
+        Bytes::from_slice(env, b"cenvote-test-credential")
+    }
+
+    fn setup(env: &Env) -> Address {
+        let public_key = BytesN::from_array(env, &hex_bytes::<65>(PUBLIC_KEY));
+        let rp_id = String::from_str(env, "localhost");
+        env.register(PasskeyAccount, (rp_id, credential_id(env), public_key))
+    }
+
+    fn assertion(env: &Env) -> WebAuthnSignature {
+        WebAuthnSignature {
+            credential_id: credential_id(env),
+            authenticator_data: Bytes::from_array(env, &hex_bytes::<37>(AUTH_DATA)),
+            client_data_json: Bytes::from_slice(env, CLIENT_DATA_JSON.as_bytes()),
+            signature: BytesN::from_array(env, &hex_bytes::<64>(SIGNATURE)),
+        }
+    }
+
+    fn check_auth(
+        env: &Env,
+        account: &Address,
+        payload: [u8; 32],
+        signature: WebAuthnSignature,
+    ) -> Result<(), Result<PasskeyError, soroban_sdk::InvokeError>> {
+        env.try_invoke_contract_check_auth::<PasskeyError>(
+            account,
+            &BytesN::from_array(env, &payload),
+            signature.into_val(env),
+            &Vec::new(env),
+        )
+    }
+
+    #[test]
+    fn test_base64url_challenge() {
+        let mut out = [0u8; 43];
+        base64url_encode(&hex_bytes::<32>(PAYLOAD), &mut out);
+        assert_eq!(&out, b"a0fLx7Qi7fiWp-ictMos-rDsLVw1cNeDpba2s5PeUUA");
+    }
+
+    #[test]
+    fn test_valid_assertion() {
+        let env = Env::default();
+        let account = setup(&env);
+
+        assert_eq!(check_auth(&env, &account, hex_bytes(PAYLOAD), assertion(&env)), Ok(()));
+    }
+
+    #[test]
+    fn test_challenge_mismatch() {
+        let env = Env::default();
+        let account = setup(&env);
+
+        let mut payload = hex_bytes::<32>(PAYLOAD);
+        payload[0] ^= 1;
+        assert_eq!(
+            check_auth(&env, &account, payload, assertion(&env)),
+            Err(Ok(PasskeyError::ChallengeMismatch))
+        );
+    }
+
+    #[test]
+    fn test_unknown_credential() {
+        let env = Env::default();
+        let account = setup(&env);
+
+        let mut signature = assertion(&env);
+        signature.credential_id = Bytes::from_slice(&env, b"other-credential");
+        assert_eq!(
+            check_auth(&env, &account, hex_bytes(PAYLOAD), signature),
+            Err(Ok(PasskeyError::UnknownSigner))
+        );
+    }
+
+    #[test]
+    fn test_rp_id_mismatch() {
+        let env = Env::default();
+        let public_key = BytesN::from_array(&env, &hex_bytes::<65>(PUBLIC_KEY));
+        let rp_id = String::from_str(&env, "cenvote.mx");
+        let account = env.register(PasskeyAccount, (rp_id, credential_id(&env), public_key));
+
+        // Una aserción válida emitida para otro sitio no autoriza a la cuenta
+        assert_eq!(
+            check_auth(&env, &account, hex_bytes(PAYLOAD), assertion(&env)),
+            Err(Ok(PasskeyError::RpIdMismatch))
+        );
+    }
+
+    #[test]
+    fn test_user_not_present() {
+        let env = Env::default();
+        let account = setup(&env);
+
+        let mut signature = assertion(&env);
+        signature.authenticator_data.set(AUTH_DATA_FLAGS_INDEX, 0x04);
+        assert_eq!(
+            check_auth(&env, &account, hex_bytes(PAYLOAD), signature),
+            Err(Ok(PasskeyError::UserNotPresent))
+        );
+    }
+
+    #[test]
+    fn test_tampered_client_data() {
+        let env = Env::default();
+        let account = setup(&env);
+
+        // Mismo challenge pero otro origen: la firma ya no corresponde
+        let mut signature = assertion(&env);
+        signature.client_data_json = Bytes::from_slice(
+            &env,
+            CLIENT_DATA_JSON.replace("localhost:4321", "evil.example").as_bytes(),
+        );
+        assert!(check_auth(&env, &account, hex_bytes(PAYLOAD), signature).is_err());
+    }
+
+    #[test]
+    fn test_manage_signers() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let account = setup(&env);
+        let client = PasskeyAccountClient::new(&env, &account);
+
+        assert_eq!(client.get_signer_count(), 1);
+        assert_eq!(
+            client.try_remove_signer(&credential_id(&env)),
+            Err(Ok(PasskeyError::LastSigner))
+        );
+
+        let laptop = Bytes::from_slice(&env, b"laptop-credential");
+        let public_key = BytesN::from_array(&env, &hex_bytes::<65>(PUBLIC_KEY));
+        client.add_signer(&laptop, &public_key);
+        assert_eq!(client.get_signer_count(), 2);
+
+        client.remove_signer(&credential_id(&env));
+        assert_eq!(client.get_signer_count(), 1);
+        assert!(client.get_signer(&credential_id(&env)).is_none());
+        assert!(client.get_signer(&laptop).is_some());
+    }
+}
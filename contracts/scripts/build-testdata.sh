@@ -0,0 +1,38 @@
+#!/usr/bin/env bash
+# Regenera los wasm de */testdata/ que usan las pruebas de upgrade.
+#
+# Son versiones congeladas de los contratos, no el código actual: se compilan
+# desde el commit que introdujo `upgrade`/`migrate`, cuando cada contrato era
+# un crate independiente. survey_v1.wasm es ese mismo código con el formato
+# de encuesta anterior (sin `created_at`, ContractVersion 1).
+#
+#   contracts/scripts/build-testdata.sh [commit]
+set -euo pipefail
+
+REV=${1:-83b537a}
+CONTRACTS=$(cd "$(dirname "$0")/.." && pwd)
+WORKTREE=$(mktemp -d)
+trap 'git -C "$CONTRACTS" worktree remove --force "$WORKTREE"' EXIT
+
+git -C "$CONTRACTS" worktree add --detach "$WORKTREE" "$REV" >/dev/null
+
+build() {
+    # Las mismas versiones de dependencias que el workspace, para que el wasm salga idéntico
+    cp "$CONTRACTS/Cargo.lock" "$WORKTREE/contracts/$1/Cargo.lock"
+    (cd "$WORKTREE/contracts/$1" && cargo build --release --target wasm32v1-none --quiet)
+    cp "$WORKTREE/contracts/$1/target/wasm32v1-none/release/$1.wasm" "$CONTRACTS/$1/testdata/$2"
+}
+
+build user_registry user_registry.wasm
+build candidate_registry candidate_registry.wasm
+build survey survey_v2.wasm
+
+sed -i \
+    -e '/pub created_at: u64,/d' \
+    -e '/created_at: env.ledger().timestamp(),/d' \
+    -e '/created_at: 0, \/\/ Desconocido para encuestas anteriores/d' \
+    -e 's/const CONTRACT_VERSION: u32 = 2;/const CONTRACT_VERSION: u32 = 1;/' \
+    "$WORKTREE/contracts/survey/src/lib.rs"
+build survey survey_v1.wasm
+
+echo "testdata regenerated from $REV"
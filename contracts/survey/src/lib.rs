@@ -1,10 +1,28 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec, Map, token};
+use dappcenvote_types::{
+    survey::{
+        CreationPolicy, CreatorRevenueClaimed, DataKey, DelegationScope, Eligibility, FeeRefunded,
+        MetadataUpdated, OptionResult, PrizeDistribution, PrizePool, PrizePoolFunded,
+        PrizePoolReclaimed, Question, ReceiptChain, ReceiptIssued, Registries, ResponseSubmitted, ResultCertificate, ResultsPolicy,
+        ResultsVisibility, RewardPaid, SignedBallot,
+        Survey, SurveyCancelled, SurveyCategory, SurveyCreated, SurveyDeposit, SurveyError,
+        SurveyFee, SurveyFinalized, SurveyInterface, SurveyMetadata, SurveyRevenue, TallyChanged,
+        TokenAmount, TreasuryWithdrawn, VoteCast, VoteFeeChanged, VoteResult, MAX_OPTIONS, MAX_QUESTIONS,
+    },
+    candidate_registry::CandidateRegistryClient,
+    user_registry::UserRegistryClient,
+    PauseScope,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, token,
+    xdr::{FromXdr, ToXdr},
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
+};
 
-// Estructura de datos para una encuesta
+// Formato de encuesta almacenado por la versión 1 del contrato
 #[contracttype]
 #[derive(Clone)]
-pub struct Survey {
+pub struct SurveyV1 {
     pub survey_id: u64,
     pub creator: Address,
     pub name: String,
@@ -14,47 +32,41 @@ pub struct Survey {
     pub candidates: Vec<Address>,
 }
 
-// Estructura para los resultados de votación
-#[contracttype]
-#[derive(Clone)]
-pub struct VoteResult {
-    pub candidate: Address,
-    pub votes: u64,
-}
-
-// Claves de almacenamiento
-#[contracttype]
-pub enum DataKey {
-    Survey(u64),                   // survey_id -> Survey
-    Vote(u64, Address),            // (survey_id, voter) -> candidate voted for
-    VoteCount(u64, Address),       // (survey_id, candidate) -> vote count
-    VoterList(u64),                // survey_id -> Vec<Address> of voters
-    SurveyCount,                   // Total number of surveys
-    VoteFee,                       // Fee in stroops (1 XLM = 10^7 stroops) for voting
-}
-
 const VOTE_FEE_STROOPS: i128 = 1_000_000; // 0.1 XLM por voto
+const CONTRACT_VERSION: u32 = 2;
+const MAX_MERKLE_PROOF_LEN: u32 = 32; // Suficiente para 2^32 votantes
+const MAX_DELEGATION_DEPTH: u32 = 8;  // Eslabones máximos en una cadena de delegación
+const MAX_DELEGATORS: u32 = 16;       // Delegantes directos por delegado y alcance
+const MAX_DELEGATED_VOTES: u32 = 16;  // Delegantes acreditados en un mismo voto
+const MAX_TAGS: u32 = 10;
+const MAX_METADATA_URI_LEN: u32 = 256;
+const METADATA_URI_PREFIX: &[u8] = b"ipfs://";
+const MAX_BPS: u32 = 10_000;
 
 #[contract]
 pub struct SurveyContract;
 
 #[contractimpl]
 impl SurveyContract {
-    /// Inicializa el contrato con la dirección del administrador
-    pub fn initialize(env: Env, admin: Address) {
-        admin.require_auth();
-        
+    /// Inicializa el contrato al desplegarlo, con la dirección del administrador
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::CreationPolicy, &CreationPolicy::Open);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+
         let fee_key = DataKey::VoteFee;
         env.storage().instance().set(&fee_key, &VOTE_FEE_STROOPS);
         env.storage().instance().extend_ttl(100, 8640000);
         
         let count_key = DataKey::SurveyCount;
-        env.storage().persistent().set(&count_key, &0u64);
-        env.storage().persistent().extend_ttl(&count_key, 100, 8640000);
+        Self::set_persistent(&env, &count_key, &0u64);
     }
+}
 
+#[contractimpl]
+impl SurveyInterface for SurveyContract {
     /// Crea una nueva encuesta
-    pub fn create_survey(
+    fn create_survey(
         env: Env,
         creator: Address,
         name: String,
@@ -66,283 +78,2060 @@ impl SurveyContract {
         // Verificar autenticación del creador
         creator.require_auth();
 
-        // Validaciones
-        if candidates.len() == 0 {
-            panic!("Survey must have at least one candidate");
-        }
-        if start_date >= end_date {
-            panic!("Start date must be before end date");
-        }
+        Self::new_survey(&env, creator, name, description, start_date, end_date, candidates)
+    }
 
-        // Obtener el siguiente ID de encuesta
-        let count_key = DataKey::SurveyCount;
-        let survey_id: u64 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+    /// Crea un cuestionario: una encuesta sin candidatos con varias preguntas
+    /// de opción múltiple que se responden con submit_response
+    fn create_questionnaire(
+        env: Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        questions: Vec<Question>,
+    ) -> u64 {
+        creator.require_auth();
 
-        // Crear la encuesta
-        let survey = Survey {
-            survey_id,
-            creator: creator.clone(),
-            name,
-            description,
-            start_date,
-            end_date,
-            candidates: candidates.clone(),
-        };
+        Self::new_questionnaire(&env, creator, name, description, start_date, end_date, questions)
+    }
 
-        // Guardar la encuesta
-        let survey_key = DataKey::Survey(survey_id);
-        env.storage().persistent().set(&survey_key, &survey);
-        env.storage().persistent().extend_ttl(&survey_key, 100, 8640000);
+    /// Crea una encuesta en nombre de `creator` (solo el programador)
+    /// La política de creación se aplica a `creator`, que queda como su creador
+    fn create_survey_for(
+        env: Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        candidates: Vec<Address>,
+    ) -> u64 {
+        Self::require_scheduler(&env);
 
-        // Actualizar contador
-        env.storage().persistent().set(&count_key, &survey_id);
-        env.storage().persistent().extend_ttl(&count_key, 100, 8640000);
+        Self::new_survey(&env, creator, name, description, start_date, end_date, candidates)
+    }
 
-        // Inicializar lista de votantes
-        let voters_key = DataKey::VoterList(survey_id);
-        env.storage().persistent().set(&voters_key, &Vec::<Address>::new(&env));
-        env.storage().persistent().extend_ttl(&voters_key, 100, 8640000);
+    /// Crea un cuestionario en nombre de `creator` (solo el programador)
+    fn create_questionnaire_for(
+        env: Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        questions: Vec<Question>,
+    ) -> u64 {
+        Self::require_scheduler(&env);
 
-        // Inicializar contadores de votos para cada candidato
-        for candidate in candidates.iter() {
-            let vote_count_key = DataKey::VoteCount(survey_id, candidate.clone());
-            env.storage().persistent().set(&vote_count_key, &0u64);
-            env.storage().persistent().extend_ttl(&vote_count_key, 100, 8640000);
-        }
+        Self::new_questionnaire(&env, creator, name, description, start_date, end_date, questions)
+    }
 
-        survey_id
+    /// Define el contrato que crea encuestas en nombre de otros (solo administrador)
+    fn set_scheduler(env: Env, scheduler: Address) {
+        Self::require_admin(&env);
+
+        env.storage().instance().set(&DataKey::Scheduler, &scheduler);
+    }
+
+    /// Obtiene el contrato programador (si existe)
+    fn get_scheduler(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Scheduler)
     }
 
     /// Registra un voto (requiere pago de fee en XLM)
-    pub fn vote(
+    /// Retorna el recibo del voto, verificable con `verify_receipt`
+    fn vote(
         env: Env,
         survey_id: u64,
         voter: Address,
         candidate: Address,
-    ) -> bool {
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32> {
         // Verificar autenticación del votante
         voter.require_auth();
 
-        // Obtener la encuesta
-        let survey_key = DataKey::Survey(survey_id);
-        let survey: Survey = env.storage().persistent()
-            .get(&survey_key)
-            .expect("Survey not found");
+        Self::cast_vote(&env, survey_id, voter.clone(), candidate, &voter, proof)
+    }
 
-        // Verificar que la encuesta esté activa
-        let current_time = env.ledger().timestamp();
-        if current_time < survey.start_date {
-            panic!("Survey has not started yet");
-        }
-        if current_time > survey.end_date {
-            panic!("Survey has ended");
+    /// Registra un voto firmado fuera de la cadena con la llave ed25519 del votante
+    /// Cualquier relayer puede enviarlo y pagar la transacción; el votante es la
+    /// cuenta G correspondiente a `voter_pubkey`. La firma cubre el XDR de
+    /// ("vote_signed", contrato, boleta)
+    /// Si la encuesta cobra fee, lo paga `relayer` (no el votante) y no puede
+    /// superar `ballot.max_fee`; un eventual reembolso vuelve al relayer
+    fn vote_signed(
+        env: Env,
+        ballot: SignedBallot,
+        voter_pubkey: BytesN<32>,
+        relayer: Address,
+        signature: BytesN<64>,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32> {
+        let voter = Self::account_address(&env, &voter_pubkey);
+
+        // El nonce debe ser exactamente el siguiente para evitar repeticiones
+        let nonce_key = DataKey::Nonce(voter.clone());
+        let expected: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
+        if ballot.nonce != expected {
+            panic_with_error!(&env, SurveyError::InvalidNonce);
         }
 
-        // Verificar que el candidato esté en la lista
-        let mut candidate_is_valid = false;
-        for sc in survey.candidates.iter() {
-            if sc == candidate {
-                candidate_is_valid = true;
-                break;
-            }
+        let payload = (Symbol::new(&env, "vote_signed"), env.current_contract_address(), ballot.clone());
+        env.crypto().ed25519_verify(&voter_pubkey, &payload.to_xdr(&env), &signature);
+
+        let due = Self::fee_due(&env, ballot.survey_id, &voter).map_or(0, |fee| fee.amount);
+        if due > ballot.max_fee {
+            panic!("Vote fee exceeds the signed maximum");
         }
-        if !candidate_is_valid {
-            panic!("Candidate is not in this survey");
+        if due > 0 {
+            relayer.require_auth();
         }
 
-        // Verificar que el votante no haya votado antes
-        let vote_key = DataKey::Vote(survey_id, voter.clone());
-        if env.storage().persistent().has(&vote_key) {
-            panic!("Voter has already voted in this survey");
+        Self::set_persistent(&env, &nonce_key, &(ballot.nonce + 1));
+
+        Self::cast_vote(&env, ballot.survey_id, voter, ballot.candidate, &relayer, proof)
+    }
+
+    /// Obtiene el siguiente nonce que vote_signed aceptará para un votante
+    fn get_nonce(env: Env, voter: Address) -> u64 {
+        let key = DataKey::Nonce(voter);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Registra las respuestas de un votante a un cuestionario: un índice de
+    /// opción por pregunta, en el orden de get_questions. Las respuestas no
+    /// se delegan
+    fn submit_response(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        answers: Vec<u32>,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32> {
+        voter.require_auth();
+        Self::require_not_paused(&env, PauseScope::Voting);
+
+        let survey = Self::get_survey(env.clone(), survey_id).expect("Survey not found");
+        let questions = Self::get_questions(env.clone(), survey_id);
+        if questions.is_empty() {
+            panic!("Survey has no questions");
         }
+        Self::require_open(&env, &survey);
 
-        // Registrar el voto
-        env.storage().persistent().set(&vote_key, &candidate);
-        env.storage().persistent().extend_ttl(&vote_key, 100, 8640000);
+        // Cada pregunta debe tener exactamente una opción válida
+        if answers.len() != questions.len() {
+            panic_with_error!(&env, SurveyError::InvalidResponse);
+        }
+        for (answer, question) in answers.iter().zip(questions.iter()) {
+            if answer >= question.options.len() {
+                panic_with_error!(&env, SurveyError::InvalidResponse);
+            }
+        }
 
-        // Incrementar contador de votos para el candidato
-        let vote_count_key = DataKey::VoteCount(survey_id, candidate.clone());
-        let current_count: u64 = env.storage().persistent()
-            .get(&vote_count_key)
-            .unwrap_or(0);
-        env.storage().persistent().set(&vote_count_key, &(current_count + 1));
-        env.storage().persistent().extend_ttl(&vote_count_key, 100, 8640000);
+        Self::require_voter(&env, survey_id, &voter, proof);
 
-        // Agregar votante a la lista
-        let voters_key = DataKey::VoterList(survey_id);
-        let mut voters: Vec<Address> = env.storage().persistent()
-            .get(&voters_key)
-            .unwrap_or(Vec::new(&env));
-        voters.push_back(voter);
-        env.storage().persistent().set(&voters_key, &voters);
-        env.storage().persistent().extend_ttl(&voters_key, 100, 8640000);
+        // Verificar que el votante no haya respondido antes
+        let response_key = DataKey::Response(survey_id, voter.clone());
+        if env.storage().persistent().has(&response_key) {
+            panic!("Voter has already voted in this survey");
+        }
 
-        true
-    }
+        Self::charge_fee(&env, survey_id, &voter, &voter);
 
-    /// Obtiene una encuesta por su ID
-    pub fn get_survey(env: Env, survey_id: u64) -> Option<Survey> {
-        let key = DataKey::Survey(survey_id);
-        env.storage().persistent().get(&key)
-    }
+        // Registrar la respuesta y sumar cada opción elegida
+        Self::set_persistent(&env, &response_key, &answers);
+        for (question, answer) in answers.iter().enumerate() {
+            let count_key = DataKey::OptionCount(survey_id, question as u32, answer);
+            let count: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            Self::set_persistent(&env, &count_key, &(count + 1));
+        }
+        let commitment = env.crypto().sha256(&answers.clone().to_xdr(&env)).to_bytes();
+        let answers = Self::live_results(&env, survey_id).then_some(answers);
+        ResponseSubmitted { survey_id, voter: voter.clone(), answers }.publish(&env);
 
-    /// Verifica si un votante ya votó en una encuesta
-    pub fn has_voted(env: Env, survey_id: u64, voter: Address) -> bool {
-        let key = DataKey::Vote(survey_id, voter);
-        env.storage().persistent().has(&key)
+        Self::add_voter(&env, survey_id, voter.clone());
+        Self::issue_receipt(&env, survey_id, &voter, &commitment)
     }
 
-    /// Obtiene el candidato por el que votó un usuario (si ya votó)
-    pub fn get_vote(env: Env, survey_id: u64, voter: Address) -> Option<Address> {
-        let key = DataKey::Vote(survey_id, voter);
-        env.storage().persistent().get(&key)
-    }
+    /// Finaliza una encuesta cerrada y devuelve el depósito al creador
+    /// si la participación alcanzó el mínimo requerido
+    /// Retorna true si el depósito fue reembolsado
+    fn finalize_survey(env: Env, survey_id: u64) -> bool {
+        Self::require_not_paused(&env, PauseScope::Voting);
 
-    /// Obtiene los resultados de una encuesta
-    pub fn get_results(env: Env, survey_id: u64) -> Vec<VoteResult> {
         let survey_key = DataKey::Survey(survey_id);
         let survey: Survey = env.storage().persistent()
             .get(&survey_key)
             .expect("Survey not found");
 
-        let mut results = Vec::new(&env);
+        if env.ledger().timestamp() <= survey.end_date {
+            panic!("Survey has not ended yet");
+        }
+        if Self::is_cancelled(env.clone(), survey_id) {
+            panic!("Survey has been cancelled");
+        }
+        // Con urna externa, los votos solo existen aquí cuando entrega su conteo
+        if Self::get_ballot_box(env.clone(), survey_id).is_some()
+            && !env.storage().persistent().has(&DataKey::TallyRecorded(survey_id))
+        {
+            panic!("Ballot box has not recorded its tally");
+        }
 
-        for candidate in survey.candidates.iter() {
-            let vote_count_key = DataKey::VoteCount(survey_id, candidate.clone());
-            let votes: u64 = env.storage().persistent()
-                .get(&vote_count_key)
-                .unwrap_or(0);
+        let finalized_key = DataKey::Finalized(survey_id);
+        if env.storage().persistent().has(&finalized_key) {
+            panic!("Survey already finalized");
+        }
+        Self::set_persistent(&env, &finalized_key, &true);
 
-            results.push_back(VoteResult {
-                candidate: candidate.clone(),
-                votes,
-            });
+        let total_votes = Self::ballot_count(&env, survey_id);
+        let certificate = Self::certify(&env, &survey, total_votes);
+        let certificate_key = DataKey::Certificate(survey_id);
+        Self::set_persistent(&env, &certificate_key, &certificate);
+        SurveyFinalized { survey_id, total_votes }.publish(&env);
+        // Los conteos ocultos durante la votación se publican ahora
+        if !Self::live_results(&env, survey_id) {
+            for result in Self::tally(&env, survey_id).iter() {
+                TallyChanged { survey_id, candidate: result.candidate, delta: result.votes as i64 }
+                    .publish(&env);
+            }
         }
+        Self::settle_revenue(&env, survey_id);
+        Self::settle_prize_pool(&env, survey_id);
 
-        results
+        // Reembolsar el depósito si se alcanzó la participación mínima;
+        // en caso contrario pasa a la tesorería
+        let deposit_key = DataKey::Deposit(survey_id);
+        let deposit: Option<SurveyDeposit> = env.storage().persistent().get(&deposit_key);
+        match deposit {
+            Some(deposit) => {
+                env.storage().persistent().remove(&deposit_key);
+                if total_votes < deposit.min_turnout {
+                    Self::credit_treasury(&env, &deposit.token, deposit.amount);
+                    return false;
+                }
+                let token_client = token::Client::new(&env, &deposit.token);
+                token_client.transfer(&env.current_contract_address(), &deposit.depositor, &deposit.amount);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Obtiene el total de votos en una encuesta
-    pub fn get_total_votes(env: Env, survey_id: u64) -> u64 {
-        let voters_key = DataKey::VoterList(survey_id);
-        let voters: Vec<Address> = env.storage().persistent()
-            .get(&voters_key)
-            .unwrap_or(Vec::new(&env));
-        voters.len() as u64
+    /// Vincula UserRegistry y CandidateRegistry (solo administrador); desde entonces
+    /// solo usuarios registrados votan y solo candidatos registrados se postulan
+    fn set_registries(env: Env, user_registry: Address, candidate_registry: Address) {
+        Self::require_admin(&env);
+
+        let registries = Registries { user_registry, candidate_registry };
+        env.storage().instance().set(&DataKey::Registries, &registries);
+        env.storage().instance().extend_ttl(100, 8640000);
     }
 
-    /// Obtiene el número total de encuestas
-    pub fn get_survey_count(env: Env) -> u64 {
-        let key = DataKey::SurveyCount;
-        env.storage().persistent().get(&key).unwrap_or(0)
+    /// Obtiene los registros vinculados (si existen)
+    fn get_registries(env: Env) -> Option<Registries> {
+        env.storage().instance().get(&DataKey::Registries)
     }
 
-    /// Obtiene el fee de votación en stroops
-    pub fn get_vote_fee(env: Env) -> i128 {
-        let key = DataKey::VoteFee;
-        env.storage().instance().get(&key).unwrap_or(VOTE_FEE_STROOPS)
+    /// Define la dirección guardián que puede pausar el contrato (solo administrador)
+    fn set_guardian(env: Env, guardian: Address) {
+        Self::require_admin(&env);
+
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    /// Obtiene la dirección guardián (si existe)
+    fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Guardian)
+    }
 
-    #[test]
-    fn test_create_survey() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, SurveyContract);
-        let client = SurveyContractClient::new(&env, &contract_id);
+    /// Pausa un ámbito de operación (administrador o guardián)
+    fn pause(env: Env, caller: Address, scope: PauseScope) {
+        Self::require_admin_or_guardian(&env, &caller);
 
-        let admin = Address::generate(&env);
-        let creator = Address::generate(&env);
-        let candidate1 = Address::generate(&env);
-        let candidate2 = Address::generate(&env);
+        env.storage().instance().set(&DataKey::Paused(scope), &true);
+    }
 
-        client.initialize(&admin);
+    /// Reanuda un ámbito de operación pausado (administrador o guardián)
+    fn unpause(env: Env, caller: Address, scope: PauseScope) {
+        Self::require_admin_or_guardian(&env, &caller);
 
-        let mut candidates = Vec::new(&env);
-        candidates.push_back(candidate1.clone());
-        candidates.push_back(candidate2.clone());
+        env.storage().instance().remove(&DataKey::Paused(scope));
+    }
 
-        let survey_id = client.create_survey(
-            &creator,
-            &String::from_str(&env, "Test Survey"),
-            &String::from_str(&env, "Description"),
-            &1000,
-            &2000,
-            &candidates,
-        );
+    /// Verifica si un ámbito de operación está pausado
+    fn is_paused(env: Env, scope: PauseScope) -> bool {
+        let key = DataKey::Paused(scope);
+        env.storage().instance().get(&key).unwrap_or(false)
+    }
 
-        assert_eq!(survey_id, 1);
-        assert_eq!(client.get_survey_count(), 1);
+    /// Reemplaza el código del contrato conservando su almacenamiento (solo administrador)
+    /// Debe seguirse de `migrate` si la nueva versión cambia el formato de los datos
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
 
-        let survey = client.get_survey(&survey_id);
-        assert!(survey.is_some());
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
-    #[test]
-    fn test_voting() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, SurveyContract);
-        let client = SurveyContractClient::new(&env, &contract_id);
+    /// Migra el almacenamiento a la versión de este código (solo administrador)
+    /// Sin efecto si el almacenamiento ya está en esa versión
+    /// Reescribe a lo sumo `limit` encuestas por llamada y guarda el avance; se
+    /// repite hasta que retorne true. Conviene pausar Voting y SurveyCreation mientras tanto
+    fn migrate(env: Env, limit: u32) -> bool {
+        Self::require_admin(&env);
 
-        let admin = Address::generate(&env);
-        let creator = Address::generate(&env);
-        let voter1 = Address::generate(&env);
-        let voter2 = Address::generate(&env);
-        let candidate1 = Address::generate(&env);
-        let candidate2 = Address::generate(&env);
+        let version = Self::version(env.clone());
+        if version >= CONTRACT_VERSION {
+            return true; // Ya está al día; repetir la llamada no es un error
+        }
 
-        client.initialize(&admin);
+        // v1 -> v2: las encuestas incorporan `created_at`. El rango se fija en la
+        // primera llamada; las encuestas creadas después ya usan el formato nuevo
+        let progress_key = DataKey::MigrationProgress;
+        let (mut next, last): (u64, u64) = env.storage().instance()
+            .get(&progress_key)
+            .unwrap_or((1, Self::get_survey_count(env.clone())));
+        let stop = next.saturating_add(limit as u64).min(last + 1);
+        while next < stop {
+            let survey_key = DataKey::Survey(next);
+            next += 1;
+            let old: SurveyV1 = match env.storage().persistent().get(&survey_key) {
+                Some(old) => old,
+                None => continue,
+            };
+            let survey = Survey {
+                survey_id: old.survey_id,
+                creator: old.creator,
+                name: old.name,
+                description: old.description,
+                start_date: old.start_date,
+                end_date: old.end_date,
+                candidates: old.candidates,
+                created_at: 0, // Desconocido para encuestas anteriores
+            };
+            Self::set_persistent(&env, &survey_key, &survey);
+        }
 
-        let mut candidates = Vec::new(&env);
-        candidates.push_back(candidate1.clone());
-        candidates.push_back(candidate2.clone());
+        if next <= last {
+            env.storage().instance().set(&progress_key, &(next, last));
+            return false;
+        }
+        env.storage().instance().remove(&progress_key);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        true
+    }
 
-        // Crear encuesta con fechas que incluyen el tiempo actual
-        env.ledger().set_timestamp(1500);
-        
-        let survey_id = client.create_survey(
-            &creator,
-            &String::from_str(&env, "Voting Test"),
-            &String::from_str(&env, "Test Description"),
-            &1000,
-            &3000,
-            &candidates,
-        );
+    /// Obtiene la versión del formato de almacenamiento
+    fn version(env: Env) -> u32 {
+        let key = DataKey::ContractVersion;
+        env.storage().instance().get(&key).unwrap_or(1)
+    }
 
-        // Votar
-        let vote_result = client.vote(&survey_id, &voter1, &candidate1);
-        assert!(vote_result);
+    /// Define el electorado de una encuesta antes de que inicie (solo creador)
+    fn set_eligibility(env: Env, survey_id: u64, eligibility: Eligibility) {
+        let survey = Self::require_draft_creator(&env, survey_id);
 
-        // Verificar que votó
-        assert!(client.has_voted(&survey_id, &voter1));
+        let key = DataKey::Eligibility(survey.survey_id);
+        Self::set_persistent(&env, &key, &eligibility);
+    }
 
-        // Votar con otro usuario
-        client.vote(&survey_id, &voter2, &candidate2);
+    /// Agrega votantes a la lista de elegibles de una encuesta Allowlist (solo creador)
+    fn add_eligible_voters(env: Env, survey_id: u64, voters: Vec<Address>) {
+        Self::require_draft_creator(&env, survey_id);
 
-        // Verificar resultados
-        let results = client.get_results(&survey_id);
-        assert_eq!(results.len(), 2);
-        assert_eq!(client.get_total_votes(&survey_id), 2);
+        if Self::get_eligibility(env.clone(), survey_id) != Eligibility::Allowlist {
+            panic!("Survey does not use an allowlist");
+        }
+        for voter in voters.iter() {
+            let key = DataKey::Eligible(survey_id, voter);
+            Self::set_persistent(&env, &key, &true);
+        }
     }
 
-    #[test]
-    #[should_panic(expected = "Voter has already voted")]
-    fn test_double_voting() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, SurveyContract);
-        let client = SurveyContractClient::new(&env, &contract_id);
+    /// Obtiene el electorado de una encuesta
+    fn get_eligibility(env: Env, survey_id: u64) -> Eligibility {
+        let key = DataKey::Eligibility(survey_id);
+        env.storage().persistent().get(&key).unwrap_or(Eligibility::Open)
+    }
 
-        let admin = Address::generate(&env);
-        let creator = Address::generate(&env);
-        let voter = Address::generate(&env);
-        let candidate1 = Address::generate(&env);
-        let candidate2 = Address::generate(&env);
+    /// Verifica si un votante es elegible en una encuesta
+    /// Las encuestas MerkleRoot requieren la prueba de inclusión del votante
+    fn is_eligible(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> bool {
+        match Self::get_eligibility(env.clone(), survey_id) {
+            Eligibility::Open => true,
+            Eligibility::Allowlist => {
+                let key = DataKey::Eligible(survey_id, voter);
+                env.storage().persistent().get(&key).unwrap_or(false)
+            }
+            Eligibility::MerkleRoot(root) => match proof {
+                Some(proof) => Self::verify_merkle_proof(&env, &root, &voter, &proof),
+                None => false,
+            },
+        }
+    }
 
-        client.initialize(&admin);
+    /// Delega el voto en otra dirección, para una encuesta o globalmente
+    /// Si el delegado vota, su voto cuenta también por el delegante;
+    /// el voto directo del delegante siempre reemplaza la delegación
+    fn delegate(env: Env, delegator: Address, delegate: Address, scope: DelegationScope) {
+        delegator.require_auth();
+        Self::require_not_paused(&env, PauseScope::Voting);
+
+        if delegator == delegate {
+            panic!("Cannot delegate to self");
+        }
+        if let Some(registries) = Self::get_registries(env.clone()) {
+            if !UserRegistryClient::new(&env, &registries.user_registry).user_exists(&delegator) {
+                panic_with_error!(&env, SurveyError::NotRegistered);
+            }
+        }
+
+        // Recorrer la cadena del delegado para detectar ciclos y limitar su longitud
+        let survey_id = match scope {
+            DelegationScope::Global => None,
+            DelegationScope::Survey(survey_id) => Some(survey_id),
+        };
+        let mut current = delegate.clone();
+        let mut depth = 1;
+        while let Some(next) = Self::effective_delegate(&env, &current, survey_id) {
+            if next == delegator {
+                panic!("Delegation cycle detected");
+            }
+            depth += 1;
+            if depth > MAX_DELEGATION_DEPTH {
+                panic!("Delegation chain too deep");
+            }
+            current = next;
+        }
+
+        Self::remove_delegation(&env, &delegator, &scope);
+
+        let key = DataKey::Delegation(delegator.clone(), scope.clone());
+        Self::set_persistent(&env, &key, &delegate);
+
+        let delegators_key = DataKey::Delegators(delegate, scope);
+        let mut delegators: Vec<Address> = env.storage().persistent()
+            .get(&delegators_key)
+            .unwrap_or(Vec::new(&env));
+        if delegators.len() >= MAX_DELEGATORS {
+            panic!("Delegate has too many delegators");
+        }
+        delegators.push_back(delegator);
+        Self::set_persistent(&env, &delegators_key, &delegators);
+    }
+
+    /// Revoca una delegación; los votos ya emitidos por el delegado no cambian
+    fn undelegate(env: Env, delegator: Address, scope: DelegationScope) {
+        delegator.require_auth();
+        Self::require_not_paused(&env, PauseScope::Voting);
+
+        if !Self::remove_delegation(&env, &delegator, &scope) {
+            panic!("Delegation not found");
+        }
+    }
+
+    /// Obtiene el delegado de una dirección para un alcance (si existe)
+    fn get_delegate(env: Env, delegator: Address, scope: DelegationScope) -> Option<Address> {
+        let key = DataKey::Delegation(delegator, scope);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Obtiene quién emitió el voto delegado de una dirección en una encuesta
+    fn get_cast_by(env: Env, survey_id: u64, delegator: Address) -> Option<Address> {
+        let key = DataKey::CastBy(survey_id, delegator);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Cambia la política de creación de encuestas (solo administrador)
+    fn set_creation_policy(env: Env, policy: CreationPolicy) {
+        Self::require_admin(&env);
+
+        if let CreationPolicy::Deposit(config) = &policy {
+            if config.amount <= 0 {
+                panic!("Deposit amount must be positive");
+            }
+        }
+        env.storage().instance().set(&DataKey::CreationPolicy, &policy);
+    }
+
+    /// Autoriza a una dirección para crear encuestas (solo administrador)
+    fn add_creator(env: Env, creator: Address) {
+        Self::require_admin(&env);
+
+        let key = DataKey::Creator(creator);
+        Self::set_persistent(&env, &key, &true);
+    }
+
+    /// Revoca la autorización de creación de una dirección (solo administrador)
+    fn remove_creator(env: Env, creator: Address) {
+        Self::require_admin(&env);
+
+        let key = DataKey::Creator(creator);
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Obtiene la política de creación vigente
+    fn get_creation_policy(env: Env) -> CreationPolicy {
+        let key = DataKey::CreationPolicy;
+        env.storage().instance().get(&key).unwrap_or(CreationPolicy::Open)
+    }
+
+    /// Verifica si una dirección está autorizada para crear encuestas
+    fn is_allowed_creator(env: Env, creator: Address) -> bool {
+        let key = DataKey::Creator(creator);
+        env.storage().persistent().get(&key).unwrap_or(false)
+    }
+
+    /// Obtiene el depósito pendiente de una encuesta (si existe)
+    fn get_deposit(env: Env, survey_id: u64) -> Option<SurveyDeposit> {
+        let key = DataKey::Deposit(survey_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Verifica si una encuesta ya fue finalizada
+    fn is_finalized(env: Env, survey_id: u64) -> bool {
+        let key = DataKey::Finalized(survey_id);
+        env.storage().persistent().has(&key)
+    }
+
+    /// Obtiene el certificado de resultados de una encuesta finalizada
+    fn get_certificate(env: Env, survey_id: u64) -> Option<ResultCertificate> {
+        let key = DataKey::Certificate(survey_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Define o actualiza los metadatos de una encuesta (solo creador, antes de que inicie)
+    fn update_metadata(env: Env, survey_id: u64, metadata: SurveyMetadata) {
+        let survey = Self::require_draft_creator(&env, survey_id);
+        Self::validate_metadata(&env, &metadata);
+
+        let key = DataKey::Metadata(survey.survey_id);
+        Self::set_persistent(&env, &key, &metadata);
+
+        MetadataUpdated {
+            survey_id,
+            category: metadata.category,
+            tags: metadata.tags,
+            metadata_uri: metadata.metadata_uri,
+            content_hash: metadata.content_hash,
+        }
+        .publish(&env);
+    }
+
+    /// Obtiene los metadatos de una encuesta (si existen)
+    fn get_metadata(env: Env, survey_id: u64) -> Option<SurveyMetadata> {
+        let key = DataKey::Metadata(survey_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Obtiene una encuesta por su ID
+    fn get_survey(env: Env, survey_id: u64) -> Option<Survey> {
+        let key = DataKey::Survey(survey_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Verifica si un votante ya votó (o respondió el cuestionario) en una encuesta
+    fn has_voted(env: Env, survey_id: u64, voter: Address) -> bool {
+        let storage = env.storage().persistent();
+        storage.has(&DataKey::Vote(survey_id, voter.clone()))
+            || storage.has(&DataKey::Response(survey_id, voter))
+    }
+
+    /// Obtiene el candidato por el que votó un usuario (si ya votó)
+    /// Falla con ResultsHidden mientras la política de la encuesta oculte los resultados
+    fn get_vote(env: Env, survey_id: u64, voter: Address) -> Option<Address> {
+        Self::require_results_visible(&env, survey_id);
+        let key = DataKey::Vote(survey_id, voter);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Obtiene los resultados de una encuesta
+    /// Falla con ResultsHidden mientras la política de la encuesta oculte los resultados
+    fn get_results(env: Env, survey_id: u64) -> Vec<VoteResult> {
+        Self::require_results_visible(&env, survey_id);
+        Self::tally(&env, survey_id)
+    }
+
+    /// Resultados para un lector autorizado: cualquiera si ya son visibles, y antes
+    /// de tiempo el creador o un auditor si la encuesta lo permite
+    fn get_results_as(env: Env, survey_id: u64, viewer: Address) -> Vec<VoteResult> {
+        viewer.require_auth();
+        Self::require_results_visible_to(&env, survey_id, &viewer);
+        Self::tally(&env, survey_id)
+    }
+
+    /// Voto de un usuario para un lector autorizado; el propio votante siempre puede leerlo
+    fn get_vote_as(env: Env, survey_id: u64, voter: Address, viewer: Address) -> Option<Address> {
+        viewer.require_auth();
+        if viewer != voter {
+            Self::require_results_visible_to(&env, survey_id, &viewer);
+        }
+        let key = DataKey::Vote(survey_id, voter);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Define cuándo se publican los resultados (solo el creador, antes del inicio)
+    /// Con privileged_access el creador y los auditores pueden leerlos antes con get_results_as.
+    /// Fuera de Live, los eventos omiten el candidato y los conteos hasta finalizar;
+    /// el almacenamiento sigue siendo legible en la cadena
+    fn set_results_visibility(
+        env: Env,
+        survey_id: u64,
+        visibility: ResultsVisibility,
+        privileged_access: bool,
+    ) {
+        Self::require_draft_creator(&env, survey_id);
+
+        let key = DataKey::ResultsPolicy(survey_id);
+        Self::set_persistent(&env, &key, &ResultsPolicy { visibility, privileged_access });
+    }
+
+    /// Obtiene la política de resultados de una encuesta (Live por defecto)
+    fn get_results_policy(env: Env, survey_id: u64) -> ResultsPolicy {
+        env.storage().persistent()
+            .get(&DataKey::ResultsPolicy(survey_id))
+            .unwrap_or(ResultsPolicy {
+                visibility: ResultsVisibility::Live,
+                privileged_access: false,
+            })
+    }
+
+    /// Otorga el rol de auditor (solo administrador)
+    fn add_auditor(env: Env, auditor: Address) {
+        Self::require_admin(&env);
+
+        let key = DataKey::Auditor(auditor);
+        Self::set_persistent(&env, &key, &true);
+    }
+
+    /// Retira el rol de auditor (solo administrador)
+    fn remove_auditor(env: Env, auditor: Address) {
+        Self::require_admin(&env);
+
+        env.storage().persistent().remove(&DataKey::Auditor(auditor));
+    }
+
+    /// Verifica si una dirección tiene el rol de auditor
+    fn is_auditor(env: Env, auditor: Address) -> bool {
+        let key = DataKey::Auditor(auditor);
+        env.storage().persistent().get(&key).unwrap_or(false)
+    }
+
+    /// Aprueba un contrato de urna para `set_ballot_box` (solo administrador)
+    fn add_ballot_box_contract(env: Env, ballot_box: Address) {
+        Self::require_admin(&env);
+
+        let key = DataKey::BallotBoxContract(ballot_box);
+        Self::set_persistent(&env, &key, &true);
+    }
+
+    /// Retira la aprobación de un contrato de urna (solo administrador)
+    /// Las encuestas que ya lo usan lo conservan
+    fn remove_ballot_box_contract(env: Env, ballot_box: Address) {
+        Self::require_admin(&env);
+
+        env.storage().persistent().remove(&DataKey::BallotBoxContract(ballot_box));
+    }
+
+    /// Delega la recepción de votos en una urna aprobada, p. ej. una urna cifrada
+    /// (solo el creador, antes del inicio). Desde entonces `vote` se rechaza, el
+    /// conteo llega al cierre con `record_tally` y la encuesta no cobra fees
+    fn set_ballot_box(env: Env, survey_id: u64, ballot_box: Address) {
+        let survey = Self::require_draft_creator(&env, survey_id);
+        if !env.storage().persistent().has(&DataKey::BallotBoxContract(ballot_box.clone())) {
+            panic!("Ballot box contract is not approved");
+        }
+        if survey.candidates.is_empty() {
+            panic!("Ballot boxes need candidates");
+        }
+        let fee: Option<SurveyFee> = env.storage().persistent().get(&DataKey::SurveyFee(survey_id));
+        if fee.is_some_and(|fee| fee.amount > 0) {
+            panic!("Ballot box surveys cannot charge vote fees");
+        }
+        let pool = Self::get_prize_pool(env.clone(), survey_id);
+        if pool.is_some_and(|pool| pool.distribution == PrizeDistribution::Participants) {
+            panic!("Ballot box surveys cannot have participant prizes");
+        }
+
+        let key = DataKey::BallotBox(survey_id);
+        Self::set_persistent(&env, &key, &ballot_box);
+    }
+
+    /// Obtiene el contrato que recibe los votos de una encuesta (si lo hay)
+    fn get_ballot_box(env: Env, survey_id: u64) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::BallotBox(survey_id))
+    }
+
+    /// Registra el conteo de la urna externa, una vez cerrada la encuesta (solo la urna)
+    /// `counts` sigue el orden de los candidatos; `voters` se suma al padrón
+    fn record_tally(env: Env, survey_id: u64, counts: Vec<u64>, voters: Vec<Address>) {
+        let ballot_box = Self::get_ballot_box(env.clone(), survey_id)
+            .expect("Survey has no ballot box");
+        ballot_box.require_auth();
+        Self::require_not_paused(&env, PauseScope::Voting);
+
+        let survey = Self::get_survey(env.clone(), survey_id).expect("Survey not found");
+        if env.ledger().timestamp() <= survey.end_date {
+            panic!("Survey has not ended yet");
+        }
+        if Self::is_finalized(env.clone(), survey_id) || Self::is_cancelled(env.clone(), survey_id) {
+            panic!("Survey is closed");
+        }
+        if counts.len() != survey.candidates.len() {
+            panic!("Counts do not match candidates");
+        }
+        let recorded_key = DataKey::TallyRecorded(survey_id);
+        if env.storage().persistent().has(&recorded_key) {
+            panic!("Tally already recorded");
+        }
+        let ballots: u64 = counts.iter().sum();
+        Self::set_persistent(&env, &recorded_key, &ballots);
+
+        let live = Self::live_results(&env, survey_id);
+        for (candidate, votes) in survey.candidates.iter().zip(counts.iter()) {
+            let count_key = DataKey::VoteCount(survey_id, candidate.clone());
+            Self::set_persistent(&env, &count_key, &votes);
+            if live {
+                TallyChanged { survey_id, candidate, delta: votes as i64 }.publish(&env);
+            }
+        }
+        for voter in voters.iter() {
+            Self::add_voter(&env, survey_id, voter);
+        }
+    }
+
+    /// Emite el recibo de una papeleta recibida por la urna externa (solo la urna)
+    /// `commitment` es el hash de la papeleta, que así no se revela
+    fn record_receipt(env: Env, survey_id: u64, voter: Address, commitment: BytesN<32>) -> BytesN<32> {
+        let ballot_box = Self::get_ballot_box(env.clone(), survey_id)
+            .expect("Survey has no ballot box");
+        ballot_box.require_auth();
+        Self::require_not_paused(&env, PauseScope::Voting);
+
+        Self::issue_receipt(&env, survey_id, &voter, &commitment)
+    }
+
+    /// Indica si un recibo forma parte de la cadena de recibos de la encuesta
+    fn verify_receipt(env: Env, survey_id: u64, receipt: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::Receipt(survey_id, receipt))
+    }
+
+    /// Obtiene el acumulador de recibos de una encuesta
+    fn get_receipt_chain(env: Env, survey_id: u64) -> ReceiptChain {
+        env.storage().persistent()
+            .get(&DataKey::ReceiptChain(survey_id))
+            .unwrap_or(ReceiptChain { head: BytesN::from_array(&env, &[0; 32]), length: 0 })
+    }
+
+    /// Obtiene las preguntas de un cuestionario (vacío si es una encuesta de candidatos)
+    fn get_questions(env: Env, survey_id: u64) -> Vec<Question> {
+        let key = DataKey::Questions(survey_id);
+        env.storage().persistent().get(&key).unwrap_or(Vec::new(&env))
+    }
+
+    /// Obtiene las opciones elegidas por un votante (si ya respondió)
+    fn get_response(env: Env, survey_id: u64, voter: Address) -> Option<Vec<u32>> {
+        Self::require_results_visible(&env, survey_id);
+        let key = DataKey::Response(survey_id, voter);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Obtiene el conteo de cada opción de una pregunta, en el orden de sus opciones
+    fn get_question_results(env: Env, survey_id: u64, question: u32) -> Vec<OptionResult> {
+        Self::require_results_visible(&env, survey_id);
+        let questions = Self::get_questions(env.clone(), survey_id);
+        let question_data = questions.get(question).expect("Question not found");
+        Self::option_results(&env, survey_id, question, question_data)
+    }
+
+    /// Obtiene el total de votos en una encuesta
+    /// Cuenta papeletas emitidas, no votos delegados: con delegación la suma de
+    /// get_results puede ser mayor, porque cada papeleta arrastra a sus delegantes
+    /// Con urna externa cuentan sus papeletas, aunque no revele a los votantes
+    /// Falla con ResultsHidden mientras la política de la encuesta oculte los resultados
+    fn get_total_votes(env: Env, survey_id: u64) -> u64 {
+        Self::require_results_visible(&env, survey_id);
+        Self::ballot_count(&env, survey_id)
+    }
+
+    /// Obtiene los votantes directos de una encuesta, en orden de emisión
+    /// Falla con ResultsHidden mientras la política de la encuesta oculte los resultados
+    fn get_voters(env: Env, survey_id: u64) -> Vec<Address> {
+        Self::require_results_visible(&env, survey_id);
+        Self::voter_list(&env, survey_id)
+    }
+
+    /// Obtiene el número total de encuestas
+    fn get_survey_count(env: Env) -> u64 {
+        let key = DataKey::SurveyCount;
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Obtiene el fee de votación en stroops
+    fn get_vote_fee(env: Env) -> i128 {
+        let key = DataKey::VoteFee;
+        env.storage().instance().get(&key).unwrap_or(VOTE_FEE_STROOPS)
+    }
+
+    /// Cede el control del fee de votación a un contrato de gobernanza. La primera vez
+    /// lo autoriza el administrador; después solo la gobernanza vigente puede cambiarla
+    fn set_governance(env: Env, governance: Address) {
+        match Self::get_governance(env.clone()) {
+            Some(current) => current.require_auth(),
+            None => {
+                Self::require_admin(&env);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::Governance, &governance);
+    }
+
+    /// Obtiene el contrato de gobernanza (si existe)
+    fn get_governance(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Governance)
+    }
+
+    /// Cambia el fee de votación en stroops; lo autoriza el contrato de gobernanza
+    /// si está definido y, si no, el administrador
+    fn set_vote_fee(env: Env, fee: i128) {
+        match Self::get_governance(env.clone()) {
+            Some(governance) => governance.require_auth(),
+            None => {
+                Self::require_admin(&env);
+            }
+        }
+        if fee < 0 {
+            panic!("Vote fee cannot be negative");
+        }
+
+        env.storage().instance().set(&DataKey::VoteFee, &fee);
+        VoteFeeChanged { fee }.publish(&env);
+    }
+
+    /// Define el token en que se cobra el fee de votación por defecto (solo administrador)
+    /// Sin token, las encuestas sin fee propio son gratuitas
+    fn set_fee_token(env: Env, token: Address) {
+        Self::require_admin(&env);
+
+        env.storage().instance().set(&DataKey::FeeToken, &token);
+    }
+
+    /// Obtiene el token del fee de votación por defecto (si existe)
+    fn get_fee_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::FeeToken)
+    }
+
+    /// Define el fee por voto y su token para una encuesta (solo creador, antes de que inicie)
+    /// Un monto 0 hace la encuesta gratuita aunque exista un fee por defecto
+    fn set_survey_fee(env: Env, survey_id: u64, token: Address, amount: i128) {
+        Self::require_draft_creator(&env, survey_id);
+
+        if amount < 0 {
+            panic!("Vote fee cannot be negative");
+        }
+        if amount > 0 && Self::get_ballot_box(env.clone(), survey_id).is_some() {
+            panic!("Ballot box surveys cannot charge vote fees");
+        }
+        let key = DataKey::SurveyFee(survey_id);
+        Self::set_persistent(&env, &key, &SurveyFee { token, amount });
+    }
+
+    /// Obtiene el fee que paga cada votante de una encuesta: el propio de la
+    /// encuesta o, si no tiene, VoteFee en el token por defecto
+    /// Las encuestas con urna externa no cobran fee: sus votos no pasan por `vote`
+    fn get_survey_fee(env: Env, survey_id: u64) -> Option<SurveyFee> {
+        if Self::get_ballot_box(env.clone(), survey_id).is_some() {
+            return None;
+        }
+        let key = DataKey::SurveyFee(survey_id);
+        let fee: Option<SurveyFee> = env.storage().persistent().get(&key);
+        fee.or_else(|| {
+            let token = Self::get_fee_token(env.clone())?;
+            Some(SurveyFee { token, amount: Self::get_vote_fee(env.clone()) })
+        })
+    }
+
+    /// Exime de fees a un grupo de votantes en todas las encuestas (solo administrador)
+    fn add_fee_exempt(env: Env, voters: Vec<Address>) {
+        Self::require_admin(&env);
+
+        for voter in voters.iter() {
+            let key = DataKey::FeeExempt(voter);
+            Self::set_persistent(&env, &key, &true);
+        }
+    }
+
+    /// Retira la exención de fees de un votante (solo administrador)
+    fn remove_fee_exempt(env: Env, voter: Address) {
+        Self::require_admin(&env);
+
+        env.storage().persistent().remove(&DataKey::FeeExempt(voter));
+    }
+
+    /// Verifica si un votante está exento de fees
+    fn is_fee_exempt(env: Env, voter: Address) -> bool {
+        let key = DataKey::FeeExempt(voter);
+        env.storage().persistent().get(&key).unwrap_or(false)
+    }
+
+    /// Cancela una encuesta no finalizada (creador o administrador)
+    /// Se detiene la votación y cada votante puede reclamar su fee con claim_refund;
+    /// el depósito de creación, si lo hay, pasa a la tesorería
+    fn cancel_survey(env: Env, survey_id: u64, caller: Address) {
+        caller.require_auth();
+        Self::require_not_paused(&env, PauseScope::Voting);
+
+        let survey_key = DataKey::Survey(survey_id);
+        let survey: Survey = env.storage().persistent()
+            .get(&survey_key)
+            .expect("Survey not found");
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if caller != survey.creator && admin.as_ref() != Some(&caller) {
+            panic_with_error!(&env, SurveyError::Unauthorized);
+        }
+        if Self::is_finalized(env.clone(), survey_id) {
+            panic!("Survey already finalized");
+        }
+        // Una vez abierta la votación, el fondo de premios ya está prometido a los votantes
+        if env.ledger().timestamp() >= survey.start_date && Self::get_prize_pool(env.clone(), survey_id).is_some() {
+            panic!("Survey with a prize pool cannot be cancelled once voting starts");
+        }
+
+        let cancelled_key = DataKey::Cancelled(survey_id);
+        if env.storage().persistent().has(&cancelled_key) {
+            panic!("Survey already cancelled");
+        }
+        Self::set_persistent(&env, &cancelled_key, &true);
+
+        let deposit: Option<SurveyDeposit> = Self::get_deposit(env.clone(), survey_id);
+        if let Some(deposit) = deposit {
+            env.storage().persistent().remove(&DataKey::Deposit(survey_id));
+            Self::credit_treasury(&env, &deposit.token, deposit.amount);
+        }
+
+        SurveyCancelled { survey_id, cancelled_by: caller }.publish(&env);
+    }
+
+    /// Verifica si una encuesta fue cancelada
+    fn is_cancelled(env: Env, survey_id: u64) -> bool {
+        let key = DataKey::Cancelled(survey_id);
+        env.storage().persistent().get(&key).unwrap_or(false)
+    }
+
+    /// Devuelve el fee del voto de un votante en una encuesta cancelada
+    /// Cualquiera puede llamarla; el reembolso siempre va a quien pagó el fee
+    /// (el votante o el relayer de vote_signed). Retorna el monto
+    fn claim_refund(env: Env, survey_id: u64, voter: Address) -> i128 {
+        Self::require_not_paused(&env, PauseScope::Funds);
+        if !Self::is_cancelled(env.clone(), survey_id) {
+            panic_with_error!(&env, SurveyError::NotCancelled);
+        }
+
+        let paid_key = DataKey::FeePaid(survey_id, voter.clone());
+        let paid: SurveyFee = match env.storage().persistent().get(&paid_key) {
+            Some(paid) => paid,
+            None => panic_with_error!(&env, SurveyError::NothingToRefund),
+        };
+        env.storage().persistent().remove(&paid_key);
+        Self::record_revenue(&env, survey_id, &paid.token, -paid.amount);
+
+        let payer_key = DataKey::FeePayer(survey_id, voter.clone());
+        let payer: Address = env.storage().persistent().get(&payer_key).unwrap_or(voter.clone());
+        env.storage().persistent().remove(&payer_key);
+
+        let token_client = token::Client::new(&env, &paid.token);
+        token_client.transfer(&env.current_contract_address(), &payer, &paid.amount);
+        FeeRefunded { survey_id, voter, token: paid.token, amount: paid.amount }.publish(&env);
+
+        paid.amount
+    }
+
+    /// Define qué parte de los fees recibe el creador de cada encuesta nueva, en
+    /// puntos básicos (solo administrador); el resto va a la tesorería
+    fn set_creator_share(env: Env, creator_bps: u32) {
+        Self::require_admin(&env);
+
+        if creator_bps > MAX_BPS {
+            panic!("Creator share cannot exceed 10000 basis points");
+        }
+        env.storage().instance().set(&DataKey::CreatorShareBps, &creator_bps);
+    }
+
+    /// Obtiene la parte de los fees para los creadores de encuestas nuevas
+    fn get_creator_share(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::CreatorShareBps).unwrap_or(0)
+    }
+
+    /// Obtiene el libro de fees de una encuesta, una entrada por token
+    fn get_survey_revenue(env: Env, survey_id: u64) -> Vec<SurveyRevenue> {
+        let key = DataKey::Revenue(survey_id);
+        env.storage().persistent().get(&key).unwrap_or(Vec::new(&env))
+    }
+
+    /// Obtiene el saldo de la plataforma disponible para retirar en un token
+    fn get_treasury_balance(env: Env, token: Address) -> i128 {
+        let key = DataKey::Treasury(token);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Retira fondos de la tesorería (solo administrador); nunca toca los fees
+    /// pendientes, la parte de los creadores ni los depósitos en garantía
+    fn withdraw(env: Env, token: Address, to: Address, amount: i128) {
+        Self::require_admin(&env);
+        Self::require_not_paused(&env, PauseScope::Funds);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        if amount > Self::get_treasury_balance(env.clone(), token.clone()) {
+            panic_with_error!(&env, SurveyError::InsufficientTreasury);
+        }
+        Self::credit_treasury(&env, &token, -amount);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &to, &amount);
+        TreasuryWithdrawn { token, to, amount }.publish(&env);
+    }
+
+    /// Transfiere al creador su parte de los fees de una encuesta finalizada
+    /// Retorna los montos transferidos por token
+    fn claim_creator_revenue(env: Env, survey_id: u64) -> Vec<TokenAmount> {
+        let survey = Self::get_survey(env.clone(), survey_id).expect("Survey not found");
+        survey.creator.require_auth();
+        Self::require_not_paused(&env, PauseScope::Funds);
+
+        if !Self::is_finalized(env.clone(), survey_id) {
+            panic_with_error!(&env, SurveyError::NotFinalized);
+        }
+
+        let mut revenue = Self::get_survey_revenue(env.clone(), survey_id);
+        let mut claimed = Vec::new(&env);
+        for i in 0..revenue.len() {
+            let mut entry = revenue.get_unchecked(i);
+            if entry.creator_claimed || entry.creator_share == 0 {
+                continue;
+            }
+            entry.creator_claimed = true;
+            revenue.set(i, entry.clone());
+            claimed.push_back(TokenAmount { token: entry.token, amount: entry.creator_share });
+        }
+        if claimed.is_empty() {
+            panic_with_error!(&env, SurveyError::NothingToClaim);
+        }
+        let key = DataKey::Revenue(survey_id);
+        Self::set_persistent(&env, &key, &revenue);
+
+        for payout in claimed.iter() {
+            let token_client = token::Client::new(&env, &payout.token);
+            token_client.transfer(&env.current_contract_address(), &survey.creator, &payout.amount);
+            CreatorRevenueClaimed {
+                survey_id,
+                creator: survey.creator.clone(),
+                token: payout.token,
+                amount: payout.amount,
+            }
+            .publish(&env);
+        }
+        claimed
+    }
+
+    /// Deposita un fondo de premios en la encuesta (solo creador, antes de que inicie)
+    /// Al finalizar se reparte según `distribution`; lo no reclamado en
+    /// `claim_period` segundos vuelve al creador con reclaim_prize_pool
+    fn fund_prize_pool(
+        env: Env,
+        survey_id: u64,
+        token: Address,
+        amount: i128,
+        distribution: PrizeDistribution,
+        claim_period: u64,
+    ) {
+        let survey = Self::require_draft_creator(&env, survey_id);
+        Self::require_not_paused(&env, PauseScope::Funds);
+
+        if amount <= 0 {
+            panic!("Prize pool must be positive");
+        }
+        if distribution == PrizeDistribution::Winner && survey.candidates.is_empty() {
+            panic!("Winner prizes need candidates");
+        }
+        // Las papeletas de una urna externa no dejan votantes que puedan reclamar
+        if distribution == PrizeDistribution::Participants && Self::get_ballot_box(env.clone(), survey_id).is_some() {
+            panic!("Ballot box surveys cannot have participant prizes");
+        }
+        let pool_key = DataKey::PrizePool(survey_id);
+        if env.storage().persistent().has(&pool_key) {
+            panic!("Prize pool already funded");
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&survey.creator, env.current_contract_address(), &amount);
+
+        let pool = PrizePool {
+            funder: survey.creator,
+            token: token.clone(),
+            amount,
+            distribution,
+            claim_period,
+            claim_deadline: 0,
+            reward: 0,
+            paid: 0,
+            reclaimed: false,
+        };
+        Self::set_persistent(&env, &pool_key, &pool);
+
+        PrizePoolFunded { survey_id, token, amount, distribution }.publish(&env);
+    }
+
+    /// Obtiene el fondo de premios de una encuesta (si existe)
+    fn get_prize_pool(env: Env, survey_id: u64) -> Option<PrizePool> {
+        let key = DataKey::PrizePool(survey_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Entrega a un votante directo su parte de un fondo Participants
+    /// Cualquiera puede llamarla; el premio siempre va al votante. Retorna el monto
+    fn claim_reward(env: Env, survey_id: u64, voter: Address) -> i128 {
+        Self::require_not_paused(&env, PauseScope::Funds);
+        let mut pool = Self::load_prize_pool(&env, survey_id);
+        if !Self::is_finalized(env.clone(), survey_id) {
+            panic_with_error!(&env, SurveyError::NotFinalized);
+        }
+        if env.ledger().timestamp() > pool.claim_deadline {
+            panic_with_error!(&env, SurveyError::ClaimPeriodOver);
+        }
+
+        let claimed_key = DataKey::RewardClaimed(survey_id, voter.clone());
+        if pool.distribution != PrizeDistribution::Participants
+            || pool.reward == 0
+            || !Self::has_voted(env.clone(), survey_id, voter.clone())
+            || env.storage().persistent().has(&claimed_key)
+        {
+            panic_with_error!(&env, SurveyError::NothingToClaim);
+        }
+        Self::set_persistent(&env, &claimed_key, &true);
+
+        pool.paid += pool.reward;
+        Self::save_prize_pool(&env, survey_id, &pool);
+        Self::pay_reward(&env, survey_id, &pool.token, &voter, pool.reward);
+        pool.reward
+    }
+
+    /// Devuelve al creador lo que queda del fondo de premios (premios no
+    /// reclamados y restos del redondeo) una vez vencido el plazo de reclamo,
+    /// o en cualquier momento si la encuesta fue cancelada. Retorna el monto
+    fn reclaim_prize_pool(env: Env, survey_id: u64) -> i128 {
+        Self::require_not_paused(&env, PauseScope::Funds);
+        let mut pool = Self::load_prize_pool(&env, survey_id);
+        if pool.reclaimed {
+            panic_with_error!(&env, SurveyError::NothingToClaim);
+        }
+        if !Self::is_cancelled(env.clone(), survey_id) {
+            if !Self::is_finalized(env.clone(), survey_id) {
+                panic_with_error!(&env, SurveyError::NotFinalized);
+            }
+            if env.ledger().timestamp() <= pool.claim_deadline {
+                panic_with_error!(&env, SurveyError::ClaimPeriodActive);
+            }
+        }
+
+        let remaining = pool.amount - pool.paid;
+        pool.reclaimed = true;
+        Self::save_prize_pool(&env, survey_id, &pool);
+
+        if remaining > 0 {
+            let token_client = token::Client::new(&env, &pool.token);
+            token_client.transfer(&env.current_contract_address(), &pool.funder, &remaining);
+        }
+        PrizePoolReclaimed { survey_id, funder: pool.funder, amount: remaining }.publish(&env);
+        remaining
+    }
+}
+
+impl SurveyContract {
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        admin
+    }
+
+    fn set_persistent<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(key, 100, 8640000);
+    }
+
+    fn require_admin_or_guardian(env: &Env, caller: &Address) {
+        caller.require_auth();
+
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        let guardian: Option<Address> = env.storage().instance().get(&DataKey::Guardian);
+        if admin.as_ref() != Some(caller) && guardian.as_ref() != Some(caller) {
+            panic_with_error!(env, SurveyError::Unauthorized);
+        }
+    }
+
+    // Valida y registra un voto ya autenticado (vote o vote_signed); `payer` paga el fee
+    fn cast_vote(
+        env: &Env,
+        survey_id: u64,
+        voter: Address,
+        candidate: Address,
+        payer: &Address,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32> {
+        Self::require_not_paused(env, PauseScope::Voting);
+
+        let survey = Self::get_survey(env.clone(), survey_id).expect("Survey not found");
+        Self::require_open(env, &survey);
+        if Self::get_ballot_box(env.clone(), survey_id).is_some() {
+            panic!("Survey uses an external ballot box");
+        }
+
+        // Verificar que el candidato esté en la lista
+        let mut candidate_is_valid = false;
+        for sc in survey.candidates.iter() {
+            if sc == candidate {
+                candidate_is_valid = true;
+                break;
+            }
+        }
+        if !candidate_is_valid {
+            panic!("Candidate is not in this survey");
+        }
+
+        Self::require_voter(env, survey_id, &voter, proof);
+
+        // Verificar que el votante no haya votado antes
+        let vote_key = DataKey::Vote(survey_id, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            panic!("Voter has already voted in this survey");
+        }
+
+        Self::charge_fee(env, survey_id, &voter, payer);
+
+        // Registrar el voto
+        Self::set_persistent(env, &vote_key, &candidate);
+        let shown = Self::live_results(env, survey_id).then(|| candidate.clone());
+        VoteCast { survey_id, voter: voter.clone(), candidate: shown }.publish(env);
+
+        // Acreditar el voto propio y el de los delegantes que aún no votaron
+        Self::credit_vote(env, survey_id, &voter, &candidate);
+
+        Self::add_voter(env, survey_id, voter.clone());
+        let commitment = env.crypto().sha256(&candidate.to_xdr(env)).to_bytes();
+        Self::issue_receipt(env, survey_id, &voter, &commitment)
+    }
+
+    // La encuesta debe estar dentro de sus fechas y no cancelada
+    fn require_open(env: &Env, survey: &Survey) {
+        let current_time = env.ledger().timestamp();
+        if current_time < survey.start_date {
+            panic!("Survey has not started yet");
+        }
+        if current_time > survey.end_date {
+            panic!("Survey has ended");
+        }
+        if Self::is_cancelled(env.clone(), survey.survey_id) {
+            panic!("Survey has been cancelled");
+        }
+    }
+
+    // El votante debe pertenecer al electorado y, si hay registros vinculados, estar registrado
+    fn require_voter(env: &Env, survey_id: u64, voter: &Address, proof: Option<Vec<BytesN<32>>>) {
+        if !Self::is_eligible(env.clone(), survey_id, voter.clone(), proof) {
+            panic_with_error!(env, SurveyError::NotEligible);
+        }
+        if let Some(registries) = Self::get_registries(env.clone()) {
+            if !UserRegistryClient::new(env, &registries.user_registry).user_exists(voter) {
+                panic_with_error!(env, SurveyError::NotRegistered);
+            }
+        }
+    }
+
+    // Fee que corresponde al voto de `voter`: ninguno si la encuesta es gratuita
+    // o el votante está exento
+    fn fee_due(env: &Env, survey_id: u64, voter: &Address) -> Option<SurveyFee> {
+        if Self::is_fee_exempt(env.clone(), voter.clone()) {
+            return None;
+        }
+        Self::get_survey_fee(env.clone(), survey_id).filter(|fee| fee.amount > 0)
+    }
+
+    // Cobra a `payer` el fee del voto de `voter` y lo registra para poder
+    // reembolsarlo si la encuesta se cancela
+    fn charge_fee(env: &Env, survey_id: u64, voter: &Address, payer: &Address) {
+        let fee = match Self::fee_due(env, survey_id, voter) {
+            Some(fee) => fee,
+            None => return,
+        };
+
+        let token_client = token::Client::new(env, &fee.token);
+        token_client.transfer(payer, env.current_contract_address(), &fee.amount);
+
+        let paid_key = DataKey::FeePaid(survey_id, voter.clone());
+        Self::set_persistent(env, &paid_key, &fee);
+        if payer != voter {
+            let payer_key = DataKey::FeePayer(survey_id, voter.clone());
+            Self::set_persistent(env, &payer_key, payer);
+        }
+        Self::record_revenue(env, survey_id, &fee.token, fee.amount);
+    }
+
+    // Suma (o resta, en reembolsos) fees al libro de la encuesta
+    fn record_revenue(env: &Env, survey_id: u64, token: &Address, delta: i128) {
+        let mut revenue = Self::get_survey_revenue(env.clone(), survey_id);
+        let index = revenue.iter().position(|entry| entry.token == *token);
+        match index {
+            Some(i) => {
+                let mut entry = revenue.get_unchecked(i as u32);
+                entry.collected += delta;
+                revenue.set(i as u32, entry);
+            }
+            None => revenue.push_back(SurveyRevenue {
+                token: token.clone(),
+                collected: delta,
+                platform_share: 0,
+                creator_share: 0,
+                creator_claimed: false,
+            }),
+        }
+        let key = DataKey::Revenue(survey_id);
+        Self::set_persistent(env, &key, &revenue);
+    }
+
+    // Reparte los fees de una encuesta finalizada; el redondeo favorece a la
+    // plataforma, de modo que platform_share + creator_share == collected
+    fn settle_revenue(env: &Env, survey_id: u64) {
+        let share_bps: u32 = env.storage().persistent()
+            .get(&DataKey::SurveyShareBps(survey_id))
+            .unwrap_or(0);
+        let mut revenue = Self::get_survey_revenue(env.clone(), survey_id);
+        for i in 0..revenue.len() {
+            let mut entry = revenue.get_unchecked(i);
+            entry.creator_share = entry.collected * share_bps as i128 / MAX_BPS as i128;
+            entry.platform_share = entry.collected - entry.creator_share;
+            Self::credit_treasury(env, &entry.token, entry.platform_share);
+            revenue.set(i, entry);
+        }
+        let key = DataKey::Revenue(survey_id);
+        Self::set_persistent(env, &key, &revenue);
+    }
+
+    // Fija el premio por votante o paga a los ganadores; el resto de la división
+    // entera queda en el fondo y vuelve al creador con reclaim_prize_pool
+    fn settle_prize_pool(env: &Env, survey_id: u64) {
+        let mut pool = match Self::get_prize_pool(env.clone(), survey_id) {
+            Some(pool) => pool,
+            None => return,
+        };
+        pool.claim_deadline = env.ledger().timestamp() + pool.claim_period;
+
+        match pool.distribution {
+            PrizeDistribution::Participants => {
+                // Reclaman los votantes directos; las encuestas con urna no tienen este reparto
+                let claimants = Self::voter_list(env, survey_id).len();
+                if claimants > 0 {
+                    pool.reward = pool.amount / claimants as i128;
+                }
+            }
+            PrizeDistribution::Winner => {
+                let results = Self::tally(env, survey_id);
+                let top = results.iter().map(|result| result.votes).max().unwrap_or(0);
+                let mut winners = Vec::new(env);
+                for result in results.iter() {
+                    if top > 0 && result.votes == top {
+                        winners.push_back(result.candidate);
+                    }
+                }
+                if !winners.is_empty() {
+                    pool.reward = pool.amount / winners.len() as i128;
+                    for winner in winners.iter() {
+                        Self::pay_reward(env, survey_id, &pool.token, &winner, pool.reward);
+                        pool.paid += pool.reward;
+                    }
+                }
+            }
+        }
+        Self::save_prize_pool(env, survey_id, &pool);
+    }
+
+    fn load_prize_pool(env: &Env, survey_id: u64) -> PrizePool {
+        match Self::get_prize_pool(env.clone(), survey_id) {
+            Some(pool) => pool,
+            None => panic_with_error!(env, SurveyError::NoPrizePool),
+        }
+    }
+
+    fn save_prize_pool(env: &Env, survey_id: u64, pool: &PrizePool) {
+        let key = DataKey::PrizePool(survey_id);
+        Self::set_persistent(env, &key, pool);
+    }
+
+    fn pay_reward(env: &Env, survey_id: u64, token: &Address, recipient: &Address, amount: i128) {
+        if amount == 0 {
+            return;
+        }
+        let token_client = token::Client::new(env, token);
+        token_client.transfer(&env.current_contract_address(), recipient, &amount);
+        RewardPaid { survey_id, recipient: recipient.clone(), amount }.publish(env);
+    }
+
+    fn credit_treasury(env: &Env, token: &Address, delta: i128) {
+        let key = DataKey::Treasury(token.clone());
+        let balance = Self::get_treasury_balance(env.clone(), token.clone());
+        Self::set_persistent(env, &key, &(balance + delta));
+    }
+
+    // Agrega un votante a la lista de la encuesta
+    // Papeletas emitidas: las de la urna externa si ya entregó su conteo, si no el padrón
+    fn ballot_count(env: &Env, survey_id: u64) -> u64 {
+        if let Some(ballots) = env.storage().persistent().get(&DataKey::TallyRecorded(survey_id)) {
+            return ballots;
+        }
+        Self::voter_list(env, survey_id).len() as u64
+    }
+
+    fn voter_list(env: &Env, survey_id: u64) -> Vec<Address> {
+        let voters_key = DataKey::VoterList(survey_id);
+        env.storage().persistent().get(&voters_key).unwrap_or(Vec::new(env))
+    }
+
+    fn add_voter(env: &Env, survey_id: u64, voter: Address) {
+        let mut voters = Self::voter_list(env, survey_id);
+        voters.push_back(voter);
+        Self::set_persistent(env, &DataKey::VoterList(survey_id), &voters);
+    }
+
+    // Aplica la política de creación y guarda una encuesta nueva; retorna su ID
+    // Valida una encuesta de candidatos ya autorizada y la guarda
+    fn new_survey(
+        env: &Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        candidates: Vec<Address>,
+    ) -> u64 {
+        Self::require_not_paused(env, PauseScope::SurveyCreation);
+
+        // Validaciones
+        if candidates.is_empty() {
+            panic!("Survey must have at least one candidate");
+        }
+        if start_date >= end_date {
+            panic!("Start date must be before end date");
+        }
+
+        // Con registros vinculados, cada candidato debe estar dado de alta
+        if let Some(registries) = Self::get_registries(env.clone()) {
+            let candidate_registry = CandidateRegistryClient::new(env, &registries.candidate_registry);
+            for candidate in candidates.iter() {
+                if !candidate_registry.candidate_exists(&candidate) {
+                    panic_with_error!(env, SurveyError::NotRegistered);
+                }
+            }
+        }
+
+        Self::store_survey(env, creator, name, description, start_date, end_date, candidates)
+    }
+
+    // Valida un cuestionario ya autorizado y lo guarda junto a sus preguntas
+    fn new_questionnaire(
+        env: &Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        questions: Vec<Question>,
+    ) -> u64 {
+        Self::require_not_paused(env, PauseScope::SurveyCreation);
+
+        // Validaciones
+        if questions.is_empty() {
+            panic!("Questionnaire must have at least one question");
+        }
+        if questions.len() > MAX_QUESTIONS {
+            panic!("Too many questions");
+        }
+        for question in questions.iter() {
+            if question.prompt.is_empty() {
+                panic!("Question prompt cannot be empty");
+            }
+            if question.options.len() < 2 {
+                panic!("Question must have at least two options");
+            }
+            if question.options.len() > MAX_OPTIONS {
+                panic!("Too many options");
+            }
+        }
+        if start_date >= end_date {
+            panic!("Start date must be before end date");
+        }
+
+        let survey_id = Self::store_survey(
+            env,
+            creator,
+            name,
+            description,
+            start_date,
+            end_date,
+            Vec::new(env),
+        );
+
+        let questions_key = DataKey::Questions(survey_id);
+        Self::set_persistent(env, &questions_key, &questions);
+
+        survey_id
+    }
+
+    fn require_scheduler(env: &Env) {
+        let scheduler: Address = env.storage().instance()
+            .get(&DataKey::Scheduler)
+            .expect("Scheduler not set");
+        scheduler.require_auth();
+    }
+
+    fn store_survey(
+        env: &Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        candidates: Vec<Address>,
+    ) -> u64 {
+        // Obtener el siguiente ID de encuesta
+        let count_key = DataKey::SurveyCount;
+        let survey_id: u64 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+
+        // Aplicar la política de creación vigente
+        match Self::get_creation_policy(env.clone()) {
+            CreationPolicy::Open => {}
+            CreationPolicy::Allowlist => {
+                if !Self::is_allowed_creator(env.clone(), creator.clone()) {
+                    panic!("Creator is not allowed to create surveys");
+                }
+            }
+            CreationPolicy::Deposit(config) => {
+                let token_client = token::Client::new(env, &config.token);
+                token_client.transfer(&creator, env.current_contract_address(), &config.amount);
+
+                let deposit = SurveyDeposit {
+                    depositor: creator.clone(),
+                    token: config.token,
+                    amount: config.amount,
+                    min_turnout: config.min_turnout,
+                };
+                let deposit_key = DataKey::Deposit(survey_id);
+                Self::set_persistent(env, &deposit_key, &deposit);
+            }
+        }
+
+        // Crear la encuesta
+        let survey = Survey {
+            survey_id,
+            creator: creator.clone(),
+            name,
+            description,
+            start_date,
+            end_date,
+            candidates: candidates.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+
+        // Guardar la encuesta
+        let survey_key = DataKey::Survey(survey_id);
+        Self::set_persistent(env, &survey_key, &survey);
+
+        // Fijar el reparto de fees vigente al crearla
+        let share_key = DataKey::SurveyShareBps(survey_id);
+        Self::set_persistent(env, &share_key, &Self::get_creator_share(env.clone()));
+
+        // Actualizar contador
+        Self::set_persistent(env, &count_key, &survey_id);
+
+        // Inicializar lista de votantes
+        let voters_key = DataKey::VoterList(survey_id);
+        Self::set_persistent(env, &voters_key, &Vec::<Address>::new(env));
+
+        // Inicializar contadores de votos para cada candidato
+        for candidate in candidates.iter() {
+            let vote_count_key = DataKey::VoteCount(survey_id, candidate.clone());
+            Self::set_persistent(env, &vote_count_key, &0u64);
+        }
+
+        SurveyCreated {
+            survey_id,
+            creator,
+            name: survey.name,
+            start_date,
+            end_date,
+            candidates,
+        }
+        .publish(env);
+
+        survey_id
+    }
+
+    // Conteo por candidato sin aplicar la política de visibilidad
+    fn tally(env: &Env, survey_id: u64) -> Vec<VoteResult> {
+        let survey_key = DataKey::Survey(survey_id);
+        let survey: Survey = env.storage().persistent()
+            .get(&survey_key)
+            .expect("Survey not found");
+
+        let mut results = Vec::new(env);
+
+        for candidate in survey.candidates.iter() {
+            let vote_count_key = DataKey::VoteCount(survey_id, candidate.clone());
+            let votes: u64 = env.storage().persistent()
+                .get(&vote_count_key)
+                .unwrap_or(0);
+
+            results.push_back(VoteResult {
+                candidate: candidate.clone(),
+                votes,
+            });
+        }
+
+        results
+    }
+
+    // Falla con ResultsHidden si la política aún oculta los resultados de la encuesta
+    fn require_results_visible(env: &Env, survey_id: u64) {
+        if !Self::results_visible(env, survey_id) {
+            panic_with_error!(env, SurveyError::ResultsHidden);
+        }
+    }
+
+    // Como require_results_visible, pero el creador y los auditores pasan si la
+    // encuesta concede acceso privilegiado
+    fn require_results_visible_to(env: &Env, survey_id: u64, viewer: &Address) {
+        if Self::results_visible(env, survey_id) {
+            return;
+        }
+        let policy = Self::get_results_policy(env.clone(), survey_id);
+        let privileged = policy.privileged_access
+            && (Self::get_survey(env.clone(), survey_id).is_some_and(|s| s.creator == *viewer)
+                || Self::is_auditor(env.clone(), viewer.clone()));
+        if !privileged {
+            panic_with_error!(env, SurveyError::ResultsHidden);
+        }
+    }
+
+    // Los eventos solo revelan votos y conteos en encuestas con resultados en vivo
+    fn live_results(env: &Env, survey_id: u64) -> bool {
+        Self::get_results_policy(env.clone(), survey_id).visibility == ResultsVisibility::Live
+    }
+
+    fn results_visible(env: &Env, survey_id: u64) -> bool {
+        match Self::get_results_policy(env.clone(), survey_id).visibility {
+            ResultsVisibility::Live => true,
+            ResultsVisibility::AfterClose => {
+                Self::is_finalized(env.clone(), survey_id)
+                    || Self::get_survey(env.clone(), survey_id)
+                        .is_some_and(|s| env.ledger().timestamp() > s.end_date)
+            }
+            ResultsVisibility::AfterFinalize => Self::is_finalized(env.clone(), survey_id),
+        }
+    }
+
+    // Hash de los resultados, el padrón de votantes y los parámetros de la encuesta
+    fn option_results(env: &Env, survey_id: u64, question: u32, question_data: Question) -> Vec<OptionResult> {
+        let mut results = Vec::new(env);
+        for (option, label) in question_data.options.iter().enumerate() {
+            let count_key = DataKey::OptionCount(survey_id, question, option as u32);
+            let votes: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            results.push_back(OptionResult { label, votes });
+        }
+        results
+    }
+
+    fn certify(env: &Env, survey: &Survey, total_votes: u64) -> ResultCertificate {
+        let mut results: Vec<(Address, u64)> = Vec::new(env);
+        for result in Self::tally(env, survey.survey_id).iter() {
+            results.push_back((result.candidate, result.votes));
+        }
+        // Los cuestionarios certifican sus preguntas y el conteo de cada opción
+        let questions = Self::get_questions(env.clone(), survey.survey_id);
+        let mut question_results: Vec<Vec<OptionResult>> = Vec::new(env);
+        for (question, question_data) in questions.iter().enumerate() {
+            question_results.push_back(Self::option_results(env, survey.survey_id, question as u32, question_data));
+        }
+        let canonical = (
+            survey.survey_id,
+            survey.creator.clone(),
+            survey.name.clone(),
+            survey.description.clone(),
+            survey.start_date,
+            survey.end_date,
+            survey.created_at,
+            survey.candidates.clone(),
+            results,
+            Self::voter_list(env, survey.survey_id),
+            questions,
+            question_results,
+        );
+
+        ResultCertificate {
+            survey_id: survey.survey_id,
+            hash: env.crypto().sha256(&canonical.to_xdr(env)).into(),
+            ledger: env.ledger().sequence(),
+            total_votes,
+        }
+    }
+
+    // recibo = sha256(survey_id || secuencia del ledger || XDR del votante || compromiso),
+    // encadenado al acumulador de la encuesta
+    fn issue_receipt(env: &Env, survey_id: u64, voter: &Address, commitment: &BytesN<32>) -> BytesN<32> {
+        let mut data = Bytes::from_array(env, &survey_id.to_be_bytes());
+        data.extend_from_array(&env.ledger().sequence().to_be_bytes());
+        data.append(&voter.clone().to_xdr(env));
+        data.append(commitment.as_ref());
+        let receipt: BytesN<32> = env.crypto().sha256(&data).into();
+
+        let mut chain = Self::get_receipt_chain(env.clone(), survey_id);
+        let mut link: Bytes = chain.head.into();
+        link.append(receipt.as_ref());
+        chain.head = env.crypto().sha256(&link).into();
+        chain.length += 1;
+
+        let chain_key = DataKey::ReceiptChain(survey_id);
+        Self::set_persistent(env, &chain_key, &chain);
+        let receipt_key = DataKey::Receipt(survey_id, receipt.clone());
+        Self::set_persistent(env, &receipt_key, &chain.length);
+        ReceiptIssued { survey_id, receipt: receipt.clone(), head: chain.head }.publish(env);
+        receipt
+    }
+
+    // Cuenta G correspondiente a una llave pública ed25519
+    fn account_address(env: &Env, public_key: &BytesN<32>) -> Address {
+        // XDR de ScVal::Address(ScAddress::Account(PublicKey::Ed25519(..)))
+        let mut xdr = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+        xdr.append(public_key.as_ref());
+        Address::from_xdr(env, &xdr).expect("Invalid public key")
+    }
+
+    // Solo el creador puede configurar la encuesta, únicamente antes de que inicie
+    // y mientras no esté pausada la creación de encuestas
+    fn require_draft_creator(env: &Env, survey_id: u64) -> Survey {
+        let survey_key = DataKey::Survey(survey_id);
+        let survey: Survey = env.storage().persistent()
+            .get(&survey_key)
+            .expect("Survey not found");
+        survey.creator.require_auth();
+        Self::require_not_paused(env, PauseScope::SurveyCreation);
+
+        if env.ledger().timestamp() >= survey.start_date {
+            panic!("Survey has already started");
+        }
+        survey
+    }
+
+    // URI ipfs:// acotada, etiquetas sin repetir y categoría personalizada no vacía
+    fn validate_metadata(env: &Env, metadata: &SurveyMetadata) {
+        let uri_len = metadata.metadata_uri.len();
+        if uri_len <= METADATA_URI_PREFIX.len() as u32 || uri_len > MAX_METADATA_URI_LEN {
+            panic_with_error!(env, SurveyError::InvalidMetadata);
+        }
+        let mut buf = [0u8; MAX_METADATA_URI_LEN as usize];
+        let uri = &mut buf[..uri_len as usize];
+        metadata.metadata_uri.copy_into_slice(uri);
+        if !uri.starts_with(METADATA_URI_PREFIX) {
+            panic_with_error!(env, SurveyError::InvalidMetadata);
+        }
+
+        if metadata.tags.len() > MAX_TAGS {
+            panic_with_error!(env, SurveyError::InvalidMetadata);
+        }
+        for (i, tag) in metadata.tags.iter().enumerate() {
+            if metadata.tags.first_index_of(&tag) != Some(i as u32) {
+                panic_with_error!(env, SurveyError::InvalidMetadata);
+            }
+        }
+
+        if let SurveyCategory::Custom(name) = &metadata.category {
+            if *name == Symbol::new(env, "") {
+                panic_with_error!(env, SurveyError::InvalidMetadata);
+            }
+        }
+    }
+
+    // Hoja = sha256(XDR de la dirección); cada nivel hashea el par ordenado de nodos
+    fn verify_merkle_proof(
+        env: &Env,
+        root: &BytesN<32>,
+        voter: &Address,
+        proof: &Vec<BytesN<32>>,
+    ) -> bool {
+        if proof.len() > MAX_MERKLE_PROOF_LEN {
+            return false;
+        }
+
+        let mut node: BytesN<32> = env.crypto().sha256(&voter.clone().to_xdr(env)).into();
+        for sibling in proof.iter() {
+            let (left, right) = if node <= sibling { (node, sibling) } else { (sibling, node) };
+            let mut data: Bytes = left.into();
+            data.append(right.as_ref());
+            node = env.crypto().sha256(&data).into();
+        }
+        node == *root
+    }
+
+    // Delegado vigente: la delegación de la encuesta tiene prioridad sobre la global
+    fn effective_delegate(env: &Env, delegator: &Address, survey_id: Option<u64>) -> Option<Address> {
+        if let Some(survey_id) = survey_id {
+            let key = DataKey::Delegation(delegator.clone(), DelegationScope::Survey(survey_id));
+            if let Some(delegate) = env.storage().persistent().get(&key) {
+                return Some(delegate);
+            }
+        }
+        let key = DataKey::Delegation(delegator.clone(), DelegationScope::Global);
+        env.storage().persistent().get(&key)
+    }
+
+    fn remove_delegation(env: &Env, delegator: &Address, scope: &DelegationScope) -> bool {
+        let key = DataKey::Delegation(delegator.clone(), scope.clone());
+        let delegate: Address = match env.storage().persistent().get(&key) {
+            Some(delegate) => delegate,
+            None => return false,
+        };
+        env.storage().persistent().remove(&key);
+
+        let delegators_key = DataKey::Delegators(delegate, scope.clone());
+        let mut delegators: Vec<Address> = env.storage().persistent()
+            .get(&delegators_key)
+            .unwrap_or(Vec::new(env));
+        if let Some(index) = delegators.first_index_of(delegator) {
+            delegators.remove(index);
+        }
+        Self::set_persistent(env, &delegators_key, &delegators);
+        true
+    }
+
+    // Suma el voto de `voter` y, nivel por nivel, el de cada delegante cuya cadena
+    // llega a `voter` sin pasar por alguien que votó directamente. Si un delegante
+    // ya estaba acreditado a otro votante, su voto se mueve al nuevo candidato.
+    // Las encuestas MerkleRoot no cuentan delegaciones porque no hay pruebas de
+    // elegibilidad para los delegantes.
+    // Un voto acredita como mucho MAX_DELEGATED_VOTES delegantes, los más cercanos
+    // primero, para que el recorrido quepa en una transacción; los demás conservan
+    // su acreditación anterior y siempre pueden votar directamente.
+    fn credit_vote(env: &Env, survey_id: u64, voter: &Address, candidate: &Address) {
+        let eligibility = Self::get_eligibility(env.clone(), survey_id);
+        let follow_delegations = !matches!(eligibility, Eligibility::MerkleRoot(_));
+
+        let mut level = Vec::new(env);
+        level.push_back(voter.clone());
+        let mut depth = 0;
+        let mut queued = 0;
+        while !level.is_empty() {
+            let mut next_level = Vec::new(env);
+            for node in level.iter() {
+                let cast_by_key = DataKey::CastBy(survey_id, node.clone());
+                let previous: Option<Address> = env.storage().persistent().get(&cast_by_key);
+                if let Some(previous) = previous {
+                    let previous_candidate: Address = env.storage().persistent()
+                        .get(&DataKey::Vote(survey_id, previous))
+                        .expect("Delegated vote without ballot");
+                    Self::add_to_count(env, survey_id, &previous_candidate, -1);
+                }
+                Self::add_to_count(env, survey_id, candidate, 1);
+
+                if node == *voter {
+                    env.storage().persistent().remove(&cast_by_key);
+                } else {
+                    Self::set_persistent(env, &cast_by_key, voter);
+                }
+
+                if follow_delegations && depth < MAX_DELEGATION_DEPTH {
+                    let limit = MAX_DELEGATED_VOTES - queued;
+                    Self::collect_delegators(env, survey_id, &node, &eligibility, &mut next_level, limit);
+                }
+            }
+            queued += next_level.len();
+            level = next_level;
+            depth += 1;
+        }
+    }
+
+    // Delegantes cuyo delegado vigente en la encuesta es `delegate` y que no votaron,
+    // hasta que `out` alcance `limit`
+    fn collect_delegators(
+        env: &Env,
+        survey_id: u64,
+        delegate: &Address,
+        eligibility: &Eligibility,
+        out: &mut Vec<Address>,
+        limit: u32,
+    ) {
+        for scope in [DelegationScope::Survey(survey_id), DelegationScope::Global] {
+            let scope_is_global = scope == DelegationScope::Global;
+            let delegators: Vec<Address> = env.storage().persistent()
+                .get(&DataKey::Delegators(delegate.clone(), scope))
+                .unwrap_or(Vec::new(env));
+            for delegator in delegators.iter() {
+                if out.len() >= limit {
+                    return;
+                }
+                if scope_is_global
+                    && env.storage().persistent().has(&DataKey::Delegation(
+                        delegator.clone(),
+                        DelegationScope::Survey(survey_id),
+                    ))
+                {
+                    continue; // La delegación global está reemplazada por una de la encuesta
+                }
+                if env.storage().persistent().has(&DataKey::Vote(survey_id, delegator.clone())) {
+                    continue; // El voto directo reemplaza la delegación
+                }
+                if *eligibility == Eligibility::Allowlist
+                    && !env.storage().persistent().has(&DataKey::Eligible(survey_id, delegator.clone()))
+                {
+                    continue;
+                }
+                out.push_back(delegator);
+            }
+        }
+    }
+
+    fn add_to_count(env: &Env, survey_id: u64, candidate: &Address, delta: i64) {
+        let vote_count_key = DataKey::VoteCount(survey_id, candidate.clone());
+        let current_count: u64 = env.storage().persistent()
+            .get(&vote_count_key)
+            .unwrap_or(0);
+        let new_count = current_count.checked_add_signed(delta).expect("Vote count underflow");
+        Self::set_persistent(env, &vote_count_key, &new_count);
+        if Self::live_results(env, survey_id) {
+            TallyChanged { survey_id, candidate: candidate.clone(), delta }.publish(env);
+        }
+    }
+
+    fn require_not_paused(env: &Env, scope: PauseScope) {
+        if Self::is_paused(env.clone(), scope) {
+            panic_with_error!(env, SurveyError::Paused);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use dappcenvote_types::survey::{DepositConfig, SurveyClient};
+    use soroban_sdk::{testutils::{Address as _, Ledger}, token::StellarAssetClient, Env, TryFromVal};
+
+    // Versiones congeladas del contrato; se regeneran con scripts/build-testdata.sh
+    mod survey_v1 {
+        soroban_sdk::contractimport!(file = "testdata/survey_v1.wasm");
+    }
+
+    mod survey_v2 {
+        soroban_sdk::contractimport!(file = "testdata/survey_v2.wasm");
+    }
+
+    #[test]
+    fn test_create_survey() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(SurveyContract, (admin.clone(),));
+        let client = SurveyContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let candidate1 = Address::generate(&env);
+        let candidate2 = Address::generate(&env);
+
+        let mut candidates = Vec::new(&env);
+        candidates.push_back(candidate1.clone());
+        candidates.push_back(candidate2.clone());
+
+        let survey_id = client.create_survey(
+            &creator,
+            &String::from_str(&env, "Test Survey"),
+            &String::from_str(&env, "Description"),
+            &1000,
+            &2000,
+            &candidates,
+        );
+
+        assert_eq!(survey_id, 1);
+        assert_eq!(client.get_survey_count(), 1);
+
+        let survey = client.get_survey(&survey_id);
+        assert!(survey.is_some());
+    }
+
+    #[test]
+    fn test_voting() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(SurveyContract, (admin.clone(),));
+        let client = SurveyContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let voter1 = Address::generate(&env);
+        let voter2 = Address::generate(&env);
+        let candidate1 = Address::generate(&env);
+        let candidate2 = Address::generate(&env);
+
+        let mut candidates = Vec::new(&env);
+        candidates.push_back(candidate1.clone());
+        candidates.push_back(candidate2.clone());
+
+        // Crear encuesta con fechas que incluyen el tiempo actual
+        env.ledger().set_timestamp(1500);
+        
+        let survey_id = client.create_survey(
+            &creator,
+            &String::from_str(&env, "Voting Test"),
+            &String::from_str(&env, "Test Description"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+
+        // Votar
+        let receipt = client.vote(&survey_id, &voter1, &candidate1, &None);
+        assert!(client.verify_receipt(&survey_id, &receipt));
+
+        // Verificar que votó
+        assert!(client.has_voted(&survey_id, &voter1));
+
+        // Votar con otro usuario
+        client.vote(&survey_id, &voter2, &candidate2, &None);
+
+        // Verificar resultados
+        let results = client.get_results(&survey_id);
+        assert_eq!(results.len(), 2);
+        assert_eq!(client.get_total_votes(&survey_id), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Voter has already voted")]
+    fn test_double_voting() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(SurveyContract, (admin.clone(),));
+        let client = SurveyContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let voter = Address::generate(&env);
+        let candidate1 = Address::generate(&env);
+        let candidate2 = Address::generate(&env);
 
         let mut candidates = Vec::new(&env);
         candidates.push_back(candidate1.clone());
@@ -351,18 +2140,1894 @@ mod test {
         env.ledger().set_timestamp(1500);
 
         let survey_id = client.create_survey(
-            &creator,
-            &String::from_str(&env, "Double Vote Test"),
+            &creator,
+            &String::from_str(&env, "Double Vote Test"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+
+        // Primer voto
+        client.vote(&survey_id, &voter, &candidate1, &None);
+
+        // Segundo voto (debe fallar)
+        client.vote(&survey_id, &voter, &candidate2, &None);
+    }
+
+    #[test]
+    fn test_vote_receipts() {
+        let env = Env::default();
+        let (client, _, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        env.ledger().set_timestamp(1500);
+        env.ledger().set_sequence_number(42);
+        let survey_id = client.create_survey(
+            &creator,
+            &String::from_str(&env, "Receipts"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+
+        let voter = Address::generate(&env);
+        let candidate = candidates.get(0).unwrap();
+        let receipt = client.vote(&survey_id, &voter, &candidate, &None);
+
+        // El votante puede recalcular su recibo sin depender del contrato
+        let mut data = Bytes::from_array(&env, &survey_id.to_be_bytes());
+        data.extend_from_array(&42u32.to_be_bytes());
+        data.append(&voter.clone().to_xdr(&env));
+        data.extend_from_array(&env.crypto().sha256(&candidate.to_xdr(&env)).to_array());
+        assert_eq!(receipt, env.crypto().sha256(&data).to_bytes());
+
+        let second = client.vote(&survey_id, &Address::generate(&env), &candidates.get(1).unwrap(), &None);
+        let mut head = Bytes::from_array(&env, &[0; 32]);
+        for link in [&receipt, &second] {
+            head.extend_from_array(&link.to_array());
+            head = Bytes::from_array(&env, &env.crypto().sha256(&head).to_array());
+        }
+        let chain = client.get_receipt_chain(&survey_id);
+        assert_eq!(chain.length, 2);
+        assert_eq!(Bytes::from_array(&env, &chain.head.to_array()), head);
+
+        assert!(client.verify_receipt(&survey_id, &second));
+        assert!(!client.verify_receipt(&survey_id, &BytesN::from_array(&env, &[7; 32])));
+        assert!(!client.verify_receipt(&(survey_id + 1), &receipt));
+    }
+
+    fn setup_policy_test(env: &Env) -> (SurveyContractClient<'_>, Address, Vec<Address>) {
+        env.mock_all_auths();
+        let admin = Address::generate(env);
+        let contract_id = env.register(SurveyContract, (admin.clone(),));
+        let client = SurveyContractClient::new(env, &contract_id);
+
+        let mut candidates = Vec::new(env);
+        candidates.push_back(Address::generate(env));
+        candidates.push_back(Address::generate(env));
+
+        (client, admin, candidates)
+    }
+
+    #[test]
+    fn test_open_policy_allows_anyone() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        assert_eq!(client.get_creation_policy(), CreationPolicy::Open);
+
+        let creator = Address::generate(&env);
+        let survey_id = client.create_survey(
+            &creator,
+            &String::from_str(&env, "Open Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &2000,
+            &candidates,
+        );
+        assert_eq!(survey_id, 1);
+        assert!(client.get_deposit(&survey_id).is_none());
+    }
+
+    #[test]
+    fn test_allowlist_policy() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        let creator = Address::generate(&env);
+        client.set_creation_policy(&CreationPolicy::Allowlist);
+        client.add_creator(&creator);
+        assert!(client.is_allowed_creator(&creator));
+
+        let survey_id = client.create_survey(
+            &creator,
+            &String::from_str(&env, "Allowlisted Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &2000,
+            &candidates,
+        );
+        assert_eq!(survey_id, 1);
+
+        client.remove_creator(&creator);
+        assert!(!client.is_allowed_creator(&creator));
+    }
+
+    #[test]
+    #[should_panic(expected = "Creator is not allowed to create surveys")]
+    fn test_allowlist_policy_rejects_unknown_creator() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        client.set_creation_policy(&CreationPolicy::Allowlist);
+
+        client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Spam Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &2000,
+            &candidates,
+        );
+    }
+
+    #[test]
+    fn test_scheduler_creates_for_creator() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        let scheduler = Address::generate(&env);
+        let creator = Address::generate(&env);
+        client.set_scheduler(&scheduler);
+        assert_eq!(client.get_scheduler(), Some(scheduler.clone()));
+        client.set_creation_policy(&CreationPolicy::Allowlist);
+        client.add_creator(&creator);
+
+        // El programador autoriza y el creador real queda como dueño de la encuesta
+        let survey_id = client.create_survey_for(
+            &creator,
+            &String::from_str(&env, "Scheduled Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &2000,
+            &candidates,
+        );
+        let auths = env.auths();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].0, scheduler);
+        assert_eq!(client.get_survey(&survey_id).unwrap().creator, creator);
+
+        // La política se aplica al creador, no al programador
+        let result = client.try_create_survey_for(
+            &Address::generate(&env),
+            &String::from_str(&env, "Spam Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &2000,
+            &candidates,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deposit_refunded_after_turnout() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+
+        let token = env.register_stellar_asset_contract_v2(admin.clone());
+        let token_client = token::Client::new(&env, &token.address());
+        let creator = Address::generate(&env);
+        StellarAssetClient::new(&env, &token.address()).mint(&creator, &500);
+
+        client.set_creation_policy(&CreationPolicy::Deposit(DepositConfig {
+            token: token.address(),
+            amount: 200,
+            min_turnout: 2,
+        }));
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &creator,
+            &String::from_str(&env, "Deposit Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        assert_eq!(token_client.balance(&creator), 300);
+        assert_eq!(token_client.balance(&client.address), 200);
+
+        client.vote(&survey_id, &Address::generate(&env), &candidates.get(0).unwrap(), &None);
+        client.vote(&survey_id, &Address::generate(&env), &candidates.get(1).unwrap(), &None);
+
+        env.ledger().set_timestamp(3001);
+        assert!(client.finalize_survey(&survey_id));
+        assert!(client.is_finalized(&survey_id));
+        assert!(client.get_deposit(&survey_id).is_none());
+        assert_eq!(token_client.balance(&creator), 500);
+        assert_eq!(token_client.balance(&client.address), 0);
+    }
+
+    #[test]
+    fn test_deposit_forfeited_below_turnout() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+
+        let token = env.register_stellar_asset_contract_v2(admin.clone());
+        let token_client = token::Client::new(&env, &token.address());
+        let creator = Address::generate(&env);
+        StellarAssetClient::new(&env, &token.address()).mint(&creator, &500);
+
+        client.set_creation_policy(&CreationPolicy::Deposit(DepositConfig {
+            token: token.address(),
+            amount: 200,
+            min_turnout: 2,
+        }));
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &creator,
+            &String::from_str(&env, "Low Turnout"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        client.vote(&survey_id, &Address::generate(&env), &candidates.get(0).unwrap(), &None);
+
+        env.ledger().set_timestamp(3001);
+        assert!(!client.finalize_survey(&survey_id));
+        assert!(client.get_deposit(&survey_id).is_none());
+        assert_eq!(token_client.balance(&creator), 300);
+        assert_eq!(token_client.balance(&client.address), 200);
+    }
+
+    #[test]
+    #[should_panic(expected = "Survey has not ended yet")]
+    fn test_finalize_before_end() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Still Open"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        client.finalize_survey(&survey_id);
+    }
+
+    #[test]
+    fn test_result_certificate() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        // Dos encuestas idénticas que solo difieren en el resultado
+        let mut survey_ids = [0u64; 2];
+        for (i, survey_id) in survey_ids.iter_mut().enumerate() {
+            env.ledger().set_timestamp(0);
+            *survey_id = client.create_survey(
+                &Address::generate(&env),
+                &String::from_str(&env, "Certified"),
+                &String::from_str(&env, "Test"),
+                &1000,
+                &2000,
+                &candidates,
+            );
+            env.ledger().set_timestamp(1500);
+            client.vote(survey_id, &Address::generate(&env), &candidates.get(i as u32).unwrap(), &None);
+        }
+        assert_eq!(client.get_certificate(&survey_ids[0]), None);
+
+        env.ledger().set_timestamp(2001);
+        env.ledger().set_sequence_number(42);
+        client.finalize_survey(&survey_ids[0]);
+        client.finalize_survey(&survey_ids[1]);
+
+        let first = client.get_certificate(&survey_ids[0]).unwrap();
+        let second = client.get_certificate(&survey_ids[1]).unwrap();
+        assert_eq!(first.survey_id, survey_ids[0]);
+        assert_eq!(first.ledger, 42);
+        assert_eq!(first.total_votes, 1);
+        assert_eq!(client.get_voters(&survey_ids[0]).len(), 1);
+        assert_ne!(first.hash, second.hash);
+    }
+
+    #[test]
+    fn test_upgrade_and_migrate() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        // Desplegar la versión 1 y crear datos con el formato anterior
+        let contract_id = env.register(survey_v1::WASM, ());
+        let v1_client = survey_v1::Client::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let candidate = Address::generate(&env);
+        v1_client.initialize(&admin);
+        assert_eq!(v1_client.version(), 1);
+
+        let mut candidates = Vec::new(&env);
+        candidates.push_back(candidate.clone());
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = v1_client.create_survey(
+            &creator,
+            &String::from_str(&env, "Legacy Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        v1_client.vote(&survey_id, &Address::generate(&env), &candidate);
+
+        // Actualizar a la versión 2 y migrar el almacenamiento
+        let v2_hash = env.deployer().upload_contract_wasm(survey_v2::WASM);
+        v1_client.upgrade(&v2_hash);
+
+        survey_v2::Client::new(&env, &contract_id).migrate();
+        let client = SurveyContractClient::new(&env, &contract_id);
+        assert_eq!(client.version(), 2);
+
+        let survey = client.get_survey(&survey_id).unwrap();
+        assert_eq!(survey.name, String::from_str(&env, "Legacy Survey"));
+        assert_eq!(survey.created_at, 0);
+        assert_eq!(client.get_total_votes(&survey_id), 1);
+
+        // Las encuestas nuevas usan el formato actual
+        let new_id = client.create_survey(
+            &creator,
+            &String::from_str(&env, "New Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        assert_eq!(client.get_survey(&new_id).unwrap().created_at, 1500);
+    }
+
+    #[test]
+    fn test_migrate_current_version() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+
+        // Repetir la migración no falla ni cambia la versión
+        assert!(client.migrate(&10));
+        assert_eq!(client.version(), CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_in_batches() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        // Almacenamiento de la versión 1 con tres encuestas
+        env.as_contract(&client.address, || {
+            for survey_id in 1..=3u64 {
+                let old = SurveyV1 {
+                    survey_id,
+                    creator: Address::generate(&env),
+                    name: String::from_str(&env, "Legacy"),
+                    description: String::from_str(&env, "Test"),
+                    start_date: 1000,
+                    end_date: 3000,
+                    candidates: candidates.clone(),
+                };
+                env.storage().persistent().set(&DataKey::Survey(survey_id), &old);
+            }
+            env.storage().persistent().set(&DataKey::SurveyCount, &3u64);
+            env.storage().instance().set(&DataKey::ContractVersion, &1u32);
+        });
+
+        // Cada llamada avanza a lo sumo `limit` encuestas
+        assert!(!client.migrate(&2));
+        assert_eq!(client.version(), 1);
+        assert_eq!(client.get_survey(&2).unwrap().created_at, 0);
+
+        // Una encuesta creada a mitad de la migración ya usa el formato nuevo
+        env.ledger().set_timestamp(500);
+        let new_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "New"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+
+        assert!(client.migrate(&2));
+        assert_eq!(client.version(), CONTRACT_VERSION);
+        assert_eq!(client.get_survey(&3).unwrap().name, String::from_str(&env, "Legacy"));
+        assert_eq!(client.get_survey(&new_id).unwrap().created_at, 500);
+    }
+
+    #[test]
+    fn test_pause_voting() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Paused Survey"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        let voter = Address::generate(&env);
+        let candidate = candidates.get(0).unwrap();
+
+        client.pause(&admin, &PauseScope::Voting);
+        assert!(client.is_paused(&PauseScope::Voting));
+        assert_eq!(
+            client.try_vote(&survey_id, &voter, &candidate, &None),
+            Err(Ok(SurveyError::Paused.into()))
+        );
+
+        // Las consultas siguen funcionando durante la pausa
+        assert_eq!(client.get_results(&survey_id).len(), 2);
+        assert!(!client.has_voted(&survey_id, &voter));
+
+        client.unpause(&admin, &PauseScope::Voting);
+        client.vote(&survey_id, &voter, &candidate, &None);
+    }
+
+    #[test]
+    fn test_guardian_pauses_survey_creation() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        let guardian = Address::generate(&env);
+        client.set_guardian(&guardian);
+        assert_eq!(client.get_guardian(), Some(guardian.clone()));
+
+        client.pause(&guardian, &PauseScope::SurveyCreation);
+        assert!(!client.is_paused(&PauseScope::Voting));
+
+        let result = client.try_create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Blocked"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &2000,
+            &candidates,
+        );
+        assert_eq!(result, Err(Ok(SurveyError::Paused.into())));
+    }
+
+    #[test]
+    fn test_pause_requires_admin_or_guardian() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+
+        let stranger = Address::generate(&env);
+        assert_eq!(
+            client.try_pause(&stranger, &PauseScope::Voting),
+            Err(Ok(SurveyError::Unauthorized.into()))
+        );
+        assert!(!client.is_paused(&PauseScope::Voting));
+    }
+
+    // Construye fuera de la cadena un árbol Merkle de votantes con el mismo
+    // esquema que verify_merkle_proof; devuelve la raíz y la prueba de cada hoja
+    fn build_merkle_tree(
+        env: &Env,
+        voters: &[Address],
+    ) -> ([u8; 32], std::vec::Vec<std::vec::Vec<[u8; 32]>>) {
+        use sha2::{Digest, Sha256};
+
+        let hash_pair = |a: [u8; 32], b: [u8; 32]| -> [u8; 32] {
+            let (left, right) = if a <= b { (a, b) } else { (b, a) };
+            let mut hasher = Sha256::new();
+            hasher.update(left);
+            hasher.update(right);
+            hasher.finalize().into()
+        };
+
+        let mut level: std::vec::Vec<[u8; 32]> = voters
+            .iter()
+            .map(|voter| {
+                let xdr: std::vec::Vec<u8> = voter.clone().to_xdr(env).iter().collect();
+                Sha256::digest(&xdr).into()
+            })
+            .collect();
+        let mut positions: std::vec::Vec<usize> = (0..voters.len()).collect();
+        let mut proofs = std::vec![std::vec::Vec::new(); voters.len()];
+
+        while level.len() > 1 {
+            for (leaf, pos) in positions.iter_mut().enumerate() {
+                let sibling = *pos ^ 1;
+                if sibling < level.len() {
+                    proofs[leaf].push(level[sibling]);
+                }
+                *pos /= 2;
+            }
+            // Un nodo sin pareja sube sin cambios al siguiente nivel
+            level = level
+                .chunks(2)
+                .map(|pair| if pair.len() == 2 { hash_pair(pair[0], pair[1]) } else { pair[0] })
+                .collect();
+        }
+        (level[0], proofs)
+    }
+
+    fn to_proof(env: &Env, proof: &[[u8; 32]]) -> Option<Vec<BytesN<32>>> {
+        let mut result = Vec::new(env);
+        for node in proof {
+            result.push_back(BytesN::from_array(env, node));
+        }
+        Some(result)
+    }
+
+    #[test]
+    fn test_allowlist_eligibility() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Members Only"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+
+        let member = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let mut members = Vec::new(&env);
+        members.push_back(member.clone());
+
+        client.set_eligibility(&survey_id, &Eligibility::Allowlist);
+        client.add_eligible_voters(&survey_id, &members);
+        assert!(client.is_eligible(&survey_id, &member, &None));
+        assert!(!client.is_eligible(&survey_id, &outsider, &None));
+
+        env.ledger().set_timestamp(1500);
+        let candidate = candidates.get(0).unwrap();
+        client.vote(&survey_id, &member, &candidate, &None);
+        assert_eq!(
+            client.try_vote(&survey_id, &outsider, &candidate, &None),
+            Err(Ok(SurveyError::NotEligible.into()))
+        );
+    }
+
+    #[test]
+    fn test_merkle_eligibility() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        let voters: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&env)).collect();
+        let (root, proofs) = build_merkle_tree(&env, &voters);
+
+        env.ledger().set_timestamp(500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Shareholders"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        client.set_eligibility(&survey_id, &Eligibility::MerkleRoot(BytesN::from_array(&env, &root)));
+
+        env.ledger().set_timestamp(1500);
+        let candidate = candidates.get(0).unwrap();
+
+        // Una prueba válida no sirve para otra dirección, y sin prueba no se vota
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client.try_vote(&survey_id, &outsider, &candidate, &to_proof(&env, &proofs[0])),
+            Err(Ok(SurveyError::NotEligible.into()))
+        );
+        assert_eq!(
+            client.try_vote(&survey_id, &voters[0], &candidate, &None),
+            Err(Ok(SurveyError::NotEligible.into()))
+        );
+        assert_eq!(
+            client.try_vote(&survey_id, &voters[0], &candidate, &to_proof(&env, &proofs[1])),
+            Err(Ok(SurveyError::NotEligible.into()))
+        );
+
+        for (voter, proof) in voters.iter().zip(proofs.iter()) {
+            client.vote(&survey_id, voter, &candidate, &to_proof(&env, proof));
+        }
+        assert_eq!(client.get_total_votes(&survey_id), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Survey has already started")]
+    fn test_set_eligibility_after_start() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Already Open"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        client.set_eligibility(&survey_id, &Eligibility::Allowlist);
+    }
+
+    fn metadata(env: &Env, category: SurveyCategory, tags: &[&str], uri: &str) -> SurveyMetadata {
+        let mut tag_list = Vec::new(env);
+        for tag in tags {
+            tag_list.push_back(Symbol::new(env, tag));
+        }
+        SurveyMetadata {
+            category,
+            tags: tag_list,
+            metadata_uri: String::from_str(env, uri),
+            content_hash: BytesN::from_array(env, &[7; 32]),
+        }
+    }
+
+    #[test]
+    fn test_update_metadata() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Presupuesto participativo"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        assert_eq!(client.get_metadata(&survey_id), None);
+
+        let uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+        let first = metadata(&env, SurveyCategory::Community, &["barrio", "obras"], uri);
+        client.update_metadata(&survey_id, &first);
+        assert_eq!(client.get_metadata(&survey_id), Some(first));
+
+        // Mientras siga en borrador se puede reemplazar
+        let custom = SurveyCategory::Custom(Symbol::new(&env, "vecinal"));
+        let second = metadata(&env, custom, &["presupuesto"], uri);
+        client.update_metadata(&survey_id, &second);
+        assert_eq!(client.get_metadata(&survey_id), Some(second));
+    }
+
+    #[test]
+    fn test_update_metadata_rejects_invalid() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Metadatos"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+
+        let uri = "ipfs://bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+        let long_uri = std::format!("ipfs://{}", "a".repeat(300));
+        let too_many: std::vec::Vec<std::string::String> =
+            (0..11).map(|i| std::format!("tag{i}")).collect();
+        let too_many: std::vec::Vec<&str> = too_many.iter().map(|tag| tag.as_str()).collect();
+        let invalid = [
+            metadata(&env, SurveyCategory::General, &[], "https://example.com/survey.json"),
+            metadata(&env, SurveyCategory::General, &[], "ipfs://"),
+            metadata(&env, SurveyCategory::General, &[], &long_uri),
+            metadata(&env, SurveyCategory::Opinion, &["salud", "salud"], uri),
+            metadata(&env, SurveyCategory::Opinion, &too_many, uri),
+            metadata(&env, SurveyCategory::Custom(Symbol::new(&env, "")), &[], uri),
+        ];
+        for metadata in invalid.iter() {
+            assert_eq!(
+                client.try_update_metadata(&survey_id, metadata),
+                Err(Ok(SurveyError::InvalidMetadata.into()))
+            );
+        }
+        assert_eq!(client.get_metadata(&survey_id), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Survey has already started")]
+    fn test_update_metadata_after_start() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Already Open"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        let uri = "ipfs://bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+        client.update_metadata(&survey_id, &metadata(&env, SurveyCategory::Election, &[], uri));
+    }
+
+    fn questions(env: &Env, spec: &[(&str, &[&str])]) -> Vec<Question> {
+        let mut questions = Vec::new(env);
+        for (prompt, labels) in spec {
+            let mut options = Vec::new(env);
+            for label in labels.iter() {
+                options.push_back(String::from_str(env, label));
+            }
+            questions.push_back(Question { prompt: String::from_str(env, prompt), options });
+        }
+        questions
+    }
+
+    fn answers(env: &Env, choices: &[u32]) -> Vec<u32> {
+        Vec::from_slice(env, choices)
+    }
+
+    fn create_questionnaire(env: &Env, client: &SurveyContractClient) -> u64 {
+        env.ledger().set_timestamp(500);
+        client.create_questionnaire(
+            &Address::generate(env),
+            &String::from_str(env, "Transporte público"),
+            &String::from_str(env, "Test"),
+            &1000,
+            &3000,
+            &questions(
+                env,
+                &[
+                    ("¿Cómo llegas al trabajo?", &["Metro", "Autobús", "Bicicleta"]),
+                    ("¿Usarías una ciclovía nueva?", &["Sí", "No"]),
+                ],
+            ),
+        )
+    }
+
+    fn option_votes(client: &SurveyContractClient, survey_id: u64, question: u32) -> std::vec::Vec<u64> {
+        client
+            .get_question_results(&survey_id, &question)
+            .iter()
+            .map(|result| result.votes)
+            .collect()
+    }
+
+    #[test]
+    fn test_questionnaire_responses() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+        let survey_id = create_questionnaire(&env, &client);
+        assert_eq!(client.get_questions(&survey_id).len(), 2);
+        assert!(client.get_survey(&survey_id).unwrap().candidates.is_empty());
+
+        env.ledger().set_timestamp(1500);
+        let voters: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+        for (voter, choices) in voters.iter().zip([[2, 0], [1, 0], [2, 1]]) {
+            client.submit_response(&survey_id, voter, &answers(&env, &choices), &None);
+        }
+
+        assert_eq!(option_votes(&client, survey_id, 0), [0, 1, 2]);
+        assert_eq!(option_votes(&client, survey_id, 1), [2, 1]);
+        let results = client.get_question_results(&survey_id, &1);
+        assert_eq!(results.get(0).unwrap().label, String::from_str(&env, "Sí"));
+        assert_eq!(client.get_response(&survey_id, &voters[1]), Some(answers(&env, &[1, 0])));
+        assert!(client.has_voted(&survey_id, &voters[0]));
+        assert_eq!(client.get_total_votes(&survey_id), 3);
+
+        env.ledger().set_timestamp(3001);
+        client.finalize_survey(&survey_id);
+        assert_eq!(client.get_certificate(&survey_id).unwrap().total_votes, 3);
+    }
+
+    #[test]
+    fn test_questionnaire_rejects_invalid_response() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+        let survey_id = create_questionnaire(&env, &client);
+
+        env.ledger().set_timestamp(1500);
+        let voter = Address::generate(&env);
+        for choices in [&[0][..], &[0, 1, 1], &[3, 0], &[0, 2]] {
+            assert_eq!(
+                client.try_submit_response(&survey_id, &voter, &answers(&env, choices), &None),
+                Err(Ok(SurveyError::InvalidResponse.into()))
+            );
+        }
+        assert!(!client.has_voted(&survey_id, &voter));
+        assert_eq!(option_votes(&client, survey_id, 0), [0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Voter has already voted in this survey")]
+    fn test_questionnaire_double_response() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+        let survey_id = create_questionnaire(&env, &client);
+
+        env.ledger().set_timestamp(1500);
+        let voter = Address::generate(&env);
+        client.submit_response(&survey_id, &voter, &answers(&env, &[0, 0]), &None);
+        client.submit_response(&survey_id, &voter, &answers(&env, &[1, 1]), &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Survey has no questions")]
+    fn test_response_to_candidate_survey() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Candidates"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        client.submit_response(&survey_id, &Address::generate(&env), &answers(&env, &[0]), &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Question must have at least two options")]
+    fn test_questionnaire_single_option() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+
+        client.create_questionnaire(
+            &Address::generate(&env),
+            &String::from_str(&env, "Sin opciones"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &questions(&env, &[("¿Única?", &["Sí"])]),
+        );
+    }
+
+    #[test]
+    fn test_set_vote_fee() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+        assert_eq!(client.get_vote_fee(), VOTE_FEE_STROOPS);
+
+        client.set_vote_fee(&0);
+        assert_eq!(client.get_vote_fee(), 0);
+
+        let governance = Address::generate(&env);
+        client.set_governance(&governance);
+        assert_eq!(client.get_governance(), Some(governance.clone()));
+        client.set_vote_fee(&5_000_000);
+        assert_eq!(env.auths()[0].0, governance);
+        assert_eq!(client.get_vote_fee(), 5_000_000);
+
+        // Una vez cedida, solo la gobernanza vigente puede reemplazarse
+        let next = Address::generate(&env);
+        client.set_governance(&next);
+        assert_eq!(env.auths()[0].0, governance);
+        assert_eq!(client.get_governance(), Some(next));
+    }
+
+    #[test]
+    #[should_panic(expected = "Vote fee cannot be negative")]
+    fn test_negative_vote_fee() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+        client.set_vote_fee(&-1);
+    }
+
+    // Token de prueba con saldo inicial para cada votante
+    fn fee_token(env: &Env, admin: &Address, holders: &[Address], amount: i128) -> token::Client<'static> {
+        let token = env.register_stellar_asset_contract_v2(admin.clone());
+        for holder in holders {
+            StellarAssetClient::new(env, &token.address()).mint(holder, &amount);
+        }
+        token::Client::new(env, &token.address())
+    }
+
+    fn draft_survey(env: &Env, client: &SurveyContractClient, creator: &Address, candidates: &Vec<Address>) -> u64 {
+        env.ledger().set_timestamp(500);
+        client.create_survey(
+            creator,
+            &String::from_str(env, "Con fee"),
+            &String::from_str(env, "Test"),
+            &1000,
+            &3000,
+            candidates,
+        )
+    }
+
+    #[test]
+    fn test_survey_fee_accounting() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let voters: std::vec::Vec<Address> = (0..4).map(|_| Address::generate(&env)).collect();
+        let xlm = fee_token(&env, &admin, &voters, 1000);
+        let usdc = fee_token(&env, &admin, &voters, 1000);
+        let creator = Address::generate(&env);
+
+        // Fee por defecto de 100 en "XLM"; la segunda encuesta es gratuita y la
+        // tercera cobra 30 en otro token
+        client.set_fee_token(&xlm.address);
+        client.set_vote_fee(&100);
+        let default_fee = draft_survey(&env, &client, &creator, &candidates);
+        let free = draft_survey(&env, &client, &creator, &candidates);
+        client.set_survey_fee(&free, &xlm.address, &0);
+        let custom = draft_survey(&env, &client, &creator, &candidates);
+        client.set_survey_fee(&custom, &usdc.address, &30);
+        assert_eq!(client.get_survey_fee(&default_fee), Some(SurveyFee { token: xlm.address.clone(), amount: 100 }));
+
+        let exempt = &voters[3];
+        let mut exempt_list = Vec::new(&env);
+        exempt_list.push_back(exempt.clone());
+        client.add_fee_exempt(&exempt_list);
+        assert!(client.is_fee_exempt(exempt));
+
+        env.ledger().set_timestamp(1500);
+        let candidate = candidates.get(0).unwrap();
+        for voter in &voters {
+            for survey_id in [default_fee, free, custom] {
+                client.vote(&survey_id, voter, &candidate, &None);
+            }
+        }
+
+        for voter in &voters[..3] {
+            assert_eq!(xlm.balance(voter), 900);
+            assert_eq!(usdc.balance(voter), 970);
+        }
+        assert_eq!(xlm.balance(exempt), 1000);
+        assert_eq!(usdc.balance(exempt), 1000);
+        assert_eq!(xlm.balance(&client.address), 300);
+        assert_eq!(usdc.balance(&client.address), 90);
+
+        // Al quitar la exención vuelve a pagar
+        client.remove_fee_exempt(exempt);
+        let later = draft_survey(&env, &client, &creator, &candidates);
+        env.ledger().set_timestamp(1500);
+        client.vote(&later, exempt, &candidate, &None);
+        assert_eq!(xlm.balance(exempt), 900);
+    }
+
+    #[test]
+    fn test_cancel_and_claim_refund() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let voters: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+        let token = fee_token(&env, &admin, &voters, 1000);
+        let creator = Address::generate(&env);
+
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+        client.set_survey_fee(&survey_id, &token.address, &250);
+        let mut exempt_list = Vec::new(&env);
+        exempt_list.push_back(voters[2].clone());
+        client.add_fee_exempt(&exempt_list);
+
+        env.ledger().set_timestamp(1500);
+        for voter in &voters {
+            client.vote(&survey_id, voter, &candidates.get(1).unwrap(), &None);
+        }
+        assert_eq!(token.balance(&client.address), 500);
+        assert_eq!(
+            client.try_claim_refund(&survey_id, &voters[0]),
+            Err(Ok(SurveyError::NotCancelled.into()))
+        );
+
+        client.cancel_survey(&survey_id, &creator);
+        assert!(client.is_cancelled(&survey_id));
+
+        assert_eq!(client.claim_refund(&survey_id, &voters[0]), 250);
+        assert_eq!(client.claim_refund(&survey_id, &voters[1]), 250);
+        for voter in &voters {
+            assert_eq!(token.balance(voter), 1000);
+        }
+        assert_eq!(token.balance(&client.address), 0);
+
+        // No hay doble reembolso ni reembolso para quien no pagó
+        for voter in &voters {
+            assert_eq!(
+                client.try_claim_refund(&survey_id, voter),
+                Err(Ok(SurveyError::NothingToRefund.into()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_pause_funds_and_configuration() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let voter = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let token = fee_token(&env, &admin, &[voter.clone(), creator.clone()], 1000);
+
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+        client.set_survey_fee(&survey_id, &token.address, &100);
+
+        // La pausa de creación también congela la configuración de borradores
+        client.pause(&admin, &PauseScope::SurveyCreation);
+        let paused = Err(Ok(SurveyError::Paused.into()));
+        assert_eq!(client.try_set_eligibility(&survey_id, &Eligibility::Allowlist), paused);
+        assert_eq!(
+            client.try_update_metadata(&survey_id, &metadata(&env, SurveyCategory::General, &[], "ipfs://x")),
+            paused
+        );
+        client.unpause(&admin, &PauseScope::SurveyCreation);
+
+        env.ledger().set_timestamp(1500);
+        client.vote(&survey_id, &voter, &candidates.get(0).unwrap(), &None);
+        client.cancel_survey(&survey_id, &creator);
+
+        // Con Funds en pausa no sale ningún token, pero la votación sigue abierta
+        client.pause(&admin, &PauseScope::Funds);
+        assert!(!client.is_paused(&PauseScope::Voting));
+        assert_eq!(client.try_claim_refund(&survey_id, &voter), Err(Ok(SurveyError::Paused.into())));
+        assert_eq!(client.try_withdraw(&token.address, &admin, &1), paused);
+        assert_eq!(token.balance(&voter), 900);
+
+        client.unpause(&admin, &PauseScope::Funds);
+        assert_eq!(client.claim_refund(&survey_id, &voter), 100);
+    }
+
+    #[test]
+    fn test_cancelled_survey_rejects_votes() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+
+        assert_eq!(
+            client.try_cancel_survey(&survey_id, &Address::generate(&env)),
+            Err(Ok(SurveyError::Unauthorized.into()))
+        );
+        client.cancel_survey(&survey_id, &admin);
+
+        env.ledger().set_timestamp(1500);
+        let candidate = candidates.get(0).unwrap();
+        assert!(client.try_vote(&survey_id, &Address::generate(&env), &candidate, &None).is_err());
+        env.ledger().set_timestamp(3001);
+        assert!(client.try_finalize_survey(&survey_id).is_err());
+        assert!(!client.is_finalized(&survey_id));
+    }
+
+    #[test]
+    #[should_panic(expected = "Survey already finalized")]
+    fn test_cancel_finalized_survey() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+
+        env.ledger().set_timestamp(3001);
+        client.finalize_survey(&survey_id);
+        client.cancel_survey(&survey_id, &creator);
+    }
+
+    // Suma de saldos que el contrato debe custodiar en un token
+    fn owed(client: &SurveyContractClient, surveys: &[u64], token: &Address) -> i128 {
+        let mut owed = client.get_treasury_balance(token);
+        for survey_id in surveys {
+            for entry in client.get_survey_revenue(survey_id).iter().filter(|e| e.token == *token) {
+                if !client.is_finalized(survey_id) {
+                    owed += entry.collected;
+                } else if !entry.creator_claimed {
+                    owed += entry.creator_share;
+                }
+            }
+        }
+        owed
+    }
+
+    #[test]
+    fn test_treasury_split_invariant() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let voters: std::vec::Vec<Address> = (0..7).map(|_| Address::generate(&env)).collect();
+        let token = fee_token(&env, &admin, &voters, 1000);
+        let creators = [Address::generate(&env), Address::generate(&env)];
+
+        // Un tercio para el creador: los fees de 7 y 13 no se reparten exactos
+        client.set_creator_share(&3333);
+        let mut surveys = std::vec::Vec::new();
+        for (creator, fee, turnout) in [(&creators[0], 7, 7), (&creators[1], 13, 4)] {
+            let survey_id = draft_survey(&env, &client, creator, &candidates);
+            client.set_survey_fee(&survey_id, &token.address, &fee);
+            env.ledger().set_timestamp(1500);
+            for voter in &voters[..turnout] {
+                client.vote(&survey_id, voter, &candidates.get(0).unwrap(), &None);
+            }
+            surveys.push(survey_id);
+        }
+        assert_eq!(token.balance(&client.address), 7 * 7 + 13 * 4);
+        assert_eq!(owed(&client, &surveys, &token.address), token.balance(&client.address));
+        assert_eq!(client.get_treasury_balance(&token.address), 0);
+        assert_eq!(
+            client.try_claim_creator_revenue(&surveys[0]),
+            Err(Ok(SurveyError::NotFinalized.into()))
+        );
+
+        env.ledger().set_timestamp(3001);
+        let mut platform_total = 0;
+        for survey_id in &surveys {
+            client.finalize_survey(survey_id);
+            let entry = client.get_survey_revenue(survey_id).get(0).unwrap();
+            assert_eq!(entry.platform_share + entry.creator_share, entry.collected);
+            assert_eq!(entry.creator_share, entry.collected * 3333 / 10_000);
+            platform_total += entry.platform_share;
+        }
+        assert_eq!(client.get_treasury_balance(&token.address), platform_total);
+        assert_eq!(owed(&client, &surveys, &token.address), token.balance(&client.address));
+
+        // Cada creador cobra una sola vez su parte
+        let claimed = client.claim_creator_revenue(&surveys[0]);
+        assert_eq!(claimed.get(0).unwrap().amount, 16); // 49 * 3333 / 10000
+        assert_eq!(token.balance(&creators[0]), 16);
+        assert_eq!(
+            client.try_claim_creator_revenue(&surveys[0]),
+            Err(Ok(SurveyError::NothingToClaim.into()))
+        );
+        client.claim_creator_revenue(&surveys[1]);
+        assert_eq!(token.balance(&client.address), platform_total);
+
+        // La tesorería solo entrega lo que le corresponde
+        let treasurer = Address::generate(&env);
+        assert_eq!(
+            client.try_withdraw(&token.address, &treasurer, &(platform_total + 1)),
+            Err(Ok(SurveyError::InsufficientTreasury.into()))
+        );
+        client.withdraw(&token.address, &treasurer, &platform_total);
+        assert_eq!(token.balance(&treasurer), platform_total);
+        assert_eq!(client.get_treasury_balance(&token.address), 0);
+        assert_eq!(token.balance(&client.address), 0);
+    }
+
+    #[test]
+    fn test_refunds_and_forfeited_deposits_in_ledger() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let voters: std::vec::Vec<Address> = (0..2).map(|_| Address::generate(&env)).collect();
+        let mut holders = voters.clone();
+        holders.push(creator.clone());
+        let token = fee_token(&env, &admin, &holders, 1000);
+
+        client.set_creation_policy(&CreationPolicy::Deposit(DepositConfig {
+            token: token.address.clone(),
+            amount: 200,
+            min_turnout: 1,
+        }));
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+        client.set_survey_fee(&survey_id, &token.address, &50);
+        env.ledger().set_timestamp(1500);
+        for voter in &voters {
+            client.vote(&survey_id, voter, &candidates.get(0).unwrap(), &None);
+        }
+        assert_eq!(client.get_survey_revenue(&survey_id).get(0).unwrap().collected, 100);
+
+        // Al cancelar, el depósito pasa a la tesorería y los fees se devuelven
+        client.cancel_survey(&survey_id, &creator);
+        assert!(client.get_deposit(&survey_id).is_none());
+        assert_eq!(client.get_treasury_balance(&token.address), 200);
+        client.claim_refund(&survey_id, &voters[0]);
+        assert_eq!(client.get_survey_revenue(&survey_id).get(0).unwrap().collected, 50);
+        client.claim_refund(&survey_id, &voters[1]);
+        assert_eq!(client.get_survey_revenue(&survey_id).get(0).unwrap().collected, 0);
+        assert_eq!(token.balance(&client.address), client.get_treasury_balance(&token.address));
+    }
+
+    #[test]
+    fn test_creator_share_fixed_at_creation() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let voter = Address::generate(&env);
+        let token = fee_token(&env, &admin, core::slice::from_ref(&voter), 1000);
+        let creator = Address::generate(&env);
+
+        client.set_creator_share(&5000);
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+        client.set_survey_fee(&survey_id, &token.address, &100);
+        client.set_creator_share(&0);
+
+        env.ledger().set_timestamp(1500);
+        client.vote(&survey_id, &voter, &candidates.get(0).unwrap(), &None);
+        env.ledger().set_timestamp(3001);
+        client.finalize_survey(&survey_id);
+
+        let entry = client.get_survey_revenue(&survey_id).get(0).unwrap();
+        assert_eq!((entry.platform_share, entry.creator_share), (50, 50));
+    }
+
+    fn funded_survey(
+        env: &Env,
+        client: &SurveyContractClient,
+        creator: &Address,
+        candidates: &Vec<Address>,
+        token: &token::Client,
+        amount: i128,
+        distribution: PrizeDistribution,
+    ) -> u64 {
+        let survey_id = draft_survey(env, client, creator, candidates);
+        client.fund_prize_pool(&survey_id, &token.address, &amount, &distribution, &1000);
+        survey_id
+    }
+
+    #[test]
+    fn test_prize_pool_for_participants_with_dust() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let token = fee_token(&env, &admin, core::slice::from_ref(&creator), 100);
+        let survey_id =
+            funded_survey(&env, &client, &creator, &candidates, &token, 100, PrizeDistribution::Participants);
+        assert_eq!(token.balance(&client.address), 100);
+
+        env.ledger().set_timestamp(1500);
+        let voters: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+        for voter in &voters {
+            client.vote(&survey_id, voter, &candidates.get(0).unwrap(), &None);
+        }
+        assert_eq!(
+            client.try_claim_reward(&survey_id, &voters[0]),
+            Err(Ok(SurveyError::NotFinalized.into()))
+        );
+
+        env.ledger().set_timestamp(3001);
+        client.finalize_survey(&survey_id);
+        let pool = client.get_prize_pool(&survey_id).unwrap();
+        assert_eq!((pool.reward, pool.claim_deadline), (33, 4001));
+
+        assert_eq!(client.claim_reward(&survey_id, &voters[0]), 33);
+        assert_eq!(client.claim_reward(&survey_id, &voters[1]), 33);
+        for voter in [&voters[0], &Address::generate(&env)] {
+            assert_eq!(
+                client.try_claim_reward(&survey_id, voter),
+                Err(Ok(SurveyError::NothingToClaim.into()))
+            );
+        }
+        assert_eq!(
+            client.try_reclaim_prize_pool(&survey_id),
+            Err(Ok(SurveyError::ClaimPeriodActive.into()))
+        );
+
+        // Tras el plazo, lo no reclamado y el resto del redondeo vuelven al creador
+        env.ledger().set_timestamp(4002);
+        assert_eq!(
+            client.try_claim_reward(&survey_id, &voters[2]),
+            Err(Ok(SurveyError::ClaimPeriodOver.into()))
+        );
+        assert_eq!(client.reclaim_prize_pool(&survey_id), 34);
+        assert_eq!(token.balance(&creator), 34);
+        assert_eq!(token.balance(&voters[0]) + token.balance(&voters[1]), 66);
+        assert_eq!(token.balance(&client.address), 0);
+        assert_eq!(
+            client.try_reclaim_prize_pool(&survey_id),
+            Err(Ok(SurveyError::NothingToClaim.into()))
+        );
+    }
+
+    #[test]
+    fn test_prize_pool_for_tied_winners() {
+        let env = Env::default();
+        let (client, admin, mut candidates) = setup_policy_test(&env);
+        candidates.push_back(Address::generate(&env));
+        let creator = Address::generate(&env);
+        let token = fee_token(&env, &admin, core::slice::from_ref(&creator), 101);
+        let survey_id =
+            funded_survey(&env, &client, &creator, &candidates, &token, 101, PrizeDistribution::Winner);
+
+        env.ledger().set_timestamp(1500);
+        for choice in [0, 1, 0, 1, 2] {
+            client.vote(&survey_id, &Address::generate(&env), &candidates.get(choice).unwrap(), &None);
+        }
+        env.ledger().set_timestamp(3001);
+        client.finalize_survey(&survey_id);
+
+        // Empate entre los dos primeros: 50 cada uno y 1 de resto
+        assert_eq!(token.balance(&candidates.get(0).unwrap()), 50);
+        assert_eq!(token.balance(&candidates.get(1).unwrap()), 50);
+        assert_eq!(token.balance(&candidates.get(2).unwrap()), 0);
+        assert_eq!(
+            client.try_claim_reward(&survey_id, &candidates.get(0).unwrap()),
+            Err(Ok(SurveyError::NothingToClaim.into()))
+        );
+
+        env.ledger().set_timestamp(4001);
+        assert_eq!(
+            client.try_reclaim_prize_pool(&survey_id),
+            Err(Ok(SurveyError::ClaimPeriodActive.into()))
+        );
+        env.ledger().set_timestamp(4002);
+        assert_eq!(client.reclaim_prize_pool(&survey_id), 1);
+        assert_eq!(token.balance(&client.address), 0);
+    }
+
+    #[test]
+    fn test_prize_pool_without_votes_or_cancelled() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let token = fee_token(&env, &admin, core::slice::from_ref(&creator), 300);
+
+        let empty = funded_survey(&env, &client, &creator, &candidates, &token, 100, PrizeDistribution::Winner);
+        let cancelled =
+            funded_survey(&env, &client, &creator, &candidates, &token, 200, PrizeDistribution::Participants);
+        assert_eq!(token.balance(&creator), 0);
+
+        client.cancel_survey(&cancelled, &creator);
+        assert_eq!(client.reclaim_prize_pool(&cancelled), 200);
+
+        // Con la votación abierta el fondo ya no puede retirarse cancelando
+        env.ledger().set_timestamp(1000);
+        assert!(client.try_cancel_survey(&empty, &creator).is_err());
+
+        env.ledger().set_timestamp(3001);
+        client.finalize_survey(&empty);
+        assert_eq!(client.get_prize_pool(&empty).unwrap().paid, 0);
+        env.ledger().set_timestamp(4002);
+        assert_eq!(client.reclaim_prize_pool(&empty), 100);
+        assert_eq!(token.balance(&creator), 300);
+        assert_eq!(
+            client.try_claim_reward(&1000, &creator),
+            Err(Ok(SurveyError::NoPrizePool.into()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Winner prizes need candidates")]
+    fn test_winner_prize_for_questionnaire() {
+        let env = Env::default();
+        let (client, admin, _candidates) = setup_policy_test(&env);
+        let survey_id = create_questionnaire(&env, &client);
+        let creator = client.get_survey(&survey_id).unwrap().creator;
+        let token = fee_token(&env, &admin, core::slice::from_ref(&creator), 100);
+        client.fund_prize_pool(&survey_id, &token.address, &100, &PrizeDistribution::Winner, &1000);
+    }
+
+    #[test]
+    fn test_results_visibility() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let after_close = draft_survey(&env, &client, &creator, &candidates);
+        let after_finalize = draft_survey(&env, &client, &creator, &candidates);
+        client.set_results_visibility(&after_close, &ResultsVisibility::AfterClose, &false);
+        client.set_results_visibility(&after_finalize, &ResultsVisibility::AfterFinalize, &false);
+        assert_eq!(
+            client.get_results_policy(&after_finalize),
+            ResultsPolicy { visibility: ResultsVisibility::AfterFinalize, privileged_access: false }
+        );
+
+        env.ledger().set_timestamp(1500);
+        let voter = Address::generate(&env);
+        let candidate = candidates.get(0).unwrap();
+        client.vote(&after_close, &voter, &candidate, &None);
+        client.vote(&after_finalize, &voter, &candidate, &None);
+        for survey_id in [after_close, after_finalize] {
+            assert_eq!(client.try_get_results(&survey_id), Err(Ok(SurveyError::ResultsHidden.into())));
+            assert_eq!(client.try_get_vote(&survey_id, &voter), Err(Ok(SurveyError::ResultsHidden.into())));
+            assert_eq!(client.try_get_total_votes(&survey_id), Err(Ok(SurveyError::ResultsHidden.into())));
+            assert_eq!(client.try_get_voters(&survey_id), Err(Ok(SurveyError::ResultsHidden.into())));
+            // El votante siempre puede consultar su propio voto
+            assert_eq!(client.get_vote_as(&survey_id, &voter, &voter), Some(candidate.clone()));
+        }
+
+        // Tras el cierre solo se publica la primera
+        env.ledger().set_timestamp(3001);
+        assert_eq!(client.get_results(&after_close).get(0).unwrap().votes, 1);
+        assert_eq!(client.get_vote(&after_close, &voter), Some(candidate.clone()));
+        assert_eq!(client.try_get_results(&after_finalize), Err(Ok(SurveyError::ResultsHidden.into())));
+
+        client.finalize_survey(&after_finalize);
+        assert_eq!(client.get_results(&after_finalize).get(0).unwrap().votes, 1);
+        assert_eq!(client.get_total_votes(&after_finalize), 1);
+        assert_eq!(client.get_voters(&after_finalize).len(), 1);
+    }
+
+    #[test]
+    fn test_privileged_results_access() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let auditor = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        client.add_auditor(&auditor);
+        assert!(client.is_auditor(&auditor));
+
+        let privileged = draft_survey(&env, &client, &creator, &candidates);
+        let sealed = draft_survey(&env, &client, &creator, &candidates);
+        client.set_results_visibility(&privileged, &ResultsVisibility::AfterFinalize, &true);
+        client.set_results_visibility(&sealed, &ResultsVisibility::AfterFinalize, &false);
+
+        env.ledger().set_timestamp(1500);
+        let voter = Address::generate(&env);
+        let candidate = candidates.get(1).unwrap();
+        client.vote(&privileged, &voter, &candidate, &None);
+        client.vote(&sealed, &voter, &candidate, &None);
+
+        assert_eq!(client.get_results_as(&privileged, &creator).get(1).unwrap().votes, 1);
+        assert_eq!(client.get_results_as(&privileged, &auditor).get(1).unwrap().votes, 1);
+        assert_eq!(client.get_vote_as(&privileged, &voter, &auditor), Some(candidate));
+        assert_eq!(
+            client.try_get_results_as(&privileged, &outsider),
+            Err(Ok(SurveyError::ResultsHidden.into()))
+        );
+        for viewer in [&creator, &auditor] {
+            assert_eq!(client.try_get_results_as(&sealed, viewer), Err(Ok(SurveyError::ResultsHidden.into())));
+        }
+
+        client.remove_auditor(&auditor);
+        assert_eq!(
+            client.try_get_results_as(&privileged, &auditor),
+            Err(Ok(SurveyError::ResultsHidden.into()))
+        );
+    }
+
+    #[test]
+    fn test_hidden_questionnaire_results() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+        let survey_id = create_questionnaire(&env, &client);
+        client.set_results_visibility(&survey_id, &ResultsVisibility::AfterClose, &false);
+
+        env.ledger().set_timestamp(1500);
+        let voter = Address::generate(&env);
+        client.submit_response(&survey_id, &voter, &answers(&env, &[2, 0]), &None);
+        assert_eq!(
+            client.try_get_question_results(&survey_id, &0),
+            Err(Ok(SurveyError::ResultsHidden.into()))
+        );
+        assert_eq!(client.try_get_response(&survey_id, &voter), Err(Ok(SurveyError::ResultsHidden.into())));
+
+        env.ledger().set_timestamp(3001);
+        assert_eq!(option_votes(&client, survey_id, 0), [0, 0, 1]);
+        assert_eq!(client.get_response(&survey_id, &voter), Some(answers(&env, &[2, 0])));
+    }
+
+    #[test]
+    #[should_panic(expected = "Survey has already started")]
+    fn test_results_visibility_after_start() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+        let survey_id = draft_survey(&env, &client, &Address::generate(&env), &candidates);
+
+        env.ledger().set_timestamp(1000);
+        client.set_results_visibility(&survey_id, &ResultsVisibility::AfterClose, &false);
+    }
+
+    #[test]
+    fn test_external_ballot_box() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+        let survey_id = draft_survey(&env, &client, &Address::generate(&env), &candidates);
+        let ballot_box = Address::generate(&env);
+        client.add_ballot_box_contract(&ballot_box);
+        client.set_ballot_box(&survey_id, &ballot_box);
+        assert_eq!(client.get_ballot_box(&survey_id), Some(ballot_box));
+
+        // Los votos llegan solo a través de la urna
+        env.ledger().set_timestamp(1500);
+        let voters = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+        assert!(client.try_vote(&survey_id, &voters.get(0).unwrap(), &candidates.get(0).unwrap(), &None).is_err());
+        let counts = Vec::from_array(&env, [0u64, 2]);
+        assert!(client.try_record_tally(&survey_id, &counts, &voters).is_err());
+
+        // No se finaliza hasta que la urna entregue su conteo
+        env.ledger().set_timestamp(3001);
+        assert!(client.try_finalize_survey(&survey_id).is_err());
+        client.record_tally(&survey_id, &counts, &voters);
+        assert_eq!(client.get_results(&survey_id).get(1).unwrap().votes, 2);
+        assert_eq!(client.get_voters(&survey_id), voters);
+        assert!(client.try_record_tally(&survey_id, &counts, &voters).is_err());
+        client.finalize_survey(&survey_id);
+        assert_eq!(client.get_certificate(&survey_id).unwrap().total_votes, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ballot box contract is not approved")]
+    fn test_creator_cannot_be_own_ballot_box() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+        // Una urna retirada deja de servir para nuevas encuestas
+        let ballot_box = Address::generate(&env);
+        client.add_ballot_box_contract(&ballot_box);
+        client.remove_ballot_box_contract(&ballot_box);
+        assert!(client.try_set_ballot_box(&survey_id, &ballot_box).is_err());
+
+        // Con su propia cuenta como urna, el creador escribiría el resultado
+        client.set_ballot_box(&survey_id, &creator);
+    }
+
+    #[test]
+    fn test_ballot_box_surveys_are_free() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let token = fee_token(&env, &admin, &[], 0);
+        client.set_fee_token(&token.address);
+        let ballot_box = Address::generate(&env);
+        client.add_ballot_box_contract(&ballot_box);
+
+        // Un fee propio impide la urna, y la urna impide fijar un fee
+        let charged = draft_survey(&env, &client, &creator, &candidates);
+        client.set_survey_fee(&charged, &token.address, &10);
+        assert!(client.try_set_ballot_box(&charged, &ballot_box).is_err());
+
+        // El fee por defecto no se aplica a las encuestas con urna
+        let boxed = draft_survey(&env, &client, &creator, &candidates);
+        client.set_ballot_box(&boxed, &ballot_box);
+        assert_eq!(client.get_survey_fee(&boxed), None);
+        assert!(client.try_set_survey_fee(&boxed, &token.address, &10).is_err());
+        client.set_survey_fee(&boxed, &token.address, &0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ballot box surveys cannot have participant prizes")]
+    fn test_participant_prize_with_ballot_box() {
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let creator = Address::generate(&env);
+        let token = fee_token(&env, &admin, core::slice::from_ref(&creator), 100);
+        let survey_id =
+            funded_survey(&env, &client, &creator, &candidates, &token, 100, PrizeDistribution::Participants);
+        let ballot_box = Address::generate(&env);
+        client.add_ballot_box_contract(&ballot_box);
+        // Nadie podría reclamar por las papeletas de una urna, así que no se combinan
+        assert!(client.try_set_ballot_box(&survey_id, &ballot_box).is_err());
+
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+        client.set_ballot_box(&survey_id, &ballot_box);
+        client.fund_prize_pool(&survey_id, &token.address, &100, &PrizeDistribution::Participants, &1000);
+    }
+
+    fn votes_for(client: &SurveyContractClient, survey_id: u64, candidate: &Address) -> u64 {
+        client
+            .get_results(&survey_id)
+            .iter()
+            .find(|result| result.candidate == *candidate)
+            .map(|result| result.votes)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_delegation_chain_and_overrides() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Delegation"),
             &String::from_str(&env, "Test"),
             &1000,
             &3000,
             &candidates,
         );
+        let candidate1 = candidates.get(0).unwrap();
+        let candidate2 = candidates.get(1).unwrap();
 
-        // Primer voto
-        client.vote(&survey_id, &voter, &candidate1);
+        // a -> b -> c
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        client.delegate(&a, &b, &DelegationScope::Global);
+        client.delegate(&b, &c, &DelegationScope::Global);
+        assert_eq!(client.get_delegate(&a, &DelegationScope::Global), Some(b.clone()));
 
-        // Segundo voto (debe fallar)
-        client.vote(&survey_id, &voter, &candidate2);
+        // c vota por sí mismo y por toda la cadena
+        client.vote(&survey_id, &c, &candidate1, &None);
+        assert_eq!(votes_for(&client, survey_id, &candidate1), 3);
+        assert_eq!(client.get_cast_by(&survey_id, &a), Some(c.clone()));
+
+        // El voto directo de b reemplaza la delegación y arrastra a a
+        client.vote(&survey_id, &b, &candidate2, &None);
+        assert_eq!(votes_for(&client, survey_id, &candidate1), 1);
+        assert_eq!(votes_for(&client, survey_id, &candidate2), 2);
+        assert_eq!(client.get_cast_by(&survey_id, &a), Some(b.clone()));
+
+        // Finalmente a vota directamente
+        client.vote(&survey_id, &a, &candidate1, &None);
+        assert_eq!(votes_for(&client, survey_id, &candidate1), 2);
+        assert_eq!(votes_for(&client, survey_id, &candidate2), 1);
+        assert_eq!(client.get_cast_by(&survey_id, &a), None);
+        assert_eq!(client.get_total_votes(&survey_id), 3);
+    }
+
+    #[test]
+    fn test_survey_delegation_overrides_global() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Scoped Delegation"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        let candidate1 = candidates.get(0).unwrap();
+        let candidate2 = candidates.get(1).unwrap();
+
+        let delegator = Address::generate(&env);
+        let global_delegate = Address::generate(&env);
+        let survey_delegate = Address::generate(&env);
+        client.delegate(&delegator, &global_delegate, &DelegationScope::Global);
+        client.delegate(&delegator, &survey_delegate, &DelegationScope::Survey(survey_id));
+
+        client.vote(&survey_id, &global_delegate, &candidate1, &None);
+        assert_eq!(votes_for(&client, survey_id, &candidate1), 1);
+
+        client.vote(&survey_id, &survey_delegate, &candidate2, &None);
+        assert_eq!(votes_for(&client, survey_id, &candidate2), 2);
+
+        // Revocar no altera los votos ya emitidos
+        client.undelegate(&delegator, &DelegationScope::Survey(survey_id));
+        assert_eq!(client.get_delegate(&delegator, &DelegationScope::Survey(survey_id)), None);
+        assert_eq!(votes_for(&client, survey_id, &candidate2), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Delegation cycle detected")]
+    fn test_delegation_cycle() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        client.delegate(&a, &b, &DelegationScope::Global);
+        client.delegate(&b, &c, &DelegationScope::Global);
+        client.delegate(&c, &a, &DelegationScope::Global);
+    }
+
+    #[test]
+    #[should_panic(expected = "Delegation chain too deep")]
+    fn test_delegation_max_depth() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+
+        let mut previous = Address::generate(&env);
+        for _ in 0..=MAX_DELEGATION_DEPTH {
+            let next = Address::generate(&env);
+            client.delegate(&next, &previous, &DelegationScope::Global);
+            previous = next;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Delegate has too many delegators")]
+    fn test_delegators_per_delegate_capped() {
+        let env = Env::default();
+        let (client, _admin, _candidates) = setup_policy_test(&env);
+
+        let delegate = Address::generate(&env);
+        for _ in 0..=MAX_DELEGATORS {
+            client.delegate(&Address::generate(&env), &delegate, &DelegationScope::Global);
+        }
+    }
+
+    #[test]
+    fn test_delegated_votes_per_ballot_bounded() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Bounded Delegation"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        let candidate1 = candidates.get(0).unwrap();
+
+        // Dos niveles llenos superan el presupuesto de un solo voto
+        let root = Address::generate(&env);
+        let mut first_level = std::vec::Vec::new();
+        for _ in 0..2 {
+            let delegator = Address::generate(&env);
+            client.delegate(&delegator, &root, &DelegationScope::Global);
+            first_level.push(delegator);
+        }
+        for delegate in &first_level {
+            for _ in 0..MAX_DELEGATORS {
+                client.delegate(&Address::generate(&env), delegate, &DelegationScope::Global);
+            }
+        }
+
+        client.vote(&survey_id, &root, &candidate1, &None);
+        assert_eq!(votes_for(&client, survey_id, &candidate1), 1 + MAX_DELEGATED_VOTES as u64);
+        assert_eq!(client.get_total_votes(&survey_id), 1);
+    }
+
+    // Firma una boleta como lo haría el cliente del votante fuera de la cadena
+    fn sign_ballot(
+        env: &Env,
+        signer: &ed25519_dalek::SigningKey,
+        contract: &Address,
+        ballot: &SignedBallot,
+    ) -> BytesN<64> {
+        use ed25519_dalek::Signer;
+
+        let payload = (Symbol::new(env, "vote_signed"), contract.clone(), ballot.clone());
+        let message: std::vec::Vec<u8> = payload.to_xdr(env).iter().collect();
+        BytesN::from_array(env, &signer.sign(&message).to_bytes())
+    }
+
+    fn ballot(survey_id: u64, candidate: &Address, nonce: u64, max_fee: i128) -> SignedBallot {
+        SignedBallot { survey_id, candidate: candidate.clone(), nonce, max_fee }
+    }
+
+    fn signer_address(env: &Env, signer: &ed25519_dalek::SigningKey) -> Address {
+        use soroban_sdk::xdr::{AccountId, PublicKey, ScAddress, Uint256};
+
+        let account = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+            signer.verifying_key().to_bytes(),
+        ))));
+        Address::try_from_val(env, &account).unwrap()
+    }
+
+    #[test]
+    fn test_vote_signed_by_relayer() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Gasless"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        let second_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Gasless 2"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        let candidate = candidates.get(0).unwrap();
+
+        // Sin autorizaciones simuladas: solo cuenta la firma del votante
+        env.set_auths(&[]);
+        let signer = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let pubkey = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+        let voter = signer_address(&env, &signer);
+        let relayer = Address::generate(&env);
+
+        let first = ballot(survey_id, &candidate, 0, 0);
+        let signature = sign_ballot(&env, &signer, &client.address, &first);
+        client.vote_signed(&first, &pubkey, &relayer, &signature, &None);
+        assert!(client.has_voted(&survey_id, &voter));
+        assert_eq!(client.get_vote(&survey_id, &voter), Some(candidate.clone()));
+        assert_eq!(client.get_nonce(&voter), 1);
+
+        // Repetir la misma boleta en otra encuesta falla por el nonce
+        assert_eq!(
+            client.try_vote_signed(&ballot(second_id, &candidate, 0, 0), &pubkey, &relayer, &signature, &None),
+            Err(Ok(SurveyError::InvalidNonce.into()))
+        );
+
+        let second = ballot(second_id, &candidate, 1, 0);
+        let signature = sign_ballot(&env, &signer, &client.address, &second);
+        client.vote_signed(&second, &pubkey, &relayer, &signature, &None);
+        assert_eq!(client.get_nonce(&voter), 2);
+    }
+
+    #[test]
+    fn test_vote_signed_relayer_pays_fee() {
+        use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+
+        let env = Env::default();
+        let (client, admin, candidates) = setup_policy_test(&env);
+        let relayer = Address::generate(&env);
+        let token = fee_token(&env, &admin, core::slice::from_ref(&relayer), 1000);
+        let creator = Address::generate(&env);
+        let survey_id = draft_survey(&env, &client, &creator, &candidates);
+        client.set_survey_fee(&survey_id, &token.address, &40);
+        env.ledger().set_timestamp(1500);
+        let candidate = candidates.get(0).unwrap();
+
+        let signer = ed25519_dalek::SigningKey::from_bytes(&[11u8; 32]);
+        let pubkey = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+        let voter = signer_address(&env, &signer);
+
+        // El votante firmó un máximo menor al fee vigente
+        let low = ballot(survey_id, &candidate, 0, 39);
+        let signature = sign_ballot(&env, &signer, &client.address, &low);
+        assert!(client.try_vote_signed(&low, &pubkey, &relayer, &signature, &None).is_err());
+
+        // Solo el relayer autoriza: la boleta y el pago del fee
+        let signed = ballot(survey_id, &candidate, 0, 40);
+        let signature = sign_ballot(&env, &signer, &client.address, &signed);
+        let proof: Option<Vec<BytesN<32>>> = None;
+        env.mock_auths(&[MockAuth {
+            address: &relayer,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "vote_signed",
+                args: (signed.clone(), pubkey.clone(), relayer.clone(), signature.clone(), proof).into_val(&env),
+                sub_invokes: &[MockAuthInvoke {
+                    contract: &token.address,
+                    fn_name: "transfer",
+                    args: (relayer.clone(), client.address.clone(), 40i128).into_val(&env),
+                    sub_invokes: &[],
+                }],
+            },
+        }]);
+        client.vote_signed(&signed, &pubkey, &relayer, &signature, &None);
+        assert!(client.has_voted(&survey_id, &voter));
+        assert_eq!(token.balance(&relayer), 960);
+        assert_eq!(token.balance(&client.address), 40);
+
+        // Si la encuesta se cancela, el fee vuelve al relayer
+        env.mock_all_auths();
+        client.cancel_survey(&survey_id, &creator);
+        assert_eq!(client.claim_refund(&survey_id, &voter), 40);
+        assert_eq!(token.balance(&relayer), 1000);
+    }
+
+    #[test]
+    fn test_vote_signed_rejects_bad_signature() {
+        let env = Env::default();
+        let (client, _admin, candidates) = setup_policy_test(&env);
+
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Forged"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        let candidate = candidates.get(0).unwrap();
+        let other_candidate = candidates.get(1).unwrap();
+
+        let signer = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let pubkey = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+
+        // La firma es para otro candidato
+        let signature = sign_ballot(&env, &signer, &client.address, &ballot(survey_id, &other_candidate, 0, 0));
+        let relayer = Address::generate(&env);
+        assert!(client
+            .try_vote_signed(&ballot(survey_id, &candidate, 0, 0), &pubkey, &relayer, &signature, &None)
+            .is_err());
+        assert!(!client.has_voted(&survey_id, &signer_address(&env, &signer)));
+        assert_eq!(client.get_nonce(&signer_address(&env, &signer)), 0);
+    }
+
+    #[test]
+    fn test_shared_client() {
+        let env = Env::default();
+        let (contract_client, _admin, candidates) = setup_policy_test(&env);
+
+        // El cliente de dappcenvote-types habla con el contrato desplegado
+        let client = SurveyClient::new(&env, &contract_client.address);
+        env.ledger().set_timestamp(1500);
+        let survey_id = client.create_survey(
+            &Address::generate(&env),
+            &String::from_str(&env, "Shared Types"),
+            &String::from_str(&env, "Test"),
+            &1000,
+            &3000,
+            &candidates,
+        );
+        client.vote(&survey_id, &Address::generate(&env), &candidates.get(0).unwrap(), &None);
+
+        let results = client.get_results(&survey_id);
+        assert_eq!(results.get(0).unwrap(), VoteResult { candidate: candidates.get(0).unwrap(), votes: 1 });
+        assert_eq!(client.get_survey(&survey_id).unwrap().created_at, 1500);
     }
 }
@@ -0,0 +1,543 @@
+#![no_std]
+//! Encuestas recurrentes. Una serie guarda una plantilla y cada instancia es una
+//! encuesta normal del contrato de encuestas, creada por este contrato en nombre
+//! del creador de la serie con las fechas que le corresponden. Cualquiera puede
+//! abrir la siguiente instancia desde que empieza la anterior, así que no hace
+//! falta que el creador la cree a mano, y aun así le queda tiempo para
+//! configurarla mientras es un borrador.
+use dappcenvote_types::{
+    candidate_registry::CandidateRegistryClient,
+    survey::{CreationPolicy, OptionResult, SurveyClient, VoteResult, MAX_OPTIONS, MAX_QUESTIONS},
+    survey_series::{
+        DataKey, InstanceOpened, Series, SeriesCreated, SurveySeriesError, SurveySeriesInterface,
+        SurveyTemplate,
+    },
+};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, Vec};
+
+const CONTRACT_VERSION: u32 = 1;
+
+// Límite de instancias por serie (dos años de encuestas semanales)
+const MAX_OCCURRENCES: u32 = 104;
+
+#[contract]
+pub struct SurveyScheduler;
+
+#[contractimpl]
+impl SurveyScheduler {
+    /// Inicializa el contrato al desplegarlo, con el administrador y el contrato de encuestas
+    pub fn __constructor(env: Env, admin: Address, survey_contract: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        env.storage().instance().set(&DataKey::Survey, &survey_contract);
+        env.storage().instance().extend_ttl(100, 8640000);
+    }
+}
+
+#[contractimpl]
+impl SurveySeriesInterface for SurveyScheduler {
+    /// Reemplaza el código del contrato conservando su almacenamiento (solo administrador)
+    /// Debe seguirse de `migrate` si la nueva versión cambia el formato de los datos
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Migra el almacenamiento a la versión de este código (solo administrador)
+    /// Sin efecto si el almacenamiento ya está en esa versión
+    /// La versión 1 es el formato original, por lo que aún no hay transformaciones
+    fn migrate(env: Env) {
+        Self::require_admin(&env);
+
+        let version = Self::version(env.clone());
+        if version >= CONTRACT_VERSION {
+            return; // Ya está al día; repetir la llamada no es un error
+        }
+
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+    }
+
+    /// Obtiene la versión del formato de almacenamiento
+    fn version(env: Env) -> u32 {
+        let key = DataKey::ContractVersion;
+        env.storage().instance().get(&key).unwrap_or(1)
+    }
+
+    /// Crea una serie de `occurrences` encuestas a partir de una plantilla
+    /// Las instancias no se solapan: cada una dura como mucho `interval_secs`
+    /// La plantilla se valida como al crear la encuesta, para no fallar al abrirla
+    fn create_survey_series(
+        env: Env,
+        creator: Address,
+        template: SurveyTemplate,
+        interval_secs: u64,
+        occurrences: u32,
+    ) -> u64 {
+        creator.require_auth();
+
+        if template.candidates.is_empty() == template.questions.is_empty() {
+            panic!("Template must have either candidates or questions");
+        }
+        if occurrences == 0 || occurrences > MAX_OCCURRENCES {
+            panic!("Invalid number of occurrences");
+        }
+        if template.duration == 0 || template.duration > interval_secs {
+            panic!("Duration must be positive and fit in the interval");
+        }
+        if template.first_start < env.ledger().timestamp() {
+            panic!("First instance must start in the future");
+        }
+        // La última instancia debe terminar dentro del rango de u64
+        let last_end = interval_secs
+            .checked_mul(occurrences as u64 - 1)
+            .and_then(|offset| offset.checked_add(template.first_start))
+            .and_then(|start| start.checked_add(template.duration));
+        if last_end.is_none() {
+            panic_with_error!(&env, SurveySeriesError::InvalidSchedule);
+        }
+        Self::validate_template(&env, &template);
+
+        let count_key = DataKey::SeriesCount;
+        let series_id: u64 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+        let series = Series {
+            series_id,
+            creator: creator.clone(),
+            template,
+            interval_secs,
+            occurrences,
+            opened: 0,
+        };
+        Self::save_series(&env, &series);
+        Self::set_persistent(&env, &count_key, &series_id);
+
+        SeriesCreated { series_id, creator, interval_secs, occurrences }.publish(&env);
+        series_id
+    }
+
+    /// Crea la siguiente instancia de la serie (cualquiera), desde el inicio de la
+    /// anterior; la primera, en cualquier momento. Así el creador puede configurarla
+    /// como borrador antes de su inicio. Las fechas salen de la plantilla, no de
+    /// cuándo se abre; los turnos que ya terminaron sin abrirse se saltan.
+    /// Retorna el id de la encuesta
+    fn open_next_instance(env: Env, series_id: u64) -> u64 {
+        let mut series = Self::get_series(env.clone(), series_id).expect("Series not found");
+        let now = env.ledger().timestamp();
+        while series.opened < series.occurrences
+            && Self::instance_start(&series, series.opened) + series.template.duration < now
+        {
+            series.opened += 1;
+        }
+        if series.opened >= series.occurrences {
+            panic_with_error!(&env, SurveySeriesError::SeriesComplete);
+        }
+        let start_date = Self::instance_start(&series, series.opened);
+        if series.opened > 0 && now < start_date - series.interval_secs {
+            panic_with_error!(&env, SurveySeriesError::NotDue);
+        }
+        let end_date = start_date + series.template.duration;
+
+        // El creador de la serie figura como creador de cada instancia
+        let survey_client = Self::survey_client(&env);
+        let creator = &series.creator;
+        let template = &series.template;
+        let survey_id = if template.questions.is_empty() {
+            survey_client.create_survey_for(
+                creator,
+                &template.name,
+                &template.description,
+                &start_date,
+                &end_date,
+                &template.candidates,
+            )
+        } else {
+            survey_client.create_questionnaire_for(
+                creator,
+                &template.name,
+                &template.description,
+                &start_date,
+                &end_date,
+                &template.questions,
+            )
+        };
+
+        let instances_key = DataKey::Instances(series_id);
+        let mut instances = Self::get_instances(env.clone(), series_id);
+        instances.push_back(survey_id);
+        Self::set_persistent(&env, &instances_key, &instances);
+
+        let index = series.opened;
+        series.opened += 1;
+        Self::save_series(&env, &series);
+        InstanceOpened { series_id, survey_id, index }.publish(&env);
+        survey_id
+    }
+
+    /// Obtiene una serie
+    fn get_series(env: Env, series_id: u64) -> Option<Series> {
+        env.storage().persistent().get(&DataKey::Series(series_id))
+    }
+
+    /// Obtiene el número de series creadas
+    fn get_series_count(env: Env) -> u64 {
+        env.storage().persistent().get(&DataKey::SeriesCount).unwrap_or(0)
+    }
+
+    /// Obtiene los ids de encuesta de las instancias abiertas, en orden
+    fn get_instances(env: Env, series_id: u64) -> Vec<u64> {
+        env.storage().persistent()
+            .get(&DataKey::Instances(series_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Suma por candidato los votos de todas las instancias abiertas
+    /// Falla mientras alguna instancia oculte sus resultados
+    fn get_series_results(env: Env, series_id: u64) -> Vec<VoteResult> {
+        let series = Self::get_series(env.clone(), series_id).expect("Series not found");
+        let survey_client = Self::survey_client(&env);
+
+        let mut totals = Vec::new(&env);
+        for candidate in series.template.candidates.iter() {
+            totals.push_back(VoteResult { candidate, votes: 0 });
+        }
+        for survey_id in Self::get_instances(env.clone(), series_id).iter() {
+            for (i, result) in survey_client.get_results(&survey_id).iter().enumerate() {
+                let mut total = totals.get(i as u32).unwrap();
+                total.votes += result.votes;
+                totals.set(i as u32, total);
+            }
+        }
+        totals
+    }
+
+    /// Suma por opción las respuestas a una pregunta en todas las instancias abiertas
+    fn get_series_question_results(env: Env, series_id: u64, question: u32) -> Vec<OptionResult> {
+        let series = Self::get_series(env.clone(), series_id).expect("Series not found");
+        let survey_client = Self::survey_client(&env);
+
+        let mut totals = Vec::new(&env);
+        let template_question = series.template.questions.get(question).expect("Question not found");
+        for label in template_question.options.iter() {
+            totals.push_back(OptionResult { label, votes: 0 });
+        }
+        for survey_id in Self::get_instances(env.clone(), series_id).iter() {
+            for (i, result) in survey_client.get_question_results(&survey_id, &question).iter().enumerate() {
+                let mut total = totals.get(i as u32).unwrap();
+                total.votes += result.votes;
+                totals.set(i as u32, total);
+            }
+        }
+        totals
+    }
+
+    /// Obtiene la participación de cada instancia abierta, en orden
+    fn get_series_turnout(env: Env, series_id: u64) -> Vec<u64> {
+        let survey_client = Self::survey_client(&env);
+
+        let mut turnout = Vec::new(&env);
+        for survey_id in Self::get_instances(env.clone(), series_id).iter() {
+            turnout.push_back(survey_client.get_total_votes(&survey_id));
+        }
+        turnout
+    }
+}
+
+impl SurveyScheduler {
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        admin
+    }
+
+    fn survey_client(env: &Env) -> SurveyClient<'_> {
+        let survey: Address = env.storage().instance()
+            .get(&DataKey::Survey)
+            .expect("Contract not initialized");
+        SurveyClient::new(env, &survey)
+    }
+
+    // Inicio de la instancia `index`; create_survey_series garantiza que no desborda
+    fn instance_start(series: &Series, index: u32) -> u64 {
+        series.template.first_start + index as u64 * series.interval_secs
+    }
+
+    // Las mismas reglas que aplica survey al crear cada instancia, más la política
+    // de creación: un depósito exigiría la firma del creador en cada apertura
+    fn validate_template(env: &Env, template: &SurveyTemplate) {
+        let survey_client = Self::survey_client(env);
+        if let CreationPolicy::Deposit(_) = survey_client.get_creation_policy() {
+            panic!("Series cannot be created under the Deposit policy");
+        }
+        if let Some(registries) = survey_client.get_registries() {
+            let candidate_registry = CandidateRegistryClient::new(env, &registries.candidate_registry);
+            for candidate in template.candidates.iter() {
+                if !candidate_registry.candidate_exists(&candidate) {
+                    panic!("Candidate is not registered");
+                }
+            }
+        }
+        if template.questions.len() > MAX_QUESTIONS {
+            panic!("Too many questions");
+        }
+        for question in template.questions.iter() {
+            if question.prompt.is_empty() {
+                panic!("Question prompt cannot be empty");
+            }
+            if question.options.len() < 2 {
+                panic!("Question must have at least two options");
+            }
+            if question.options.len() > MAX_OPTIONS {
+                panic!("Too many options");
+            }
+        }
+    }
+
+    fn save_series(env: &Env, series: &Series) {
+        Self::set_persistent(env, &DataKey::Series(series.series_id), series);
+    }
+
+    fn set_persistent<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(key, 100, 8640000);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use candidate_registry::{CandidateRegistry, CandidateRegistryClient as RegistryClient};
+    use dappcenvote_types::survey::{DepositConfig, Question, ResultsVisibility};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+        IntoVal, String,
+    };
+    use survey::{SurveyContract, SurveyContractClient};
+
+    const WEEK: u64 = 7 * 24 * 3600;
+
+    fn setup<'a>() -> (Env, SurveyContractClient<'a>, SurveySchedulerClient<'a>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(500);
+
+        let admin = Address::generate(&env);
+        let survey = SurveyContractClient::new(&env, &env.register(SurveyContract, (admin.clone(),)));
+        let client = SurveySchedulerClient::new(
+            &env,
+            &env.register(SurveyScheduler, (admin.clone(), survey.address.clone())),
+        );
+        survey.set_scheduler(&client.address);
+        (env, survey, client)
+    }
+
+    fn template(env: &Env, candidates: Vec<Address>, questions: Vec<Question>) -> SurveyTemplate {
+        SurveyTemplate {
+            name: String::from_str(env, "Delegado semanal"),
+            description: String::from_str(env, "Encuesta recurrente"),
+            candidates,
+            questions,
+            first_start: 1000,
+            duration: 3 * 24 * 3600,
+        }
+    }
+
+    #[test]
+    fn test_weekly_series() {
+        let (env, survey, client) = setup();
+        let candidates = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+        let creator = Address::generate(&env);
+        let series_id = client.create_survey_series(&creator, &template(&env, candidates.clone(), Vec::new(&env)), &WEEK, &2);
+        assert_eq!(series_id, 1);
+        assert_eq!(client.get_series_count(), 1);
+
+        // La primera se abre antes de su inicio; la segunda, no hasta que empiece la primera
+        let first = client.open_next_instance(&series_id);
+        let opened = survey.get_survey(&first).unwrap();
+        assert_eq!(opened.start_date, 1000);
+        assert_eq!(opened.end_date, 1000 + 3 * 24 * 3600);
+        let result = client.try_open_next_instance(&series_id);
+        assert_eq!(result.err(), Some(Ok(SurveySeriesError::NotDue.into())));
+
+        env.ledger().set_timestamp(1000);
+        survey.vote(&first, &Address::generate(&env), &candidates.get(0).unwrap(), &None);
+        survey.vote(&first, &Address::generate(&env), &candidates.get(1).unwrap(), &None);
+
+        // La segunda se abre tarde, pero conserva las fechas de su turno
+        env.ledger().set_timestamp(1000 + WEEK + 3600);
+        let second = client.open_next_instance(&series_id);
+        assert_eq!(survey.get_survey(&second).unwrap().start_date, 1000 + WEEK);
+        survey.vote(&second, &Address::generate(&env), &candidates.get(0).unwrap(), &None);
+
+        assert_eq!(client.get_instances(&series_id), Vec::from_array(&env, [first, second]));
+        assert_eq!(client.get_series(&series_id).unwrap().opened, 2);
+        let results = client.get_series_results(&series_id);
+        assert_eq!(results.get(0).unwrap().votes, 2);
+        assert_eq!(results.get(1).unwrap().votes, 1);
+        assert_eq!(client.get_series_turnout(&series_id), Vec::from_array(&env, [2u64, 1]));
+
+        env.ledger().set_timestamp(1000 + 2 * WEEK);
+        let result = client.try_open_next_instance(&series_id);
+        assert_eq!(result.err(), Some(Ok(SurveySeriesError::SeriesComplete.into())));
+    }
+
+    #[test]
+    fn test_questionnaire_series() {
+        let (env, survey, client) = setup();
+        let options = Vec::from_array(&env, [String::from_str(&env, "Sí"), String::from_str(&env, "No")]);
+        let question = Question { prompt: String::from_str(&env, "¿Satisfecho?"), options };
+        let questions = Vec::from_array(&env, [question]);
+        let series_id = client.create_survey_series(&Address::generate(&env), &template(&env, Vec::new(&env), questions), &WEEK, &3);
+
+        for week in 0..2 {
+            env.ledger().set_timestamp(1000 + week * WEEK);
+            let survey_id = client.open_next_instance(&series_id);
+            survey.submit_response(&survey_id, &Address::generate(&env), &Vec::from_array(&env, [week as u32]), &None);
+        }
+
+        let results = client.get_series_question_results(&series_id, &0);
+        assert_eq!(results.get(0).unwrap().votes, 1);
+        assert_eq!(results.get(1).unwrap().votes, 1);
+        assert_eq!(client.get_series_turnout(&series_id), Vec::from_array(&env, [1u64, 1]));
+    }
+
+    #[test]
+    fn test_instances_belong_to_series_creator() {
+        let (env, survey, client) = setup();
+        let candidates = Vec::from_array(&env, [Address::generate(&env)]);
+        let creator = Address::generate(&env);
+        survey.set_creation_policy(&CreationPolicy::Allowlist);
+        survey.add_creator(&creator);
+
+        let series_id = client.create_survey_series(&creator, &template(&env, candidates.clone(), Vec::new(&env)), &WEEK, &2);
+        env.ledger().set_timestamp(1000);
+        let survey_id = client.open_next_instance(&series_id);
+        assert_eq!(survey.get_survey(&survey_id).unwrap().creator, creator);
+
+        // Una serie de alguien fuera de la lista no puede abrir instancias
+        let outsider = client.create_survey_series(&Address::generate(&env), &template(&env, candidates, Vec::new(&env)), &WEEK, &2);
+        assert!(client.try_open_next_instance(&outsider).is_err());
+    }
+
+    #[test]
+    fn test_expired_slots_are_skipped() {
+        let (env, survey, client) = setup();
+        let candidates = Vec::from_array(&env, [Address::generate(&env)]);
+        let series_id = client.create_survey_series(&Address::generate(&env), &template(&env, candidates, Vec::new(&env)), &WEEK, &3);
+
+        // El primer turno terminó sin abrirse: se salta y se abre el segundo
+        env.ledger().set_timestamp(1000 + 3 * 24 * 3600 + 1);
+        let survey_id = client.open_next_instance(&series_id);
+        assert_eq!(survey.get_survey(&survey_id).unwrap().start_date, 1000 + WEEK);
+        assert_eq!(client.get_series(&series_id).unwrap().opened, 2);
+        assert_eq!(client.get_instances(&series_id), Vec::from_array(&env, [survey_id]));
+
+        // Si todos los turnos restantes terminaron, la serie está completa
+        env.ledger().set_timestamp(1000 + 3 * WEEK);
+        let result = client.try_open_next_instance(&series_id);
+        assert_eq!(result.err(), Some(Ok(SurveySeriesError::SeriesComplete.into())));
+    }
+
+    #[test]
+    fn test_instances_open_as_drafts() {
+        let (env, survey, client) = setup();
+        let candidates = Vec::from_array(&env, [Address::generate(&env)]);
+        let series_id = client.create_survey_series(&Address::generate(&env), &template(&env, candidates, Vec::new(&env)), &WEEK, &2);
+
+        // El creador configura la instancia antes de que empiece
+        let survey_id = client.open_next_instance(&series_id);
+        survey.set_results_visibility(&survey_id, &ResultsVisibility::AfterClose, &false);
+        assert_eq!(survey.get_results_policy(&survey_id).visibility, ResultsVisibility::AfterClose);
+
+        env.ledger().set_timestamp(1000);
+        assert!(survey.try_set_results_visibility(&survey_id, &ResultsVisibility::Live, &false).is_err());
+    }
+
+    #[test]
+    fn test_open_needs_no_creator_signature() {
+        let (env, survey, client) = setup();
+        let candidates = Vec::from_array(&env, [Address::generate(&env)]);
+        let creator = Address::generate(&env);
+        let template = template(&env, candidates, Vec::new(&env));
+
+        // Solo el creador firma la serie; cualquiera abre las instancias sin más firmas
+        env.mock_auths(&[MockAuth {
+            address: &creator,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "create_survey_series",
+                args: (&creator, template.clone(), WEEK, 2u32).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        let series_id = client.create_survey_series(&creator, &template, &WEEK, &2);
+
+        env.mock_auths(&[]);
+        let survey_id = client.open_next_instance(&series_id);
+        assert_eq!(survey.get_survey(&survey_id).unwrap().creator, creator);
+    }
+
+    #[test]
+    #[should_panic(expected = "Series cannot be created under the Deposit policy")]
+    fn test_deposit_policy_rejected() {
+        let (env, survey, client) = setup();
+        let candidates = Vec::from_array(&env, [Address::generate(&env)]);
+        let config = DepositConfig { token: Address::generate(&env), amount: 100, min_turnout: 1 };
+        survey.set_creation_policy(&CreationPolicy::Deposit(config));
+
+        client.create_survey_series(&Address::generate(&env), &template(&env, candidates, Vec::new(&env)), &WEEK, &2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Candidate is not registered")]
+    fn test_unregistered_candidates_rejected() {
+        let (env, survey, client) = setup();
+        let admin = Address::generate(&env);
+        let registry = RegistryClient::new(&env, &env.register(CandidateRegistry, (admin,)));
+        survey.set_registries(&Address::generate(&env), &registry.address);
+
+        let registered = Address::generate(&env);
+        registry.register_candidate(&registered, &String::from_str(&env, "Ana"), &String::from_str(&env, "RFC"));
+        let candidates = Vec::from_array(&env, [registered, Address::generate(&env)]);
+        client.create_survey_series(&Address::generate(&env), &template(&env, candidates, Vec::new(&env)), &WEEK, &2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Question must have at least two options")]
+    fn test_invalid_questions_rejected() {
+        let (env, _, client) = setup();
+        let options = Vec::from_array(&env, [String::from_str(&env, "Sí")]);
+        let question = Question { prompt: String::from_str(&env, "¿Satisfecho?"), options };
+        let questions = Vec::from_array(&env, [question]);
+        client.create_survey_series(&Address::generate(&env), &template(&env, Vec::new(&env), questions), &WEEK, &2);
+    }
+
+    #[test]
+    fn test_schedule_overflow() {
+        let (env, _, client) = setup();
+        let candidates = Vec::from_array(&env, [Address::generate(&env)]);
+        let result = client.try_create_survey_series(
+            &Address::generate(&env),
+            &template(&env, candidates, Vec::new(&env)),
+            &(u64::MAX / 2),
+            &3,
+        );
+        assert_eq!(result.err(), Some(Ok(SurveySeriesError::InvalidSchedule.into())));
+    }
+
+    #[test]
+    #[should_panic(expected = "Duration must be positive and fit in the interval")]
+    fn test_overlapping_instances() {
+        let (env, _, client) = setup();
+        let candidates = Vec::from_array(&env, [Address::generate(&env)]);
+        client.create_survey_series(&Address::generate(&env), &template(&env, candidates, Vec::new(&env)), &3600, &2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Template must have either candidates or questions")]
+    fn test_empty_template() {
+        let (env, _, client) = setup();
+        client.create_survey_series(&Address::generate(&env), &template(&env, Vec::new(&env), Vec::new(&env)), &WEEK, &2);
+    }
+}
@@ -0,0 +1,489 @@
+//! Herramienta de custodios para las elecciones cifradas del contrato
+//! `encrypted_ballot`: generación distribuida de la clave (Feldman conjunto),
+//! cifrado de papeletas con sus pruebas, descifrados parciales y cálculo del
+//! conteo agregado.
+//!
+//! Los transcritos de Fiat-Shamir se construyen byte a byte igual que en el
+//! contrato: `sha256(etiqueta || survey_id || contexto || puntos)`, con los
+//! puntos de G1 sin comprimir (96 bytes) y el resultado reducido módulo r.
+use anyhow::{anyhow, ensure, Result};
+use ark_bls12_381::{Fr, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, Field, PrimeField, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const KEY_TAG: &[u8] = b"dappcenvote/key";
+pub const BIT_TAG: &[u8] = b"dappcenvote/bit";
+pub const SUM_TAG: &[u8] = b"dappcenvote/sum";
+pub const SHARE_TAG: &[u8] = b"dappcenvote/share";
+
+/// Punto de G1 sin comprimir, en el formato de las funciones host de Soroban
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Point(#[serde(with = "hex::serde")] pub [u8; 96]);
+
+/// Escalar de Fr en big-endian
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Scalar(#[serde(with = "hex::serde")] pub [u8; 32]);
+
+impl Point {
+    fn from_group(point: G1Projective) -> Self {
+        let mut out = [0u8; 96];
+        point
+            .into_affine()
+            .serialize_uncompressed(&mut out[..])
+            .expect("G1 points are 96 bytes uncompressed");
+        Point(out)
+    }
+
+    // Rechaza puntos fuera de la curva o del subgrupo, como el host
+    fn to_group(self) -> Result<G1Projective> {
+        let point = G1Affine::deserialize_uncompressed(&self.0[..])
+            .map_err(|_| anyhow!("invalid G1 point"))?;
+        Ok(point.into())
+    }
+}
+
+impl Scalar {
+    fn from_field(value: Fr) -> Self {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&value.into_bigint().to_bytes_be());
+        Scalar(out)
+    }
+
+    fn to_field(self) -> Fr {
+        Fr::from_be_bytes_mod_order(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyProof {
+    pub commitment: Point,
+    pub response: Scalar,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EqualityProof {
+    pub commitment_g: Point,
+    pub commitment_h: Point,
+    pub response: Scalar,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitProof {
+    pub challenge_zero: Scalar,
+    pub challenge_one: Scalar,
+    pub response_zero: Scalar,
+    pub response_one: Scalar,
+}
+
+/// Cifrado ElGamal exponencial (r·G, m·G + r·H)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ciphertext {
+    pub a: Point,
+    pub b: Point,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedChoice {
+    pub ciphertext: Ciphertext,
+    pub proof: BitProof,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedBallot {
+    pub choices: Vec<EncryptedChoice>,
+    pub sum_proof: EqualityProof,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionShare {
+    pub share: Point,
+    pub proof: EqualityProof,
+}
+
+/// Aportación de un custodio a la clave: compromisos públicos de su polinomio,
+/// prueba de conocimiento del término independiente y una evaluación secreta
+/// para cada custodio (`shares[j - 1]` es para el custodio `j`)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dealing {
+    pub index: u32,
+    pub commitments: Vec<Point>,
+    pub proof: KeyProof,
+    pub shares: Vec<Scalar>,
+}
+
+fn challenge(tag: &[u8], survey_id: u64, context: &[u8], points: &[G1Projective]) -> Fr {
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(survey_id.to_be_bytes());
+    hasher.update(context);
+    for point in points {
+        hasher.update(Point::from_group(*point).0);
+    }
+    Fr::from_be_bytes_mod_order(&hasher.finalize())
+}
+
+fn generator() -> G1Projective {
+    G1Projective::generator()
+}
+
+// Prueba de Chaum-Pedersen de log_G(x_point) = log_base(z_point) = secret
+#[allow(clippy::too_many_arguments)]
+fn prove_equality<R: RngCore + CryptoRng>(
+    tag: &[u8],
+    survey_id: u64,
+    context: &[u8],
+    base: G1Projective,
+    x_point: G1Projective,
+    z_point: G1Projective,
+    secret: Fr,
+    rng: &mut R,
+) -> EqualityProof {
+    let k = Fr::rand(rng);
+    let (commitment_g, commitment_h) = (generator() * k, base * k);
+    let c = challenge(tag, survey_id, context, &[base, x_point, z_point, commitment_g, commitment_h]);
+    EqualityProof {
+        commitment_g: Point::from_group(commitment_g),
+        commitment_h: Point::from_group(commitment_h),
+        response: Scalar::from_field(k + c * secret),
+    }
+}
+
+fn verify_equality(
+    tag: &[u8],
+    survey_id: u64,
+    context: &[u8],
+    base: G1Projective,
+    x_point: G1Projective,
+    z_point: G1Projective,
+    proof: &EqualityProof,
+) -> Result<bool> {
+    let commitment_g = proof.commitment_g.to_group()?;
+    let commitment_h = proof.commitment_h.to_group()?;
+    let c = challenge(tag, survey_id, context, &[base, x_point, z_point, commitment_g, commitment_h]);
+    let s = proof.response.to_field();
+    Ok(generator() * s == commitment_g + x_point * c && base * s == commitment_h + z_point * c)
+}
+
+/// Genera la aportación del custodio `index` (desde 1) a una clave de
+/// `threshold` de `trustees`
+pub fn deal<R: RngCore + CryptoRng>(
+    survey_id: u64,
+    index: u32,
+    threshold: u32,
+    trustees: u32,
+    rng: &mut R,
+) -> Result<Dealing> {
+    ensure!(threshold >= 1 && threshold <= trustees, "threshold must be between 1 and the number of trustees");
+    ensure!(index >= 1 && index <= trustees, "trustee index must be between 1 and the number of trustees");
+
+    let coefficients: Vec<Fr> = (0..threshold).map(|_| Fr::rand(rng)).collect();
+    let commitments: Vec<G1Projective> = coefficients.iter().map(|a| generator() * a).collect();
+
+    // Schnorr sobre el término independiente: impide elegir la clave propia
+    // en función de las de los demás
+    let k = Fr::rand(rng);
+    let commitment = generator() * k;
+    let c = challenge(KEY_TAG, survey_id, &index.to_be_bytes(), &[commitments[0], commitment]);
+    let proof = KeyProof {
+        commitment: Point::from_group(commitment),
+        response: Scalar::from_field(k + c * coefficients[0]),
+    };
+
+    let shares = (1..=trustees)
+        .map(|j| Scalar::from_field(evaluate(&coefficients, j)))
+        .collect();
+    Ok(Dealing {
+        index,
+        commitments: commitments.into_iter().map(Point::from_group).collect(),
+        proof,
+        shares,
+    })
+}
+
+fn evaluate(coefficients: &[Fr], index: u32) -> Fr {
+    let x = Fr::from(index as u64);
+    coefficients.iter().rev().fold(Fr::zero(), |acc, a| acc * x + a)
+}
+
+// Σ index^k · C_k
+fn commitment_at(commitments: &[Point], index: u32) -> Result<G1Projective> {
+    let x = Fr::from(index as u64);
+    let mut power = Fr::from(1u64);
+    let mut point = G1Projective::zero();
+    for commitment in commitments {
+        point += commitment.to_group()? * power;
+        power *= x;
+    }
+    Ok(point)
+}
+
+/// Comprueba que la evaluación recibida de otro custodio coincide con sus compromisos
+pub fn verify_share(commitments: &[Point], index: u32, share: &Scalar) -> Result<bool> {
+    Ok(generator() * share.to_field() == commitment_at(commitments, index)?)
+}
+
+/// Llave secreta del custodio: suma de las evaluaciones que recibió de todos
+pub fn combine_shares(shares: &[Scalar]) -> Scalar {
+    Scalar::from_field(shares.iter().map(|share| share.to_field()).sum())
+}
+
+/// Suma los compromisos de todos los custodios; el primero es la clave de la elección
+pub fn aggregate_commitments(dealings: &[Vec<Point>]) -> Result<Vec<Point>> {
+    let threshold = dealings.first().map(Vec::len).unwrap_or(0);
+    let mut aggregate = vec![G1Projective::zero(); threshold];
+    for commitments in dealings {
+        ensure!(commitments.len() == threshold, "all trustees must commit to the same threshold");
+        for (sum, commitment) in aggregate.iter_mut().zip(commitments) {
+            *sum += commitment.to_group()?;
+        }
+    }
+    Ok(aggregate.into_iter().map(Point::from_group).collect())
+}
+
+/// Clave de verificación del custodio `index`, derivada de los compromisos sumados
+pub fn verification_key(commitments: &[Point], index: u32) -> Result<Point> {
+    Ok(Point::from_group(commitment_at(commitments, index)?))
+}
+
+fn bit_context(voter: &[u8], candidate: usize) -> Vec<u8> {
+    let mut context = voter.to_vec();
+    context.extend_from_slice(&(candidate as u32).to_be_bytes());
+    context
+}
+
+/// Cifra un voto por el candidato `choice` de `candidates`. `voter` es el XDR
+/// de la dirección del votante, que liga las pruebas a quien emite la papeleta
+pub fn encrypt_ballot<R: RngCore + CryptoRng>(
+    public_key: &Point,
+    survey_id: u64,
+    voter: &[u8],
+    choice: usize,
+    candidates: usize,
+    rng: &mut R,
+) -> Result<EncryptedBallot> {
+    ensure!(choice < candidates, "choice is not a candidate");
+    let h = public_key.to_group()?;
+    let g = generator();
+
+    let mut choices = Vec::with_capacity(candidates);
+    let (mut sum_a, mut sum_b, mut sum_r) = (G1Projective::zero(), G1Projective::zero(), Fr::zero());
+    for candidate in 0..candidates {
+        let m = usize::from(candidate == choice);
+        let r = Fr::rand(rng);
+        let a = g * r;
+        let b = h * r + if m == 1 { g } else { G1Projective::zero() };
+        let shifted = [b, b - g];
+
+        // Rama simulada para el valor que no se cifró y rama real para el que sí
+        let fake = 1 - m;
+        let (c_fake, s_fake) = (Fr::rand(rng), Fr::rand(rng));
+        let fake_commitments = (g * s_fake - a * c_fake, h * s_fake - shifted[fake] * c_fake);
+        let k = Fr::rand(rng);
+        let real_commitments = (g * k, h * k);
+        let (zero, one) = if m == 0 {
+            (real_commitments, fake_commitments)
+        } else {
+            (fake_commitments, real_commitments)
+        };
+        let c = challenge(
+            BIT_TAG,
+            survey_id,
+            &bit_context(voter, candidate),
+            &[h, a, b, zero.0, zero.1, one.0, one.1],
+        );
+        let c_real = c - c_fake;
+        let s_real = k + c_real * r;
+        let (challenges, responses) = if m == 0 {
+            ([c_real, c_fake], [s_real, s_fake])
+        } else {
+            ([c_fake, c_real], [s_fake, s_real])
+        };
+
+        choices.push(EncryptedChoice {
+            ciphertext: Ciphertext { a: Point::from_group(a), b: Point::from_group(b) },
+            proof: BitProof {
+                challenge_zero: Scalar::from_field(challenges[0]),
+                challenge_one: Scalar::from_field(challenges[1]),
+                response_zero: Scalar::from_field(responses[0]),
+                response_one: Scalar::from_field(responses[1]),
+            },
+        });
+        sum_a += a;
+        sum_b += b;
+        sum_r += r;
+    }
+
+    let sum_proof = prove_equality(SUM_TAG, survey_id, voter, h, sum_a, sum_b - g, sum_r, rng);
+    Ok(EncryptedBallot { choices, sum_proof })
+}
+
+fn share_context(index: u32, candidate: usize) -> Vec<u8> {
+    let mut context = index.to_be_bytes().to_vec();
+    context.extend_from_slice(&(candidate as u32).to_be_bytes());
+    context
+}
+
+/// Descifrados parciales del custodio `index` para cada conteo agregado
+pub fn decrypt_shares<R: RngCore + CryptoRng>(
+    secret: &Scalar,
+    index: u32,
+    survey_id: u64,
+    aggregate: &[Ciphertext],
+    rng: &mut R,
+) -> Result<Vec<DecryptionShare>> {
+    let x = secret.to_field();
+    let verification_key = generator() * x;
+    aggregate
+        .iter()
+        .enumerate()
+        .map(|(candidate, ciphertext)| {
+            let a = ciphertext.a.to_group()?;
+            let share = a * x;
+            let proof = prove_equality(
+                SHARE_TAG,
+                survey_id,
+                &share_context(index, candidate),
+                a,
+                verification_key,
+                share,
+                x,
+                rng,
+            );
+            Ok(DecryptionShare { share: Point::from_group(share), proof })
+        })
+        .collect()
+}
+
+/// Verifica los descifrados parciales de un custodio contra su clave de verificación
+pub fn verify_decryption_shares(
+    verification_key: &Point,
+    index: u32,
+    survey_id: u64,
+    aggregate: &[Ciphertext],
+    shares: &[DecryptionShare],
+) -> Result<bool> {
+    ensure!(shares.len() == aggregate.len(), "one share per candidate is required");
+    let key = verification_key.to_group()?;
+    for (candidate, (ciphertext, share)) in aggregate.iter().zip(shares).enumerate() {
+        let valid = verify_equality(
+            SHARE_TAG,
+            survey_id,
+            &share_context(index, candidate),
+            ciphertext.a.to_group()?,
+            key,
+            share.share.to_group()?,
+            &share.proof,
+        )?;
+        if !valid {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Coeficientes de Lagrange en 0 para los índices dados
+pub fn lagrange_at_zero(indices: &[u32]) -> Vec<Fr> {
+    indices
+        .iter()
+        .map(|&i| {
+            let (mut numerator, mut denominator) = (Fr::from(1u64), Fr::from(1u64));
+            for &k in indices.iter().filter(|&&k| k != i) {
+                numerator *= Fr::from(k as u64);
+                denominator *= Fr::from(k as u64) - Fr::from(i as u64);
+            }
+            numerator * denominator.inverse().expect("trustee indices are distinct")
+        })
+        .collect()
+}
+
+/// Combina los descifrados parciales `(índice, shares)` de al menos `threshold`
+/// custodios y resuelve cada conteo por búsqueda hasta `max_votes`
+pub fn tally(aggregate: &[Ciphertext], shares: &[(u32, Vec<Point>)], max_votes: u64) -> Result<Vec<u64>> {
+    let indices: Vec<u32> = shares.iter().map(|(index, _)| *index).collect();
+    let coefficients = lagrange_at_zero(&indices);
+
+    aggregate
+        .iter()
+        .enumerate()
+        .map(|(candidate, ciphertext)| {
+            let mut message = ciphertext.b.to_group()?;
+            for ((_, trustee_shares), lambda) in shares.iter().zip(&coefficients) {
+                let share = trustee_shares
+                    .get(candidate)
+                    .ok_or_else(|| anyhow!("missing share for candidate {candidate}"))?;
+                message -= share.to_group()? * lambda;
+            }
+
+            let mut point = G1Projective::zero();
+            for votes in 0..=max_votes {
+                if point == message {
+                    return Ok(votes);
+                }
+                point += generator();
+            }
+            Err(anyhow!("count for candidate {candidate} exceeds {max_votes}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_threshold_decryption() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let dealings: Vec<Dealing> = (1..=3).map(|i| deal(1, i, 2, 3, &mut rng).unwrap()).collect();
+        for dealing in &dealings {
+            for j in 1..=3 {
+                assert!(verify_share(&dealing.commitments, j, &dealing.shares[j as usize - 1]).unwrap());
+            }
+        }
+        let commitments = aggregate_commitments(
+            &dealings.iter().map(|d| d.commitments.clone()).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let secrets: Vec<Scalar> = (0..3)
+            .map(|j| combine_shares(&dealings.iter().map(|d| d.shares[j]).collect::<Vec<_>>()))
+            .collect();
+
+        // Dos votos por el candidato 1 y uno por el 2
+        let mut aggregate = vec![
+            Ciphertext { a: Point::from_group(G1Projective::zero()), b: Point::from_group(G1Projective::zero()) };
+            3
+        ];
+        for choice in [1, 2, 1] {
+            let ballot = encrypt_ballot(&commitments[0], 1, b"voter", choice, 3, &mut rng).unwrap();
+            for (sum, choice) in aggregate.iter_mut().zip(&ballot.choices) {
+                sum.a = Point::from_group(sum.a.to_group().unwrap() + choice.ciphertext.a.to_group().unwrap());
+                sum.b = Point::from_group(sum.b.to_group().unwrap() + choice.ciphertext.b.to_group().unwrap());
+            }
+        }
+
+        // Cualquier par de custodios basta
+        let shares: Vec<(u32, Vec<Point>)> = [1u32, 3]
+            .iter()
+            .map(|&i| {
+                let shares = decrypt_shares(&secrets[i as usize - 1], i, 1, &aggregate, &mut rng).unwrap();
+                let key = verification_key(&commitments, i).unwrap();
+                assert!(verify_decryption_shares(&key, i, 1, &aggregate, &shares).unwrap());
+                (i, shares.into_iter().map(|s| s.share).collect())
+            })
+            .collect();
+        assert_eq!(tally(&aggregate, &shares, 3).unwrap(), vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_encrypt_rejects_unknown_choice() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let dealing = deal(1, 1, 1, 1, &mut rng).unwrap();
+        assert!(encrypt_ballot(&dealing.commitments[0], 1, b"voter", 2, 2, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! `dappcenvote-trustee`: operaciones fuera de la cadena de los custodios de una
+//! elección cifrada. Lee y escribe JSON con el mismo formato que
+//! `stellar contract invoke` usa para los getters y argumentos del contrato.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use rand::rngs::OsRng;
+use serde::{de::DeserializeOwned, Serialize};
+
+use dappcenvote_trustee::{Ciphertext, Point, Scalar};
+
+#[derive(Parser)]
+#[command(name = "dappcenvote-trustee", version, about = "Trustee tool for encrypted DappCenvote elections")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate this trustee's key commitments, proof and secret shares for the others
+    Deal {
+        #[arg(long)]
+        survey_id: u64,
+        /// This trustee's index (position in the election's trustee list + 1)
+        #[arg(long)]
+        index: u32,
+        #[arg(long)]
+        threshold: u32,
+        #[arg(long)]
+        trustees: u32,
+    },
+    /// Check a share received from another trustee against their commitments
+    VerifyShare {
+        /// JSON file with the dealer's commitments
+        #[arg(long)]
+        commitments: PathBuf,
+        #[arg(long)]
+        index: u32,
+        /// Hex-encoded share
+        #[arg(long)]
+        share: String,
+    },
+    /// Add up the shares received from every trustee into this trustee's secret
+    Combine {
+        /// Hex-encoded shares, one per trustee
+        #[arg(long = "share", required = true)]
+        shares: Vec<String>,
+    },
+    /// Compute this trustee's decryption shares for `submit_shares`
+    Decrypt {
+        #[arg(long)]
+        survey_id: u64,
+        #[arg(long)]
+        index: u32,
+        /// Hex-encoded secret from `combine`
+        #[arg(long)]
+        secret: String,
+        /// JSON file with the output of `get_aggregate`
+        #[arg(long)]
+        aggregate: PathBuf,
+    },
+    /// Combine decryption shares and solve the counts for `publish_tally`
+    Tally {
+        /// JSON file with the output of `get_aggregate`
+        #[arg(long)]
+        aggregate: PathBuf,
+        /// INDEX=FILE with the output of `get_shares` for that trustee
+        #[arg(long = "shares", required = true)]
+        shares: Vec<String>,
+        /// Number of ballots cast (upper bound for each count)
+        #[arg(long)]
+        max_votes: u64,
+    },
+}
+
+fn main() -> Result<()> {
+    let mut rng = OsRng;
+
+    match Cli::parse().command {
+        Commands::Deal { survey_id, index, threshold, trustees } => {
+            print_json(&dappcenvote_trustee::deal(survey_id, index, threshold, trustees, &mut rng)?)
+        }
+        Commands::VerifyShare { commitments, index, share } => {
+            let commitments: Vec<Point> = read_json(&commitments)?;
+            if !dappcenvote_trustee::verify_share(&commitments, index, &parse_scalar(&share)?)? {
+                bail!("share does not match the dealer's commitments");
+            }
+            println!("share is valid");
+            Ok(())
+        }
+        Commands::Combine { shares } => {
+            let shares = shares.iter().map(|share| parse_scalar(share)).collect::<Result<Vec<_>>>()?;
+            println!("{}", hex::encode(dappcenvote_trustee::combine_shares(&shares).0));
+            Ok(())
+        }
+        Commands::Decrypt { survey_id, index, secret, aggregate } => {
+            let aggregate: Vec<Ciphertext> = read_json(&aggregate)?;
+            let shares = dappcenvote_trustee::decrypt_shares(
+                &parse_scalar(&secret)?,
+                index,
+                survey_id,
+                &aggregate,
+                &mut rng,
+            )?;
+            print_json(&shares)
+        }
+        Commands::Tally { aggregate, shares, max_votes } => {
+            let aggregate: Vec<Ciphertext> = read_json(&aggregate)?;
+            let shares = shares
+                .iter()
+                .map(|entry| {
+                    let (index, path) = entry.split_once('=').context("expected INDEX=FILE")?;
+                    Ok((index.parse()?, read_json(Path::new(path))?))
+                })
+                .collect::<Result<Vec<(u32, Vec<Point>)>>>()?;
+            print_json(&dappcenvote_trustee::tally(&aggregate, &shares, max_votes)?)
+        }
+    }
+}
+
+fn parse_scalar(text: &str) -> Result<Scalar> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(text.trim(), &mut bytes).context("expected 32 hex-encoded bytes")?;
+    Ok(Scalar(bytes))
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let text = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("unexpected JSON in {}", path.display()))
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use soroban_sdk::{
+    contractclient, contracterror, contractevent, contracttype, Address, BytesN, Env, Vec,
+};
+
+// Los puntos de BLS12-381 viajan sin comprimir (G1 en 96 bytes, G2 en 192) y
+// los elementos de Fr en big-endian (32 bytes), igual que en las funciones host
+
+// Clave de verificación de Groth16 del circuito de voto
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyingKey {
+    pub alpha: BytesN<96>,
+    pub beta: BytesN<192>,
+    pub gamma: BytesN<192>,
+    pub delta: BytesN<192>,
+    pub ic: Vec<BytesN<96>>, // Constante y entradas públicas [raíz, nullifier, ámbito, candidato]
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Groth16Proof {
+    pub a: BytesN<96>,
+    pub b: BytesN<192>,
+    pub c: BytesN<96>,
+}
+
+// Grupo de identidades de una encuesta anónima
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Group {
+    pub survey_id: u64,
+    pub size: u32,         // Identidades en el árbol
+    pub root: BytesN<32>,  // Raíz de Merkle actual; fija desde el inicio de la encuesta
+    pub ballots: u64,
+    pub tallied: bool,
+}
+
+// Claves de almacenamiento de AnonymousVote
+#[contracttype(export = false)]
+pub enum DataKey {
+    Admin,                         // Dirección del administrador
+    ContractVersion,               // Versión del formato de almacenamiento
+    Survey,                        // Contrato de encuestas
+    VerifyingKey,                  // Clave de verificación del circuito para nuevos grupos
+    Group(u64),                    // survey_id -> Group
+    GroupKey(u64),                 // survey_id -> VerifyingKey fijada al crear el grupo
+    FilledSubtrees(u64),           // survey_id -> último nodo izquierdo de cada nivel
+    Members(u64),                  // survey_id -> Vec<BytesN<32>> compromisos en orden
+    Member(u64, Address),          // (survey_id, usuario) -> ya aportó su compromiso
+    Commitment(u64, BytesN<32>),   // (survey_id, compromiso) -> ya está en el árbol
+    Nullifier(u64, BytesN<32>),    // (survey_id, nullifier) -> ya votó
+    Counts(u64),                   // survey_id -> Vec<u64> votos por candidato
+}
+
+// Evento: una encuesta pasó a votarse de forma anónima
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupCreated {
+    #[topic]
+    pub survey_id: u64,
+}
+
+// Evento: se añadió una identidad al grupo
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberAdded {
+    #[topic]
+    pub survey_id: u64,
+    pub commitment: BytesN<32>,
+    pub index: u32,
+    pub root: BytesN<32>,
+}
+
+// Evento: se contó un voto anónimo (sin votante)
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnonymousVoteCast {
+    #[topic]
+    pub survey_id: u64,
+    pub nullifier_hash: BytesN<32>,
+    pub candidate: Address,
+}
+
+// Evento: el conteo se entregó a la encuesta
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TallyPublished {
+    #[topic]
+    pub survey_id: u64,
+    pub counts: Vec<u64>,
+}
+
+// Errores tipados de AnonymousVote
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AnonymousVoteError {
+    AlreadyMember = 1,
+    DuplicateCommitment = 2,
+    GroupFull = 3,
+    InvalidProof = 4,
+    AlreadyVoted = 5,
+    NotEligible = 6,
+    NotRegistered = 7,
+    Paused = 8,
+    AlreadyTallied = 9,
+    InvalidField = 10,
+    NoVerifyingKey = 11,
+}
+
+#[contractclient(name = "AnonymousVoteClient")]
+pub trait AnonymousVoteInterface {
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+    fn migrate(env: Env);
+    fn version(env: Env) -> u32;
+    fn set_verifying_key(env: Env, verifying_key: VerifyingKey);
+    fn get_verifying_key(env: Env) -> Option<VerifyingKey>;
+    fn create_group(env: Env, survey_id: u64);
+    fn join_group(
+        env: Env,
+        survey_id: u64,
+        user: Address,
+        commitment: BytesN<32>,
+        proof: Option<Vec<BytesN<32>>>,
+    );
+    fn cast_vote(
+        env: Env,
+        survey_id: u64,
+        candidate: Address,
+        nullifier_hash: BytesN<32>,
+        proof: Groth16Proof,
+    ) -> BytesN<32>;
+    fn publish_tally(env: Env, survey_id: u64);
+    fn get_group(env: Env, survey_id: u64) -> Option<Group>;
+    fn get_group_verifying_key(env: Env, survey_id: u64) -> Option<VerifyingKey>;
+    fn get_members(env: Env, survey_id: u64) -> Vec<BytesN<32>>;
+    fn is_nullifier_used(env: Env, survey_id: u64, nullifier_hash: BytesN<32>) -> bool;
+}
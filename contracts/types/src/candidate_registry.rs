@@ -0,0 +1,173 @@
+use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, String, Vec};
+#[cfg(feature = "candidate_registry")]
+use soroban_sdk::{contracterror, contractevent};
+
+use crate::PauseScope;
+
+// Estructura de datos para almacenar información del candidato
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Candidate {
+    pub wallet: Address,
+    pub name: String,
+    pub rfc: String,
+    pub timestamp: u64,
+}
+
+// Versión del perfil público de un candidato. Foto, biografía y plataforma
+// viven fuera de la cadena en profile_uri; content_hash permite verificarlas.
+// El partido del candidato no se repite aquí: sale de get_candidate_party
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CandidateProfile {
+    pub version: u32,             // Empieza en 1 y crece con cada actualización
+    pub profile_uri: String,      // ipfs://<cid> del JSON del perfil
+    pub content_hash: BytesN<32>, // sha256 del contenido de profile_uri
+    pub updated_at: u64,
+}
+
+// Constancia de un registrador sobre un dato del perfil, como una red social verificada
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub registrar: Address,
+    pub claim: String,            // p. ej. "x.com/juanperez"
+    pub version: u32,             // Versión del perfil revisada
+    pub content_hash: BytesN<32>, // Hash de esa versión
+    pub timestamp: u64,
+}
+
+// Partido o planilla; el orden de members es el de la lista para asignar escaños
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Party {
+    pub party_id: u64,
+    pub name: String,
+    pub logo_hash: BytesN<32>, // sha256 del logotipo, guardado fuera de la cadena
+    pub leader: Address,       // Siempre es miembro; representa al partido en las encuestas
+    pub members: Vec<Address>,
+}
+
+// Claves de almacenamiento de CandidateRegistry
+#[contracttype(export = false)]
+pub enum DataKey {
+    Candidate(Address), // Mapea wallet address -> Candidate
+    CandidateList,      // Lista de todos los wallets de candidatos
+    CandidateCount,     // Contador total de candidatos
+    Admin,              // Dirección del administrador
+    ContractVersion,    // Versión del formato de almacenamiento
+    Guardian,           // Dirección autorizada para pausar el contrato
+    Paused(PauseScope), // scope -> bool
+    Registrar(Address), // Registrador autorizado a atestiguar perfiles -> bool
+    ProfileVersion(Address), // wallet -> última versión del perfil
+    Profile(Address, u32), // (wallet, versión) -> CandidateProfile
+    Attestations(Address), // wallet -> Vec<Attestation>
+    PartyCount,         // Contador de partidos
+    Party(u64),         // party_id -> Party
+    CandidateParty(Address), // wallet -> party_id (un partido por candidato)
+}
+
+// Evento: se registró un candidato
+#[cfg(feature = "candidate_registry")]
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CandidateRegistered {
+    #[topic]
+    pub wallet: Address,
+    pub name: String,
+    pub timestamp: u64,
+}
+
+// Evento: un candidato publicó una nueva versión de su perfil
+#[cfg(feature = "candidate_registry")]
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfileUpdated {
+    #[topic]
+    pub wallet: Address,
+    pub version: u32,
+    pub profile_uri: String,
+    pub content_hash: BytesN<32>,
+}
+
+// Evento: un registrador atestiguó un dato del perfil
+#[cfg(feature = "candidate_registry")]
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfileAttested {
+    #[topic]
+    pub wallet: Address,
+    pub registrar: Address,
+    pub claim: String,
+    pub version: u32,
+}
+
+// Evento: se creó un partido
+#[cfg(feature = "candidate_registry")]
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartyCreated {
+    #[topic]
+    pub party_id: u64,
+    pub name: String,
+    pub leader: Address,
+}
+
+// Evento: cambió la lista de miembros o el líder de un partido
+#[cfg(feature = "candidate_registry")]
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartyChanged {
+    #[topic]
+    pub party_id: u64,
+    pub leader: Address,
+    pub members: Vec<Address>,
+}
+
+// Errores tipados de CandidateRegistry
+#[cfg(feature = "candidate_registry")]
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CandidateRegistryError {
+    Paused = 1,
+    Unauthorized = 2,
+}
+
+#[contractclient(name = "CandidateRegistryClient")]
+pub trait CandidateRegistryInterface {
+    fn set_guardian(env: Env, guardian: Address);
+    fn get_guardian(env: Env) -> Option<Address>;
+    fn pause(env: Env, caller: Address, scope: PauseScope);
+    fn unpause(env: Env, caller: Address, scope: PauseScope);
+    fn is_paused(env: Env, scope: PauseScope) -> bool;
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+    fn migrate(env: Env);
+    fn version(env: Env) -> u32;
+    fn register_candidate(env: Env, wallet: Address, name: String, rfc: String) -> bool;
+    fn get_candidate(env: Env, wallet: Address) -> Option<Candidate>;
+    fn candidate_exists(env: Env, wallet: Address) -> bool;
+    fn get_all_candidates(env: Env) -> Vec<Address>;
+    fn get_candidate_count(env: Env) -> u64;
+    fn add_registrar(env: Env, registrar: Address);
+    fn remove_registrar(env: Env, registrar: Address);
+    fn is_registrar(env: Env, registrar: Address) -> bool;
+    fn update_profile(
+        env: Env,
+        wallet: Address,
+        profile_uri: String,
+        content_hash: BytesN<32>,
+    ) -> u32;
+    fn get_profile(env: Env, wallet: Address) -> Option<CandidateProfile>;
+    fn get_profile_version(env: Env, wallet: Address, version: u32) -> Option<CandidateProfile>;
+    fn get_profile_history(env: Env, wallet: Address) -> Vec<CandidateProfile>;
+    fn attest_profile(env: Env, registrar: Address, wallet: Address, claim: String);
+    fn get_attestations(env: Env, wallet: Address) -> Vec<Attestation>;
+    fn create_party(env: Env, leader: Address, name: String, logo_hash: BytesN<32>) -> u64;
+    fn add_party_member(env: Env, party_id: u64, member: Address);
+    fn remove_party_member(env: Env, party_id: u64, caller: Address, member: Address);
+    fn set_party_leader(env: Env, party_id: u64, new_leader: Address);
+    fn get_party(env: Env, party_id: u64) -> Option<Party>;
+    fn get_party_count(env: Env) -> u64;
+    fn get_candidate_party(env: Env, wallet: Address) -> Option<u64>;
+}
@@ -0,0 +1,191 @@
+use soroban_sdk::{
+    contractclient, contracterror, contractevent, contracttype, Address, BytesN, Env, Vec,
+};
+
+// Los puntos de G1 de BLS12-381 viajan sin comprimir (96 bytes) y los escalares
+// de Fr en big-endian (32 bytes), igual que en las funciones host del SDK
+
+// Cifrado ElGamal exponencial de 0 o 1: (r·G, m·G + r·H)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ciphertext {
+    pub a: BytesN<96>,
+    pub b: BytesN<96>,
+}
+
+// Prueba de Schnorr de conocimiento del logaritmo discreto de un punto
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyProof {
+    pub commitment: BytesN<96>,
+    pub response: BytesN<32>,
+}
+
+// Prueba de Chaum-Pedersen de que dos pares de puntos comparten logaritmo discreto
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EqualityProof {
+    pub commitment_g: BytesN<96>,
+    pub commitment_h: BytesN<96>,
+    pub response: BytesN<32>,
+}
+
+// Prueba disyuntiva de que un cifrado contiene 0 o 1
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BitProof {
+    pub challenge_zero: BytesN<32>,
+    pub challenge_one: BytesN<32>,
+    pub response_zero: BytesN<32>,
+    pub response_one: BytesN<32>,
+}
+
+// Cifrado de la elección para un candidato
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptedChoice {
+    pub ciphertext: Ciphertext,
+    pub proof: BitProof,
+}
+
+// Papeleta cifrada: un cifrado por candidato, en el orden de la encuesta, y la
+// prueba de que la suma de todos cifra exactamente un voto
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptedBallot {
+    pub choices: Vec<EncryptedChoice>,
+    pub sum_proof: EqualityProof,
+}
+
+// Descifrado parcial x_i·A de un conteo agregado, con su prueba
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecryptionShare {
+    pub share: BytesN<96>,
+    pub proof: EqualityProof,
+}
+
+// Elección cifrada asociada a una encuesta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Election {
+    pub survey_id: u64,
+    pub trustees: Vec<Address>,          // El índice de cada custodio es su posición + 1
+    pub threshold: u32,                  // Custodios necesarios para descifrar
+    pub committed: u32,                  // Custodios que ya publicaron sus compromisos
+    pub public_key: Option<BytesN<96>>,  // Se fija cuando todos publican sus compromisos
+    pub ballots: u64,
+    pub tallied: bool,
+}
+
+// Claves de almacenamiento de EncryptedBallot
+#[contracttype(export = false)]
+pub enum DataKey {
+    Admin,                            // Dirección del administrador
+    ContractVersion,                  // Versión del formato de almacenamiento
+    Survey,                           // Contrato de encuestas
+    Election(u64),                    // survey_id -> Election
+    Commitments(u64),                 // survey_id -> compromisos de Feldman sumados
+    TrusteeCommitments(u64, Address), // (survey_id, trustee) -> compromisos publicados
+    Aggregate(u64),                   // survey_id -> Vec<Ciphertext> sumados por candidato
+    Ballot(u64, Address),             // (survey_id, voter) -> EncryptedBallot
+    Voters(u64),                      // survey_id -> Vec<Address>
+    Shares(u64, Address),             // (survey_id, trustee) -> Vec<BytesN<96>>
+    ShareTrustees(u64),               // survey_id -> Vec<u32> índices que descifraron
+    Tally(u64),                       // survey_id -> Vec<u64>
+}
+
+// Evento: se abrió una elección cifrada
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ElectionCreated {
+    #[topic]
+    pub survey_id: u64,
+    pub trustees: Vec<Address>,
+    pub threshold: u32,
+}
+
+// Evento: todos los custodios publicaron sus compromisos y la clave quedó fijada
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ElectionKeyReady {
+    #[topic]
+    pub survey_id: u64,
+    pub public_key: BytesN<96>,
+}
+
+// Evento: se recibió una papeleta cifrada válida
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptedVoteCast {
+    #[topic]
+    pub survey_id: u64,
+    pub voter: Address,
+}
+
+// Evento: un custodio publicó sus descifrados parciales
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecryptionShared {
+    #[topic]
+    pub survey_id: u64,
+    pub trustee: Address,
+}
+
+// Evento: se publicó el conteo descifrado
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TallyPublished {
+    #[topic]
+    pub survey_id: u64,
+    pub counts: Vec<u64>,
+}
+
+// Errores tipados de EncryptedBallot
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EncryptedBallotError {
+    NotTrustee = 1,
+    AlreadyCommitted = 2,
+    KeyNotReady = 3,
+    InvalidProof = 4,
+    AlreadyVoted = 5,
+    NotEligible = 6,
+    AlreadyShared = 7,
+    NotEnoughShares = 8,
+    InvalidTally = 9,
+    AlreadyTallied = 10,
+    NotRegistered = 11,
+    Paused = 12,
+}
+
+#[contractclient(name = "EncryptedBallotClient")]
+pub trait EncryptedBallotInterface {
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+    fn migrate(env: Env);
+    fn version(env: Env) -> u32;
+    fn create_election(env: Env, survey_id: u64, trustees: Vec<Address>, threshold: u32);
+    fn commit_key(
+        env: Env,
+        survey_id: u64,
+        trustee: Address,
+        commitments: Vec<BytesN<96>>,
+        proof: KeyProof,
+    );
+    fn cast_ballot(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        ballot: EncryptedBallot,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32>;
+    fn submit_shares(env: Env, survey_id: u64, trustee: Address, shares: Vec<DecryptionShare>);
+    fn publish_tally(env: Env, survey_id: u64, counts: Vec<u64>);
+    fn get_election(env: Env, survey_id: u64) -> Option<Election>;
+    fn get_commitments(env: Env, survey_id: u64) -> Vec<BytesN<96>>;
+    fn get_aggregate(env: Env, survey_id: u64) -> Vec<Ciphertext>;
+    fn get_ballot(env: Env, survey_id: u64, voter: Address) -> Option<EncryptedBallot>;
+    fn get_shares(env: Env, survey_id: u64, trustee: Address) -> Option<Vec<BytesN<96>>>;
+    fn get_tally(env: Env, survey_id: u64) -> Option<Vec<u64>>;
+}
@@ -0,0 +1,157 @@
+use soroban_sdk::{
+    contractclient, contracterror, contractevent, contracttype, Address, BytesN, Env, String, Symbol,
+    Val, Vec,
+};
+
+// Parámetros de votación que se copian en cada propuesta al crearla
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfig {
+    pub voting_period: u64, // Segundos que dura la votación
+    pub timelock: u64,      // Segundos entre el cierre y la ejecución
+    pub quorum: u64,        // Votos mínimos (a favor + en contra + abstenciones)
+    pub threshold_bps: u32, // Votos a favor sobre (a favor + en contra), en puntos básicos
+}
+
+// Llamada a otro contrato
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractCall {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+// Acción que se ejecuta si la propuesta se aprueba. Los tipos del SDK ocupan
+// más memoria con testutils; en wasm son handles de 8 bytes
+#[contracttype]
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum ProposalAction {
+    None,                // Consulta sin efecto en la cadena
+    Call(ContractCall),  // Llamada ejecutable con `execute`
+    Upgrade(BytesN<32>), // Reemplaza el código de Governance por este wasm
+}
+
+// Propuesta de referéndum Sí/No/Abstención
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub title: String,
+    pub description: String,
+    pub action: ProposalAction,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub eta: u64, // Momento a partir del cual puede ejecutarse (end_time + timelock)
+    pub quorum: u64,
+    pub threshold_bps: u32,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub executed: bool,
+}
+
+// Sentido de un voto
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Ballot {
+    For,
+    Against,
+    Abstain,
+}
+
+// Estado de una propuesta según el momento actual
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalStatus {
+    Active,   // En votación
+    Passed,   // Aprobada; ejecutable cuando termine el timelock
+    Rejected, // Sin quórum o sin el umbral de votos a favor
+    Executed, // Acción ejecutada
+}
+
+// Claves de almacenamiento de Governance
+#[contracttype(export = false)]
+pub enum DataKey {
+    Admin,                 // Dirección del administrador
+    ContractVersion,       // Versión del formato de almacenamiento
+    Config,                // GovernanceConfig vigente
+    Member(Address),       // member -> bool
+    MemberSince(Address),  // member -> momento de su alta
+    ProposalCount,         // Total de propuestas
+    Proposal(u64),         // proposal_id -> Proposal
+    Ballot(u64, Address),  // (proposal_id, member) -> Ballot
+}
+
+// Evento: se creó una propuesta
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCreated {
+    #[topic]
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub title: String,
+    pub end_time: u64,
+    pub eta: u64,
+}
+
+// Evento: un miembro votó una propuesta
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BallotCast {
+    #[topic]
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub ballot: Ballot,
+}
+
+// Evento: se ejecutó la acción de una propuesta aprobada
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExecuted {
+    #[topic]
+    pub proposal_id: u64,
+    pub contract: Address,
+    pub function: Symbol,
+}
+
+// Errores tipados de Governance
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum GovernanceError {
+    NotMember = 1,
+    AlreadyVoted = 2,
+    VotingClosed = 3,
+    NotPassed = 4,
+    TimelockActive = 5,
+    AlreadyExecuted = 6,
+    NoAction = 7,
+    ProposalRequired = 8,
+}
+
+#[contractclient(name = "GovernanceClient")]
+pub trait GovernanceInterface {
+    fn migrate(env: Env);
+    fn version(env: Env) -> u32;
+    fn set_config(env: Env, config: GovernanceConfig);
+    fn get_config(env: Env) -> GovernanceConfig;
+    fn add_member(env: Env, member: Address);
+    fn remove_member(env: Env, member: Address);
+    fn is_member(env: Env, member: Address) -> bool;
+    fn propose(
+        env: Env,
+        proposer: Address,
+        title: String,
+        description: String,
+        action: ProposalAction,
+    ) -> u64;
+    fn cast_ballot(env: Env, proposal_id: u64, voter: Address, ballot: Ballot);
+    fn execute(env: Env, proposal_id: u64) -> Val;
+    fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal>;
+    fn get_status(env: Env, proposal_id: u64) -> ProposalStatus;
+    fn get_ballot(env: Env, proposal_id: u64, voter: Address) -> Option<Ballot>;
+    fn get_proposal_count(env: Env) -> u64;
+}
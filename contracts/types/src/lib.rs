@@ -0,0 +1,46 @@
+//! Tipos, claves de almacenamiento, errores e interfaces de cliente compartidos
+//! por los contratos de DappCenvote y por cualquier herramienta en Rust que los use.
+//!
+//! Cada contrato implementa su trait `*Interface`, por lo que el compilador
+//! garantiza que los clientes generados aquí (`SurveyClient`, `UserRegistryClient`,
+//! `CandidateRegistryClient`, `PasskeyAccountClient`, `GovernanceClient`,
+//! `EncryptedBallotClient`, `AnonymousVoteClient`, `SurveySeriesClient`,
+//! `PartyListClient`)
+//! coinciden con el contrato desplegado.
+//!
+//! La especificación de cada tipo exportado aquí se incrusta en el wasm de
+//! todos los contratos que usan este crate, aunque no lo usen. Las claves
+//! `DataKey` no forman parte de ninguna interfaz, así que no se exportan, y
+//! los módulos de los contratos satélite (urnas, gobernanza, series, partidos,
+//! passkeys) se activan con una feature del mismo nombre solo donde se usan.
+//! Lo mismo vale para los eventos y errores de los registros (`user_registry`,
+//! `candidate_registry`): los demás contratos solo necesitan sus clientes.
+//! Por eso cada contrato se compila para wasm con su propio `cargo build -p`.
+#![no_std]
+use soroban_sdk::contracttype;
+
+#[cfg(feature = "anonymous_vote")]
+pub mod anonymous_vote;
+pub mod candidate_registry;
+#[cfg(feature = "encrypted_ballot")]
+pub mod encrypted_ballot;
+#[cfg(feature = "governance")]
+pub mod governance;
+#[cfg(feature = "party_list")]
+pub mod party_list;
+#[cfg(feature = "passkey_account")]
+pub mod passkey_account;
+pub mod survey;
+#[cfg(feature = "survey_series")]
+pub mod survey_series;
+pub mod user_registry;
+
+// Ámbitos de operación que se pueden pausar en una emergencia
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PauseScope {
+    Voting,
+    Registration,
+    SurveyCreation, // También la configuración de encuestas ya creadas
+    Funds,          // Cobros, reembolsos, premios y retiros de tesorería
+}
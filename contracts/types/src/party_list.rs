@@ -0,0 +1,106 @@
+use soroban_sdk::{
+    contractclient, contracterror, contractevent, contracttype, Address, BytesN, Env, Vec,
+};
+
+// Método de promedios mayores para repartir los escaños
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SeatMethod {
+    DHondt,      // Divisores 1, 2, 3, ...
+    SainteLague, // Divisores 1, 3, 5, ...
+}
+
+// Elección por listas sobre una encuesta cuyos candidatos son los líderes de los partidos
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartyBallot {
+    pub survey_id: u64,
+    pub parties: Vec<u64>,          // party_id en el orden de los candidatos de la encuesta
+    pub slates: Vec<Vec<Address>>,  // Lista de cada partido al crear la elección
+    pub seats: u32,
+    pub method: SeatMethod,
+    pub counts: Vec<u64>,           // Votos por partido
+    pub allocation: Vec<u32>,       // Escaños por partido; vacío hasta finalizar
+}
+
+// Resultado de un partido; elected son los primeros `seats` de su lista
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartyResult {
+    pub party_id: u64,
+    pub votes: u64,
+    pub seats: u32,
+    pub elected: Vec<Address>,
+}
+
+// Claves de almacenamiento de PartyList
+#[contracttype(export = false)]
+pub enum DataKey {
+    Admin,                  // Dirección del administrador
+    ContractVersion,        // Versión del formato de almacenamiento
+    Survey,                 // Contrato de encuestas
+    Ballot(u64),            // survey_id -> PartyBallot
+    Voted(u64, Address),    // (survey_id, votante) -> party_id
+}
+
+// Evento: una encuesta pasó a ser elección por listas
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartyBallotCreated {
+    #[topic]
+    pub survey_id: u64,
+    pub parties: Vec<u64>,
+    pub seats: u32,
+    pub method: SeatMethod,
+}
+
+// Evento: se votó por un partido; party_id solo si la encuesta muestra resultados en vivo
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartyVoteCast {
+    #[topic]
+    pub survey_id: u64,
+    pub voter: Address,
+    pub party_id: Option<u64>,
+}
+
+// Evento: se repartieron los escaños al finalizar
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeatsAllocated {
+    #[topic]
+    pub survey_id: u64,
+    pub counts: Vec<u64>,
+    pub allocation: Vec<u32>,
+}
+
+// Errores tipados de PartyList
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PartyListError {
+    Paused = 1,
+    NotEligible = 2,
+    NotRegistered = 3,
+    AlreadyVoted = 4,
+    AlreadyFinalized = 5,
+    ResultsHidden = 6,
+}
+
+#[contractclient(name = "PartyListClient")]
+pub trait PartyListInterface {
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+    fn migrate(env: Env);
+    fn version(env: Env) -> u32;
+    fn create_party_ballot(env: Env, survey_id: u64, seats: u32, method: SeatMethod);
+    fn vote_party(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        party_id: u64,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32>;
+    fn finalize(env: Env, survey_id: u64) -> Vec<u32>;
+    fn get_party_ballot(env: Env, survey_id: u64) -> Option<PartyBallot>;
+    fn get_party_results(env: Env, survey_id: u64) -> Vec<PartyResult>;
+}
@@ -0,0 +1,41 @@
+use soroban_sdk::{contractclient, contracterror, contracttype, Bytes, BytesN, Env};
+
+// Aserción WebAuthn producida por el autenticador del usuario (navigator.credentials.get)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WebAuthnSignature {
+    pub credential_id: Bytes,
+    pub authenticator_data: Bytes,
+    pub client_data_json: Bytes,
+    pub signature: BytesN<64>, // r || s en formato compacto con s normalizado (low-S)
+}
+
+// Claves de almacenamiento de PasskeyAccount
+#[contracttype(export = false)]
+pub enum DataKey {
+    Signer(Bytes), // credential_id -> llave pública secp256r1 sin comprimir
+    SignerCount,   // Número de passkeys registradas
+    RpIdHash,      // sha256 del RP ID de la aplicación (p. ej. "cenvote.mx")
+}
+
+// Errores tipados de PasskeyAccount
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PasskeyError {
+    UnknownSigner = 1,
+    InvalidClientData = 2,
+    ChallengeMismatch = 3,
+    UserNotPresent = 4,
+    LastSigner = 5,
+    RpIdMismatch = 6,
+}
+
+// El constructor y __check_auth no forman parte del cliente: los invoca el host
+#[contractclient(name = "PasskeyAccountClient")]
+pub trait PasskeyAccountInterface {
+    fn add_signer(env: Env, credential_id: Bytes, public_key: BytesN<65>);
+    fn remove_signer(env: Env, credential_id: Bytes) -> Result<(), PasskeyError>;
+    fn get_signer(env: Env, credential_id: Bytes) -> Option<BytesN<65>>;
+    fn get_signer_count(env: Env) -> u32;
+}
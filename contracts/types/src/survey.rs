@@ -0,0 +1,618 @@
+use soroban_sdk::{
+    contractclient, contracterror, contractevent, contracttype, Address, BytesN, Env, String, Symbol,
+    Vec,
+};
+
+use crate::PauseScope;
+
+// Estructura de datos para una encuesta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Survey {
+    pub survey_id: u64,
+    pub creator: Address,
+    pub name: String,
+    pub description: String,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub candidates: Vec<Address>,
+    pub created_at: u64,
+}
+
+// Estructura para los resultados de votación
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteResult {
+    pub candidate: Address,
+    pub votes: u64,
+}
+
+// Configuración del depósito reembolsable para crear encuestas
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositConfig {
+    pub token: Address,
+    pub amount: i128,
+    pub min_turnout: u64, // Votos mínimos para recuperar el depósito
+}
+
+// Política de creación de encuestas
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CreationPolicy {
+    Open,                    // Cualquier dirección autenticada puede crear encuestas
+    Allowlist,               // Solo creadores autorizados por el administrador
+    Deposit(DepositConfig),  // Requiere un depósito reembolsable en un token
+}
+
+// Depósito bloqueado por una encuesta concreta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurveyDeposit {
+    pub depositor: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub min_turnout: u64,
+}
+
+// Fee por voto de una encuesta; amount 0 hace la encuesta gratuita
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurveyFee {
+    pub token: Address,
+    pub amount: i128,
+}
+
+// Boleta que el votante firma fuera de la cadena para vote_signed; max_fee es
+// lo máximo que el relayer pagará de fee por ella
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedBallot {
+    pub survey_id: u64,
+    pub candidate: Address,
+    pub nonce: u64,
+    pub max_fee: i128,
+}
+
+// Fees cobrados por una encuesta en un token. Al finalizar se reparten entre la
+// plataforma y el creador; hasta entonces ambas partes valen 0
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurveyRevenue {
+    pub token: Address,
+    pub collected: i128,      // Fees cobrados menos reembolsos
+    pub platform_share: i128, // Parte acreditada a la tesorería
+    pub creator_share: i128,  // Parte que reclama el creador
+    pub creator_claimed: bool,
+}
+
+// Monto en un token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenAmount {
+    pub token: Address,
+    pub amount: i128,
+}
+
+// Cómo se reparte un fondo de premios al finalizar
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PrizeDistribution {
+    Participants, // Partes iguales para cada votante directo, que la reclama con claim_reward
+    Winner,       // Todo al candidato más votado (partes iguales si hay empate)
+}
+
+// Fondo de premios aportado por el creador de una encuesta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrizePool {
+    pub funder: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub distribution: PrizeDistribution,
+    pub claim_period: u64,   // Segundos para reclamar tras finalizar
+    pub claim_deadline: u64, // Fijado al finalizar; 0 mientras tanto
+    pub reward: i128,        // Monto por votante o por ganador, fijado al finalizar
+    pub paid: i128,          // Total entregado hasta ahora
+    pub reclaimed: bool,     // El remanente ya volvió al creador
+}
+
+// Momento desde el que los getters muestran conteos y votos
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResultsVisibility {
+    Live,          // Siempre
+    AfterClose,    // Pasada la fecha de cierre
+    AfterFinalize, // Una vez finalizada la encuesta
+}
+
+// Política de resultados de una encuesta; con privileged_access el creador y
+// los auditores leen los conteos antes de tiempo con get_results_as
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResultsPolicy {
+    pub visibility: ResultsVisibility,
+    pub privileged_access: bool,
+}
+
+// Electorado de una encuesta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Eligibility {
+    Open,                   // Cualquier dirección puede votar
+    Allowlist,              // Solo direcciones agregadas con add_eligible_voters
+    MerkleRoot(BytesN<32>), // Raíz de un árbol Merkle de direcciones elegibles
+}
+
+// Alcance de una delegación de voto
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DelegationScope {
+    Global,      // Todas las encuestas sin delegación específica
+    Survey(u64), // Solo la encuesta indicada; tiene prioridad sobre Global
+}
+
+// Categoría de una encuesta; Custom permite categorías nuevas sin actualizar el contrato
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SurveyCategory {
+    General,
+    Election,
+    Opinion,
+    Community,
+    Education,
+    Business,
+    Custom(Symbol),
+}
+
+// Metadatos de presentación de una encuesta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurveyMetadata {
+    pub category: SurveyCategory,
+    pub tags: Vec<Symbol>,
+    pub metadata_uri: String,     // ipfs://<cid> de un JSON con imagen y documentos
+    pub content_hash: BytesN<32>, // sha256 del JSON referenciado por metadata_uri
+}
+
+// Pregunta de un cuestionario; se responde con el índice de una opción
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Question {
+    pub prompt: String,
+    pub options: Vec<String>,
+}
+
+// Resultado de una opción de una pregunta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptionResult {
+    pub label: String,
+    pub votes: u64,
+}
+
+// Registros con los que se validan votantes y candidatos
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Registries {
+    pub user_registry: Address,
+    pub candidate_registry: Address,
+}
+
+// Certificado de resultados emitido al finalizar una encuesta.
+// `hash` es sha256 del XDR de la tupla (survey_id, creator, name, description,
+// start_date, end_date, created_at, candidates, [(candidate, votes)], voters),
+// con los resultados en el orden de `get_results` y los votantes en el de `get_voters`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResultCertificate {
+    pub survey_id: u64,
+    pub hash: BytesN<32>,
+    pub ledger: u32,
+    pub total_votes: u64,
+}
+
+// Acumulador de los recibos de una encuesta: head = sha256(head anterior || recibo),
+// partiendo de 32 bytes a cero, en el orden de los eventos ReceiptIssued
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptChain {
+    pub head: BytesN<32>,
+    pub length: u64,
+}
+
+// Límites de los cuestionarios, compartidos con las plantillas de survey_series
+pub const MAX_QUESTIONS: u32 = 20;
+pub const MAX_OPTIONS: u32 = 16;
+
+// Claves de almacenamiento de SurveyContract
+#[contracttype(export = false)]
+pub enum DataKey {
+    Survey(u64),                   // survey_id -> Survey
+    Vote(u64, Address),            // (survey_id, voter) -> candidate voted for
+    VoteCount(u64, Address),       // (survey_id, candidate) -> vote count
+    VoterList(u64),                // survey_id -> Vec<Address> of voters
+    SurveyCount,                   // Total number of surveys
+    VoteFee,                       // Fee in stroops (1 XLM = 10^7 stroops) for voting
+    Admin,                         // Administrator address
+    CreationPolicy,                // Current CreationPolicy
+    Creator(Address),              // creator -> allowed to create surveys (Allowlist)
+    Deposit(u64),                  // survey_id -> SurveyDeposit
+    Finalized(u64),                // survey_id -> bool
+    ContractVersion,               // Storage layout version
+    MigrationProgress,             // (next survey_id, last survey_id) of an unfinished migrate
+    Guardian,                      // Address allowed to pause the contract
+    Paused(PauseScope),            // scope -> bool
+    Eligibility(u64),              // survey_id -> Eligibility
+    Eligible(u64, Address),        // (survey_id, voter) -> bool (Allowlist)
+    Delegation(Address, DelegationScope), // (delegator, scope) -> delegate
+    Delegators(Address, DelegationScope), // (delegate, scope) -> Vec<Address> of delegators
+    CastBy(u64, Address),          // (survey_id, delegator) -> voter who cast the delegated vote
+    Nonce(Address),                // voter -> next nonce expected by vote_signed
+    Registries,                    // Registries used to validate voters and candidates
+    Certificate(u64),              // survey_id -> ResultCertificate
+    Metadata(u64),                 // survey_id -> SurveyMetadata
+    Questions(u64),                // survey_id -> Vec<Question> (cuestionarios)
+    Response(u64, Address),        // (survey_id, voter) -> Vec<u32> of chosen options
+    OptionCount(u64, u32, u32),    // (survey_id, question, option) -> vote count
+    Governance,                    // Governance contract that controls VoteFee
+    FeeToken,                      // Token in which the default VoteFee is charged
+    SurveyFee(u64),                // survey_id -> SurveyFee set by the creator
+    FeeExempt(Address),            // voter -> bool (never pays vote fees)
+    FeePaid(u64, Address),         // (survey_id, voter) -> SurveyFee paid, until refunded
+    FeePayer(u64, Address),        // (survey_id, voter) -> relayer that paid the fee, if not the voter
+    Cancelled(u64),                // survey_id -> bool
+    CreatorShareBps,               // Creator share of vote fees for new surveys, in basis points
+    SurveyShareBps(u64),           // survey_id -> creator share fixed at creation
+    Revenue(u64),                  // survey_id -> Vec<SurveyRevenue>, one per fee token
+    Treasury(Address),             // token -> platform balance available to withdraw
+    PrizePool(u64),                // survey_id -> PrizePool
+    RewardClaimed(u64, Address),   // (survey_id, voter) -> bool
+    ResultsPolicy(u64),            // survey_id -> ResultsPolicy (Live if unset)
+    Auditor(Address),              // auditor -> bool
+    BallotBox(u64),                // survey_id -> contrato que recibe los votos
+    BallotBoxContract(Address),    // contrato -> bool (urnas aprobadas por el administrador)
+    TallyRecorded(u64),            // survey_id -> u64 papeletas contadas por la urna
+    ReceiptChain(u64),             // survey_id -> ReceiptChain
+    Receipt(u64, BytesN<32>),      // (survey_id, recibo) -> posición en la cadena (desde 1)
+    Scheduler,                     // Contrato que crea encuestas en nombre de otros creadores
+}
+
+// Evento: se creó una encuesta
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurveyCreated {
+    #[topic]
+    pub survey_id: u64,
+    pub creator: Address,
+    pub name: String,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub candidates: Vec<Address>,
+}
+
+// Evento: se definieron o cambiaron los metadatos de una encuesta
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetadataUpdated {
+    #[topic]
+    pub survey_id: u64,
+    pub category: SurveyCategory,
+    pub tags: Vec<Symbol>,
+    pub metadata_uri: String,
+    pub content_hash: BytesN<32>,
+}
+
+// Evento: un votante emitió su voto directamente; sin candidato si la encuesta
+// oculta sus resultados (visibilidad distinta de Live)
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteCast {
+    #[topic]
+    pub survey_id: u64,
+    pub voter: Address,
+    pub candidate: Option<Address>,
+}
+
+// Evento: se emitió un recibo de voto; `head` es la cadena tras añadirlo
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptIssued {
+    #[topic]
+    pub survey_id: u64,
+    pub receipt: BytesN<32>,
+    pub head: BytesN<32>,
+}
+
+// Evento: un votante respondió un cuestionario
+// Las respuestas solo se publican si la encuesta muestra sus resultados en vivo
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResponseSubmitted {
+    #[topic]
+    pub survey_id: u64,
+    pub voter: Address,
+    pub answers: Option<Vec<u32>>,
+}
+
+// Evento: cambió el conteo de un candidato (incluye votos delegados y reasignados)
+// Si la encuesta oculta sus resultados, solo se publica al finalizar, con el conteo completo
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TallyChanged {
+    #[topic]
+    pub survey_id: u64,
+    pub candidate: Address,
+    pub delta: i64,
+}
+
+// Evento: cambió el fee de votación
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteFeeChanged {
+    pub fee: i128,
+}
+
+// Evento: se canceló una encuesta; los fees pagados se pueden reclamar
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurveyCancelled {
+    #[topic]
+    pub survey_id: u64,
+    pub cancelled_by: Address,
+}
+
+// Evento: se devolvió a un votante el fee de una encuesta cancelada
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeRefunded {
+    #[topic]
+    pub survey_id: u64,
+    pub voter: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+// Evento: el administrador retiró fondos de la tesorería
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWithdrawn {
+    #[topic]
+    pub token: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+// Evento: el creador reclamó su parte de los fees de una encuesta
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorRevenueClaimed {
+    #[topic]
+    pub survey_id: u64,
+    pub creator: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+// Evento: el creador depositó un fondo de premios
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrizePoolFunded {
+    #[topic]
+    pub survey_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub distribution: PrizeDistribution,
+}
+
+// Evento: se pagó un premio a un votante o a un candidato ganador
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardPaid {
+    #[topic]
+    pub survey_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+// Evento: el remanente del fondo de premios volvió al creador
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrizePoolReclaimed {
+    #[topic]
+    pub survey_id: u64,
+    pub funder: Address,
+    pub amount: i128,
+}
+
+// Evento: se finalizó una encuesta
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurveyFinalized {
+    #[topic]
+    pub survey_id: u64,
+    pub total_votes: u64,
+}
+
+// Errores tipados de SurveyContract
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SurveyError {
+    Paused = 1,
+    Unauthorized = 2,
+    NotEligible = 3,
+    InvalidNonce = 4,
+    NotRegistered = 5,
+    InvalidMetadata = 6,
+    InvalidResponse = 7,
+    NotCancelled = 8,
+    NothingToRefund = 9,
+    InsufficientTreasury = 10,
+    NotFinalized = 11,
+    NothingToClaim = 12,
+    NoPrizePool = 13,
+    ClaimPeriodOver = 14,
+    ClaimPeriodActive = 15,
+    ResultsHidden = 16,
+}
+
+#[contractclient(name = "SurveyClient")]
+pub trait SurveyInterface {
+    fn create_survey(
+        env: Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        candidates: Vec<Address>,
+    ) -> u64;
+    fn create_questionnaire(
+        env: Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        questions: Vec<Question>,
+    ) -> u64;
+    fn create_survey_for(
+        env: Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        candidates: Vec<Address>,
+    ) -> u64;
+    fn create_questionnaire_for(
+        env: Env,
+        creator: Address,
+        name: String,
+        description: String,
+        start_date: u64,
+        end_date: u64,
+        questions: Vec<Question>,
+    ) -> u64;
+    fn set_scheduler(env: Env, scheduler: Address);
+    fn get_scheduler(env: Env) -> Option<Address>;
+    fn vote(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        candidate: Address,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32>;
+    fn vote_signed(
+        env: Env,
+        ballot: SignedBallot,
+        voter_pubkey: BytesN<32>,
+        relayer: Address,
+        signature: BytesN<64>,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32>;
+    fn get_nonce(env: Env, voter: Address) -> u64;
+    fn submit_response(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        answers: Vec<u32>,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> BytesN<32>;
+    fn finalize_survey(env: Env, survey_id: u64) -> bool;
+    fn set_registries(env: Env, user_registry: Address, candidate_registry: Address);
+    fn get_registries(env: Env) -> Option<Registries>;
+    fn set_guardian(env: Env, guardian: Address);
+    fn get_guardian(env: Env) -> Option<Address>;
+    fn pause(env: Env, caller: Address, scope: PauseScope);
+    fn unpause(env: Env, caller: Address, scope: PauseScope);
+    fn is_paused(env: Env, scope: PauseScope) -> bool;
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+    fn migrate(env: Env, limit: u32) -> bool;
+    fn version(env: Env) -> u32;
+    fn set_eligibility(env: Env, survey_id: u64, eligibility: Eligibility);
+    fn add_eligible_voters(env: Env, survey_id: u64, voters: Vec<Address>);
+    fn get_eligibility(env: Env, survey_id: u64) -> Eligibility;
+    fn is_eligible(
+        env: Env,
+        survey_id: u64,
+        voter: Address,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> bool;
+    fn delegate(env: Env, delegator: Address, delegate: Address, scope: DelegationScope);
+    fn undelegate(env: Env, delegator: Address, scope: DelegationScope);
+    fn get_delegate(env: Env, delegator: Address, scope: DelegationScope) -> Option<Address>;
+    fn get_cast_by(env: Env, survey_id: u64, delegator: Address) -> Option<Address>;
+    fn set_creation_policy(env: Env, policy: CreationPolicy);
+    fn add_creator(env: Env, creator: Address);
+    fn remove_creator(env: Env, creator: Address);
+    fn get_creation_policy(env: Env) -> CreationPolicy;
+    fn is_allowed_creator(env: Env, creator: Address) -> bool;
+    fn get_deposit(env: Env, survey_id: u64) -> Option<SurveyDeposit>;
+    fn is_finalized(env: Env, survey_id: u64) -> bool;
+    fn get_certificate(env: Env, survey_id: u64) -> Option<ResultCertificate>;
+    fn update_metadata(env: Env, survey_id: u64, metadata: SurveyMetadata);
+    fn get_metadata(env: Env, survey_id: u64) -> Option<SurveyMetadata>;
+    fn get_survey(env: Env, survey_id: u64) -> Option<Survey>;
+    fn has_voted(env: Env, survey_id: u64, voter: Address) -> bool;
+    fn get_vote(env: Env, survey_id: u64, voter: Address) -> Option<Address>;
+    fn get_results(env: Env, survey_id: u64) -> Vec<VoteResult>;
+    fn get_results_as(env: Env, survey_id: u64, viewer: Address) -> Vec<VoteResult>;
+    fn get_vote_as(env: Env, survey_id: u64, voter: Address, viewer: Address) -> Option<Address>;
+    fn set_results_visibility(
+        env: Env,
+        survey_id: u64,
+        visibility: ResultsVisibility,
+        privileged_access: bool,
+    );
+    fn get_results_policy(env: Env, survey_id: u64) -> ResultsPolicy;
+    fn add_auditor(env: Env, auditor: Address);
+    fn remove_auditor(env: Env, auditor: Address);
+    fn is_auditor(env: Env, auditor: Address) -> bool;
+    fn add_ballot_box_contract(env: Env, ballot_box: Address);
+    fn remove_ballot_box_contract(env: Env, ballot_box: Address);
+    fn set_ballot_box(env: Env, survey_id: u64, ballot_box: Address);
+    fn get_ballot_box(env: Env, survey_id: u64) -> Option<Address>;
+    fn record_tally(env: Env, survey_id: u64, counts: Vec<u64>, voters: Vec<Address>);
+    fn record_receipt(env: Env, survey_id: u64, voter: Address, commitment: BytesN<32>) -> BytesN<32>;
+    fn verify_receipt(env: Env, survey_id: u64, receipt: BytesN<32>) -> bool;
+    fn get_receipt_chain(env: Env, survey_id: u64) -> ReceiptChain;
+    fn get_questions(env: Env, survey_id: u64) -> Vec<Question>;
+    fn get_response(env: Env, survey_id: u64, voter: Address) -> Option<Vec<u32>>;
+    fn get_question_results(env: Env, survey_id: u64, question: u32) -> Vec<OptionResult>;
+    fn get_total_votes(env: Env, survey_id: u64) -> u64;
+    fn get_voters(env: Env, survey_id: u64) -> Vec<Address>;
+    fn get_survey_count(env: Env) -> u64;
+    fn get_vote_fee(env: Env) -> i128;
+    fn set_governance(env: Env, governance: Address);
+    fn get_governance(env: Env) -> Option<Address>;
+    fn set_vote_fee(env: Env, fee: i128);
+    fn set_fee_token(env: Env, token: Address);
+    fn get_fee_token(env: Env) -> Option<Address>;
+    fn set_survey_fee(env: Env, survey_id: u64, token: Address, amount: i128);
+    fn get_survey_fee(env: Env, survey_id: u64) -> Option<SurveyFee>;
+    fn add_fee_exempt(env: Env, voters: Vec<Address>);
+    fn remove_fee_exempt(env: Env, voter: Address);
+    fn is_fee_exempt(env: Env, voter: Address) -> bool;
+    fn cancel_survey(env: Env, survey_id: u64, caller: Address);
+    fn is_cancelled(env: Env, survey_id: u64) -> bool;
+    fn claim_refund(env: Env, survey_id: u64, voter: Address) -> i128;
+    fn set_creator_share(env: Env, creator_bps: u32);
+    fn get_creator_share(env: Env) -> u32;
+    fn get_survey_revenue(env: Env, survey_id: u64) -> Vec<SurveyRevenue>;
+    fn get_treasury_balance(env: Env, token: Address) -> i128;
+    fn withdraw(env: Env, token: Address, to: Address, amount: i128);
+    fn claim_creator_revenue(env: Env, survey_id: u64) -> Vec<TokenAmount>;
+    fn fund_prize_pool(
+        env: Env,
+        survey_id: u64,
+        token: Address,
+        amount: i128,
+        distribution: PrizeDistribution,
+        claim_period: u64,
+    );
+    fn get_prize_pool(env: Env, survey_id: u64) -> Option<PrizePool>;
+    fn claim_reward(env: Env, survey_id: u64, voter: Address) -> i128;
+    fn reclaim_prize_pool(env: Env, survey_id: u64) -> i128;
+}
@@ -0,0 +1,94 @@
+use soroban_sdk::{
+    contractclient, contracterror, contractevent, contracttype, Address, BytesN, Env, String, Vec,
+};
+
+use crate::survey::{OptionResult, Question, VoteResult};
+
+// Plantilla de las instancias de una serie: encuesta de candidatos o cuestionario,
+// según cuál de las dos listas no esté vacía
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurveyTemplate {
+    pub name: String,
+    pub description: String,
+    pub candidates: Vec<Address>,
+    pub questions: Vec<Question>,
+    pub first_start: u64, // Inicio de la primera instancia
+    pub duration: u64,    // Segundos que dura cada instancia
+}
+
+// Serie de encuestas recurrentes; la instancia i empieza en first_start + i·interval_secs
+// y puede abrirse desde el inicio de la anterior
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Series {
+    pub series_id: u64,
+    pub creator: Address,
+    pub template: SurveyTemplate,
+    pub interval_secs: u64,
+    pub occurrences: u32,
+    pub opened: u32, // Turnos ya abiertos o saltados
+}
+
+// Claves de almacenamiento de SurveySeries
+#[contracttype(export = false)]
+pub enum DataKey {
+    Admin,              // Dirección del administrador
+    ContractVersion,    // Versión del formato de almacenamiento
+    Survey,             // Contrato de encuestas
+    SeriesCount,        // Contador de series
+    Series(u64),        // series_id -> Series
+    Instances(u64),     // series_id -> Vec<u64> ids de encuesta en orden
+}
+
+// Evento: se creó una serie
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeriesCreated {
+    #[topic]
+    pub series_id: u64,
+    pub creator: Address,
+    pub interval_secs: u64,
+    pub occurrences: u32,
+}
+
+// Evento: se abrió la siguiente instancia de una serie; `index` es su turno
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstanceOpened {
+    #[topic]
+    pub series_id: u64,
+    pub survey_id: u64,
+    pub index: u32,
+}
+
+// Errores tipados de SurveySeries
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SurveySeriesError {
+    NotDue = 1,
+    SeriesComplete = 2,
+    InvalidSchedule = 3,
+}
+
+#[contractclient(name = "SurveySeriesClient")]
+pub trait SurveySeriesInterface {
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+    fn migrate(env: Env);
+    fn version(env: Env) -> u32;
+    fn create_survey_series(
+        env: Env,
+        creator: Address,
+        template: SurveyTemplate,
+        interval_secs: u64,
+        occurrences: u32,
+    ) -> u64;
+    fn open_next_instance(env: Env, series_id: u64) -> u64;
+    fn get_series(env: Env, series_id: u64) -> Option<Series>;
+    fn get_series_count(env: Env) -> u64;
+    fn get_instances(env: Env, series_id: u64) -> Vec<u64>;
+    fn get_series_results(env: Env, series_id: u64) -> Vec<VoteResult>;
+    fn get_series_question_results(env: Env, series_id: u64, question: u32) -> Vec<OptionResult>;
+    fn get_series_turnout(env: Env, series_id: u64) -> Vec<u64>;
+}
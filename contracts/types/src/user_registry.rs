@@ -0,0 +1,73 @@
+use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, String};
+#[cfg(feature = "user_registry")]
+use soroban_sdk::{contracterror, contractevent};
+
+use crate::PauseScope;
+
+// Estructura de datos para almacenar información del usuario
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct User {
+    pub wallet: Address,
+    pub first_name: String,
+    pub paternal_last_name: String,
+    pub maternal_last_name: String,
+    pub phone: String,
+    pub email: String,
+    pub timestamp: u64,
+}
+
+// Claves de almacenamiento de UserRegistry
+#[contracttype(export = false)]
+pub enum DataKey {
+    User(Address),      // Mapea wallet address -> User
+    UserCount,          // Contador total de usuarios
+    Admin,              // Dirección del administrador
+    ContractVersion,    // Versión del formato de almacenamiento
+    Guardian,           // Dirección autorizada para pausar el contrato
+    Paused(PauseScope), // scope -> bool
+}
+
+// Evento: se registró un usuario; sus datos personales no se publican
+#[cfg(feature = "user_registry")]
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserRegistered {
+    #[topic]
+    pub wallet: Address,
+    pub timestamp: u64,
+}
+
+// Errores tipados de UserRegistry
+#[cfg(feature = "user_registry")]
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum UserRegistryError {
+    Paused = 1,
+    Unauthorized = 2,
+}
+
+#[contractclient(name = "UserRegistryClient")]
+pub trait UserRegistryInterface {
+    fn set_guardian(env: Env, guardian: Address);
+    fn get_guardian(env: Env) -> Option<Address>;
+    fn pause(env: Env, caller: Address, scope: PauseScope);
+    fn unpause(env: Env, caller: Address, scope: PauseScope);
+    fn is_paused(env: Env, scope: PauseScope) -> bool;
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+    fn migrate(env: Env);
+    fn version(env: Env) -> u32;
+    fn register_user(
+        env: Env,
+        wallet: Address,
+        first_name: String,
+        paternal_last_name: String,
+        maternal_last_name: String,
+        phone: String,
+        email: String,
+    ) -> bool;
+    fn get_user(env: Env, wallet: Address) -> Option<User>;
+    fn user_exists(env: Env, wallet: Address) -> bool;
+    fn get_user_count(env: Env) -> u64;
+}
@@ -1,34 +1,90 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
-
-// Estructura de datos para almacenar información del usuario
-#[contracttype]
-#[derive(Clone)]
-pub struct User {
-    pub wallet: Address,
-    pub first_name: String,
-    pub paternal_last_name: String,
-    pub maternal_last_name: String,
-    pub phone: String,
-    pub email: String,
-    pub timestamp: u64,
-}
+use dappcenvote_types::{
+    user_registry::{DataKey, User, UserRegistered, UserRegistryError, UserRegistryInterface},
+    PauseScope,
+};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, String};
 
-// Clave para el contador de usuarios
-#[contracttype]
-pub enum DataKey {
-    User(Address), // Mapea wallet address -> User
-    UserCount,     // Contador total de usuarios
-}
+const CONTRACT_VERSION: u32 = 1;
 
 #[contract]
 pub struct UserRegistry;
 
 #[contractimpl]
 impl UserRegistry {
+    /// Inicializa el contrato al desplegarlo, con la dirección del administrador
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        env.storage().instance().extend_ttl(100, 8640000);
+    }
+}
+
+#[contractimpl]
+impl UserRegistryInterface for UserRegistry {
+    /// Define la dirección guardián que puede pausar el contrato (solo administrador)
+    fn set_guardian(env: Env, guardian: Address) {
+        Self::require_admin(&env);
+
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+    }
+
+    /// Obtiene la dirección guardián (si existe)
+    fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Guardian)
+    }
+
+    /// Pausa un ámbito de operación (administrador o guardián)
+    fn pause(env: Env, caller: Address, scope: PauseScope) {
+        Self::require_admin_or_guardian(&env, &caller);
+
+        env.storage().instance().set(&DataKey::Paused(scope), &true);
+    }
+
+    /// Reanuda un ámbito de operación pausado (administrador o guardián)
+    fn unpause(env: Env, caller: Address, scope: PauseScope) {
+        Self::require_admin_or_guardian(&env, &caller);
+
+        env.storage().instance().remove(&DataKey::Paused(scope));
+    }
+
+    /// Verifica si un ámbito de operación está pausado
+    fn is_paused(env: Env, scope: PauseScope) -> bool {
+        let key = DataKey::Paused(scope);
+        env.storage().instance().get(&key).unwrap_or(false)
+    }
+
+    /// Reemplaza el código del contrato conservando su almacenamiento (solo administrador)
+    /// Debe seguirse de `migrate` si la nueva versión cambia el formato de los datos
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Migra el almacenamiento a la versión de este código (solo administrador)
+    /// Sin efecto si el almacenamiento ya está en esa versión
+    /// La versión 1 es el formato original, por lo que aún no hay transformaciones
+    fn migrate(env: Env) {
+        Self::require_admin(&env);
+
+        let version = Self::version(env.clone());
+        if version >= CONTRACT_VERSION {
+            return; // Ya está al día; repetir la llamada no es un error
+        }
+
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+    }
+
+    /// Obtiene la versión del formato de almacenamiento
+    fn version(env: Env) -> u32 {
+        let key = DataKey::ContractVersion;
+        env.storage().instance().get(&key).unwrap_or(1)
+    }
+
     /// Registra un nuevo usuario en la blockchain
     /// Retorna true si el registro fue exitoso
-    pub fn register_user(
+    fn register_user(
         env: Env,
         wallet: Address,
         first_name: String,
@@ -39,6 +95,7 @@ impl UserRegistry {
     ) -> bool {
         // Verificar que el wallet esté autenticado
         wallet.require_auth();
+        Self::require_not_paused(&env, PauseScope::Registration);
 
         // Verificar si el usuario ya existe
         let key = DataKey::User(wallet.clone());
@@ -69,37 +126,70 @@ impl UserRegistry {
         env.storage().persistent().set(&count_key, &(count + 1));
         env.storage().persistent().extend_ttl(&count_key, 100, 8640000);
 
+        UserRegistered { wallet, timestamp: user.timestamp }.publish(&env);
+
         true
     }
 
     /// Obtiene los datos de un usuario por su wallet address
-    pub fn get_user(env: Env, wallet: Address) -> Option<User> {
+    fn get_user(env: Env, wallet: Address) -> Option<User> {
         let key = DataKey::User(wallet);
         env.storage().persistent().get(&key)
     }
 
     /// Verifica si un usuario está registrado
-    pub fn user_exists(env: Env, wallet: Address) -> bool {
+    fn user_exists(env: Env, wallet: Address) -> bool {
         let key = DataKey::User(wallet);
         env.storage().persistent().has(&key)
     }
 
     /// Obtiene el número total de usuarios registrados
-    pub fn get_user_count(env: Env) -> u64 {
+    fn get_user_count(env: Env) -> u64 {
         let key = DataKey::UserCount;
         env.storage().persistent().get(&key).unwrap_or(0)
     }
 }
 
+impl UserRegistry {
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        admin
+    }
+
+    fn require_admin_or_guardian(env: &Env, caller: &Address) {
+        caller.require_auth();
+
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        let guardian: Option<Address> = env.storage().instance().get(&DataKey::Guardian);
+        if admin.as_ref() != Some(caller) && guardian.as_ref() != Some(caller) {
+            panic_with_error!(env, UserRegistryError::Unauthorized);
+        }
+    }
+
+    fn require_not_paused(env: &Env, scope: PauseScope) {
+        if Self::is_paused(env.clone(), scope) {
+            panic_with_error!(env, UserRegistryError::Paused);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use soroban_sdk::{testutils::Address as _, Env};
 
+    mod user_registry_wasm {
+        soroban_sdk::contractimport!(file = "testdata/user_registry.wasm");
+    }
+
     #[test]
     fn test_register_user() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, UserRegistry);
+        env.mock_all_auths();
+        let contract_id = env.register(UserRegistry, (Address::generate(&env),));
         let client = UserRegistryClient::new(&env, &contract_id);
 
         let user_wallet = Address::generate(&env);
@@ -122,7 +212,8 @@ mod test {
     #[test]
     fn test_get_user() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, UserRegistry);
+        env.mock_all_auths();
+        let contract_id = env.register(UserRegistry, (Address::generate(&env),));
         let client = UserRegistryClient::new(&env, &contract_id);
 
         let user_wallet = Address::generate(&env);
@@ -147,7 +238,8 @@ mod test {
     #[test]
     fn test_duplicate_registration() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, UserRegistry);
+        env.mock_all_auths();
+        let contract_id = env.register(UserRegistry, (Address::generate(&env),));
         let client = UserRegistryClient::new(&env, &contract_id);
 
         let user_wallet = Address::generate(&env);
@@ -177,4 +269,102 @@ mod test {
         // Debe haber solo 1 usuario
         assert_eq!(client.get_user_count(), 1);
     }
+
+    #[test]
+    fn test_upgrade_preserves_users() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(UserRegistry, (admin.clone(),));
+        let client = UserRegistryClient::new(&env, &contract_id);
+
+        let user_wallet = Address::generate(&env);
+
+        client.register_user(
+            &user_wallet,
+            &String::from_str(&env, "Ana"),
+            &String::from_str(&env, "Torres"),
+            &String::from_str(&env, "Diaz"),
+            &String::from_str(&env, "1112223333"),
+            &String::from_str(&env, "ana@example.com"),
+        );
+
+        // Actualizar al código compilado y verificar que los datos persisten
+        let new_hash = env.deployer().upload_contract_wasm(user_registry_wasm::WASM);
+        client.upgrade(&new_hash);
+
+        let upgraded = user_registry_wasm::Client::new(&env, &contract_id);
+        assert_eq!(upgraded.version(), CONTRACT_VERSION);
+        assert!(upgraded.user_exists(&user_wallet));
+        assert_eq!(upgraded.get_user_count(), 1);
+    }
+
+    #[test]
+    fn test_migrate_current_version() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(UserRegistry, (Address::generate(&env),));
+        let client = UserRegistryClient::new(&env, &contract_id);
+
+        // Repetir la migración no falla ni cambia la versión
+        client.migrate();
+        assert_eq!(client.version(), CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn test_pause_registration() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(UserRegistry, (admin.clone(),));
+        let client = UserRegistryClient::new(&env, &contract_id);
+
+        let user_wallet = Address::generate(&env);
+        client.pause(&admin, &PauseScope::Registration);
+
+        let result = client.try_register_user(
+            &user_wallet,
+            &String::from_str(&env, "Luis"),
+            &String::from_str(&env, "Ramirez"),
+            &String::from_str(&env, "Vega"),
+            &String::from_str(&env, "4445556666"),
+            &String::from_str(&env, "luis@example.com"),
+        );
+        assert_eq!(result, Err(Ok(UserRegistryError::Paused.into())));
+        assert!(!client.user_exists(&user_wallet));
+        assert_eq!(client.get_user_count(), 0);
+
+        client.unpause(&admin, &PauseScope::Registration);
+        assert!(client.register_user(
+            &user_wallet,
+            &String::from_str(&env, "Luis"),
+            &String::from_str(&env, "Ramirez"),
+            &String::from_str(&env, "Vega"),
+            &String::from_str(&env, "4445556666"),
+            &String::from_str(&env, "luis@example.com"),
+        ));
+    }
+
+    #[test]
+    fn test_shared_client() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(UserRegistry, (Address::generate(&env),));
+
+        // El cliente de dappcenvote-types habla con el contrato desplegado
+        let client = dappcenvote_types::user_registry::UserRegistryClient::new(&env, &contract_id);
+        let user_wallet = Address::generate(&env);
+        client.register_user(
+            &user_wallet,
+            &String::from_str(&env, "Sofia"),
+            &String::from_str(&env, "Cruz"),
+            &String::from_str(&env, "Ortiz"),
+            &String::from_str(&env, "7778889999"),
+            &String::from_str(&env, "sofia@example.com"),
+        );
+
+        let user = client.get_user(&user_wallet).unwrap();
+        assert_eq!(user.first_name, String::from_str(&env, "Sofia"));
+        assert_eq!(client.get_user_count(), 1);
+    }
 }